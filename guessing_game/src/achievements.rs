@@ -0,0 +1,62 @@
+use serde::Deserialize;
+
+use crate::game::{Difficulty, GameRecord};
+use crate::profile::Profile;
+
+// the definitions live in achievements.toml and get baked into the binary, so the game loop
+// never needs to know which achievements exist
+const BUNDLED: &str = include_str!("../achievements.toml");
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Condition {
+    Wins { at_least: u32 },
+    AttemptsAtMost { attempts: usize },
+    Optimal,
+    Streak { at_least: u32 },
+    Difficulty { level: Difficulty },
+    NoRedundantGuesses,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Achievement {
+    pub id: String,
+    pub name: String,
+    pub description: String,
+    pub xp: u32,
+    pub condition: Condition,
+}
+
+#[derive(Deserialize)]
+struct AchievementFile {
+    achievement: Vec<Achievement>,
+}
+
+pub fn load() -> Vec<Achievement> {
+    let file: AchievementFile = toml::from_str(BUNDLED).expect("achievements.toml is invalid");
+    file.achievement
+}
+
+impl Condition {
+    // `profile` has already been updated with the result of `game` by the time this is called
+    fn is_met(&self, profile: &Profile, game: &GameRecord) -> bool {
+        match self {
+            Condition::Wins { at_least } => profile.wins >= *at_least,
+            Condition::Streak { at_least } => profile.streak >= *at_least,
+            // everything below is about the game that was just played, so it has to be a win
+            _ if !game.won => false,
+            Condition::AttemptsAtMost { attempts } => game.attempts <= *attempts,
+            Condition::Optimal => game.attempts <= game.difficulty.optimal_attempts(),
+            Condition::Difficulty { level } => game.difficulty == *level,
+            Condition::NoRedundantGuesses => game.redundant_guesses == 0,
+        }
+    }
+}
+
+// returns the achievements that were unlocked by this game (and not before)
+pub fn check<'a>(all: &'a [Achievement], profile: &Profile, game: &GameRecord) -> Vec<&'a Achievement> {
+    all.iter()
+        .filter(|a| !profile.unlocked.contains(&a.id))
+        .filter(|a| a.condition.is_met(profile, game))
+        .collect()
+}
@@ -0,0 +1,33 @@
+// reference tournament bot: always guesses the middle of what's left.
+// see the protocol in src/tournament.rs
+use std::io::{self, BufRead, Write};
+
+fn main() {
+    let stdin = io::stdin();
+    let mut stdout = io::stdout();
+
+    let mut low: u32 = 0;
+    let mut high: u32 = 0;
+    let mut guess: u32 = 0;
+
+    for line in stdin.lock().lines() {
+        let line = line.expect("failed to read line");
+        let words: Vec<&str> = line.split_whitespace().collect();
+
+        match words.as_slice() {
+            ["start", l, h] => {
+                low = l.parse().expect("bad low bound");
+                high = h.parse().expect("bad high bound");
+            }
+            ["low"] => low = guess + 1,
+            ["high"] => high = guess - 1,
+            ["win"] => continue,
+            ["end"] => break,
+            _ => panic!("unexpected message `{}`", line),
+        }
+
+        guess = low + (high - low) / 2;
+        writeln!(stdout, "{}", guess).expect("failed to write guess");
+        stdout.flush().expect("failed to flush");
+    }
+}
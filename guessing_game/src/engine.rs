@@ -0,0 +1,74 @@
+use std::cmp::Ordering;
+use std::ops::{Bound, RangeBounds};
+
+type Judge<T> = Box<dyn Fn(&T, &T) -> Ordering>;
+
+// the guessing part of the game without any of the io. `cmp` works for anything that's `Ord`,
+// so the same engine runs the number game, the word game and every `--kind`
+pub struct Engine<T: Ord> {
+    secret: T,
+    // overrides `cmp` for deciding the feedback, e.g. to let a float within a tolerance win
+    judge: Option<Judge<T>>,
+    attempts: usize,
+    redundant_guesses: usize,
+    // where the secret can still be, based on the feedback so far
+    low: Bound<T>,
+    high: Bound<T>,
+}
+
+impl<T: Ord> Engine<T> {
+    pub fn new(secret: T, low: Bound<T>, high: Bound<T>) -> Engine<T> {
+        Engine {
+            secret,
+            judge: None,
+            attempts: 0,
+            redundant_guesses: 0,
+            low,
+            high,
+        }
+    }
+
+    pub fn with_judge(mut self, judge: impl Fn(&T, &T) -> Ordering + 'static) -> Engine<T> {
+        self.judge = Some(Box::new(judge));
+        self
+    }
+
+    pub fn guess(&mut self, guess: T) -> Ordering {
+        self.attempts += 1;
+        // a guess outside the window is "redundant" since we already told you the answer
+        if !(self.low.as_ref(), self.high.as_ref()).contains(&guess) {
+            self.redundant_guesses += 1;
+        }
+
+        let ordering = match &self.judge {
+            Some(judge) => judge(&guess, &self.secret),
+            None => guess.cmp(&self.secret),
+        };
+        match ordering {
+            Ordering::Less => {
+                if (self.low.as_ref(), Bound::Unbounded).contains(&guess) {
+                    self.low = Bound::Excluded(guess);
+                }
+            }
+            Ordering::Greater => {
+                if (Bound::Unbounded, self.high.as_ref()).contains(&guess) {
+                    self.high = Bound::Excluded(guess);
+                }
+            }
+            Ordering::Equal => {}
+        }
+        ordering
+    }
+
+    pub fn secret(&self) -> &T {
+        &self.secret
+    }
+
+    pub fn attempts(&self) -> usize {
+        self.attempts
+    }
+
+    pub fn redundant_guesses(&self) -> usize {
+        self.redundant_guesses
+    }
+}
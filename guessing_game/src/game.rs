@@ -0,0 +1,130 @@
+use std::fmt;
+use std::io;
+use rand::Rng;
+use std::cmp::Ordering;
+use std::ops::Bound;
+use labs::format;
+use serde::{Deserialize, Serialize};
+
+use crate::engine::Engine;
+use crate::kinds::Kind;
+use crate::output::{Output, Scale};
+
+// how big the range of possible secrets is. the original game was always `Normal`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Difficulty {
+    Easy,
+    Normal,
+    Hard,
+    Insane,
+}
+
+impl Difficulty {
+    pub fn parse(s: &str) -> Option<Difficulty> {
+        match s.to_lowercase().as_str() {
+            "easy" => Some(Difficulty::Easy),
+            "normal" => Some(Difficulty::Normal),
+            "hard" => Some(Difficulty::Hard),
+            "insane" => Some(Difficulty::Insane),
+            _ => None,
+        }
+    }
+
+    pub fn max(self) -> u32 {
+        match self {
+            Difficulty::Easy => 10,
+            Difficulty::Normal => 100,
+            Difficulty::Hard => 1_000,
+            Difficulty::Insane => 1_000_000,
+        }
+    }
+
+    // the number of guesses bisection needs in the worst case, i.e. floor(log2(max)) + 1
+    pub fn optimal_attempts(self) -> usize {
+        let mut attempts = 0;
+        let mut remaining = self.max();
+        while remaining > 0 {
+            remaining /= 2;
+            attempts += 1;
+        }
+        attempts
+    }
+
+    // xp handed out for a win, before any achievements
+    pub fn win_xp(self) -> u32 {
+        match self {
+            Difficulty::Easy => 5,
+            Difficulty::Normal => 10,
+            Difficulty::Hard => 20,
+            Difficulty::Insane => 50,
+        }
+    }
+}
+
+// everything that happened in one game, which is what achievements get checked against
+pub struct GameRecord {
+    pub difficulty: Difficulty,
+    pub attempts: usize,
+    pub redundant_guesses: usize,
+    pub won: bool,
+}
+
+impl GameRecord {
+    fn new<T: Ord>(difficulty: Difficulty, engine: &Engine<T>, won: bool) -> GameRecord {
+        GameRecord {
+            difficulty,
+            attempts: engine.attempts(),
+            redundant_guesses: engine.redundant_guesses(),
+            won,
+        }
+    }
+}
+
+pub fn play(difficulty: Difficulty, out: &Output) -> GameRecord {
+    out.say(&format!("guess the number between 1 and {}", format::number(difficulty.max()).locale(out.locale)));
+
+    let chicken_dinner = rand::thread_rng().gen_range(1..=difficulty.max());
+    let mut engine = Engine::new(chicken_dinner, Bound::Included(1), Bound::Included(difficulty.max()));
+
+    let won = guess_kind(&mut engine, out);
+    GameRecord::new(difficulty, &engine, won)
+}
+
+// keeps asking for guesses until one wins (true) or the player quits (false). `read` turns what
+// was typed into a guess, or says what's wrong with it
+pub fn guess_loop<T: Ord + fmt::Display>(engine: &mut Engine<T>, out: &Output, scale: Scale, read: impl Fn(&str) -> Result<T, String>) -> bool {
+    loop {
+        let mut guess = String::new();
+        out.prompt();
+
+        io::stdin()
+            .read_line(&mut guess)
+            .expect("failed to read line");
+
+        if guess.trim() == "quit" {
+            out.reveal(engine.secret());
+            return false;
+        }
+
+        let guess = match read(guess.trim()) {
+            Ok(guess) => guess,
+            Err(e) => {
+                out.problem(&e);
+                continue;
+            }
+        };
+
+        let shown = guess.to_string();
+        let ordering = engine.guess(guess);
+        out.feedback(engine.attempts(), &shown, ordering, scale);
+        if ordering == Ordering::Equal {
+            return true;
+        }
+    }
+}
+
+// the loop for any `Kind`, which are all numbers on the screen and read the same way
+pub fn guess_kind<T: Kind>(engine: &mut Engine<T>, out: &Output) -> bool {
+    guess_loop(engine, out, Scale::Number, |input| T::read(input, out.locale))
+}
@@ -0,0 +1,275 @@
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::Bound;
+use labs::bigint::{BigInt, BigUint};
+use labs::format::{self, Locale};
+use labs::numerals;
+use rand::Rng;
+
+use crate::engine::Engine;
+use crate::game;
+use crate::output::Output;
+
+// anything the secret can be. `Ord` is what makes `cmp` (and so the feedback) work, the rest is
+// just reading guesses and picking a secret
+pub trait Kind: Ord + fmt::Display + Sized {
+    fn parse(input: &str) -> Result<Self, String>;
+    fn random_between(low: &Self, high: &Self) -> Self;
+
+    // how the range is written when the game starts. only numbers get grouped
+    fn show(&self, _locale: Locale) -> String {
+        self.to_string()
+    }
+
+    // what the player typed, which may be grouped the way `show` writes it, so "1,000,000" is
+    // fine when the range was shown like that. a separator in the wrong place still isn't
+    fn read(input: &str, locale: Locale) -> Result<Self, String> {
+        Self::parse(input).or_else(|e| match Self::parse(&input.replace(locale.group, "")) {
+            Ok(value) if !locale.group.is_empty() && value.show(locale) == input => Ok(value),
+            _ => Err(e),
+        })
+    }
+}
+
+macro_rules! integer_kind {
+    ($($t:ty),*) => {
+        $(
+            impl Kind for $t {
+                // digits, or the number in words like "forty two"
+                fn parse(input: &str) -> Result<$t, String> {
+                    input
+                        .parse()
+                        .ok()
+                        .or_else(|| numerals::parse_words(input).ok().and_then(|n| <$t>::try_from(n).ok()))
+                        .ok_or_else(|| format!("`{}` isn't a valid {}", input, stringify!($t)))
+                }
+
+                fn random_between(low: &$t, high: &$t) -> $t {
+                    rand::thread_rng().gen_range(*low..=*high)
+                }
+
+                fn show(&self, locale: Locale) -> String {
+                    format::number(*self).locale(locale).to_string()
+                }
+            }
+        )*
+    };
+}
+
+integer_kind!(u32, i64, u128);
+
+// for ranges past u128. rand can't pick a BigInt on its own, so this picks random limbs with as
+// many bits as the size of the range and throws away the ones that land past the end, which
+// happens less than half the time
+impl Kind for BigInt {
+    fn parse(input: &str) -> Result<BigInt, String> {
+        input.parse()
+    }
+
+    fn random_between(low: &BigInt, high: &BigInt) -> BigInt {
+        let size = (high - low + BigInt::from(1i64)).magnitude().clone();
+        let bits = size.bits();
+        let mut rng = rand::thread_rng();
+        loop {
+            let mut limbs: Vec<u32> = (0..bits.div_ceil(32)).map(|_| rng.gen()).collect();
+            if !bits.is_multiple_of(32) {
+                if let Some(top) = limbs.last_mut() {
+                    *top &= (1 << (bits % 32)) - 1;
+                }
+            }
+            let offset = BigUint::from_limbs(limbs);
+            if offset < size {
+                return low + BigInt::from(offset);
+            }
+        }
+    }
+
+    fn show(&self, locale: Locale) -> String {
+        format::number(self.clone()).locale(locale).to_string()
+    }
+}
+
+// an f64 that can go in an `Engine`. f64 is only `PartialOrd` because NaN isn't equal to
+// anything, so we use `total_cmp` and make sure NaN (and infinity, which would break picking a
+// random secret) never gets in here in the first place
+#[derive(Debug, Clone, Copy)]
+pub struct Float(f64);
+
+impl PartialEq for Float {
+    fn eq(&self, other: &Float) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Float {}
+
+impl PartialOrd for Float {
+    fn partial_cmp(&self, other: &Float) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Float {
+    fn cmp(&self, other: &Float) -> Ordering {
+        self.0.total_cmp(&other.0)
+    }
+}
+
+impl fmt::Display for Float {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Kind for Float {
+    fn parse(input: &str) -> Result<Float, String> {
+        let value: f64 = input.parse().map_err(|_| format!("`{}` isn't a valid number", input))?;
+        if value.is_nan() {
+            return Err(String::from("NaN isn't a valid guess"));
+        }
+        if value.is_infinite() {
+            return Err(String::from("infinity isn't a valid guess"));
+        }
+        Ok(Float(value))
+    }
+
+    fn random_between(low: &Float, high: &Float) -> Float {
+        let mut rng = rand::thread_rng();
+        if (high.0 - low.0).is_finite() {
+            return Float(rng.gen_range(low.0..=high.0));
+        }
+        // the width of a range like -1e308..=1e308 is infinite, which gen_range can't scale by.
+        // a weighted average of the ends never leaves the range, so it never overflows
+        let t: f64 = rng.gen_range(0.0..=1.0);
+        Float((low.0 * (1.0 - t) + high.0 * t).clamp(low.0, high.0))
+    }
+}
+
+// a calendar date, compared year first, then month, then day - which is exactly the order the
+// fields are declared in, so the derived `Ord` does the right thing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    year: i32,
+    month: u32,
+    day: u32,
+}
+
+fn is_leap_year(year: i32) -> bool {
+    (year % 4 == 0 && year % 100 != 0) || year % 400 == 0
+}
+
+fn days_in_month(year: i32, month: u32) -> u32 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+impl Date {
+    // days since 1970-01-01, using the usual civil calendar algorithm
+    fn to_days(self) -> i64 {
+        let year = (if self.month <= 2 { self.year - 1 } else { self.year }) as i64;
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = self.month as i64;
+        let day_of_year = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    fn from_days(days: i64) -> Date {
+        let days = days + 719_468;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+        let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+        let year = year_of_era + era * 400 + if month <= 2 { 1 } else { 0 };
+        Date { year: year as i32, month, day }
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+impl Kind for Date {
+    fn parse(input: &str) -> Result<Date, String> {
+        let invalid = || format!("`{}` isn't a date, use YYYY-MM-DD", input);
+        let parts: Vec<&str> = input.split('-').collect();
+        if parts.len() != 3 {
+            return Err(invalid());
+        }
+
+        let year: i32 = parts[0].parse().map_err(|_| invalid())?;
+        let month: u32 = parts[1].parse().map_err(|_| invalid())?;
+        let day: u32 = parts[2].parse().map_err(|_| invalid())?;
+
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return Err(format!("{} doesn't exist", input));
+        }
+        Ok(Date { year, month, day })
+    }
+
+    fn random_between(low: &Date, high: &Date) -> Date {
+        Date::from_days(rand::thread_rng().gen_range(low.to_days()..=high.to_days()))
+    }
+}
+
+pub struct Options {
+    pub kind: String,
+    pub min: Option<String>,
+    pub max: Option<String>,
+    pub tolerance: f64,
+}
+
+fn bound<T: Kind>(given: &Option<String>, default: &str, locale: Locale) -> Result<T, String> {
+    T::read(given.as_deref().unwrap_or(default), locale)
+}
+
+fn setup<T: Kind>(options: &Options, out: &Output, default_min: &str, default_max: &str) -> Result<Engine<T>, String> {
+    let min: T = bound(&options.min, default_min, out.locale)?;
+    let max: T = bound(&options.max, default_max, out.locale)?;
+    if min > max {
+        return Err(format!("--min {} is bigger than --max {}", min, max));
+    }
+
+    out.say(&format!("guess the {} between {} and {}", options.kind, min.show(out.locale), max.show(out.locale)));
+    let secret = T::random_between(&min, &max);
+    Ok(Engine::new(secret, Bound::Included(min), Bound::Included(max)))
+}
+
+// the non-default `--kind`s. these don't touch the player profile, since achievements like
+// "binary-search optimum" only make sense for the difficulty levels
+pub fn play(options: &Options, out: &Output) -> Result<(), String> {
+    match options.kind.as_str() {
+        "int" => game::guess_kind(&mut setup::<i64>(options, out, "-1000", "1000")?, out),
+        "u128" => game::guess_kind(&mut setup::<u128>(options, out, "0", "1000000000000000000000000000000")?, out),
+        "big" => game::guess_kind(&mut setup::<BigInt>(options, out, "0", "100_000_000_000_000_000_000_000_000_000_000_000_000_000_000_000_000")?, out),
+        "date" => game::guess_kind(&mut setup::<Date>(options, out, "1900-01-01", "2099-12-31")?, out),
+        "float" => {
+            if options.tolerance.is_nan() || options.tolerance < 0.0 {
+                return Err(String::from("--tolerance can't be negative"));
+            }
+            let tolerance = options.tolerance;
+            let engine = setup::<Float>(options, out, "0", "100")?;
+            out.say(&format!("anything within {} of the answer wins", tolerance));
+            game::guess_kind(&mut engine.with_judge(move |guess: &Float, secret: &Float| {
+                if (guess.0 - secret.0).abs() <= tolerance {
+                    Ordering::Equal
+                } else {
+                    guess.cmp(secret)
+                }
+            }), out)
+        }
+        other => return Err(format!("unknown kind `{}`", other)),
+    };
+    Ok(())
+}
@@ -1,36 +1,209 @@
+use std::env;
 use std::io;
-use rand::Rng;
-use std::cmp::Ordering;
+use std::time::Duration;
+
+mod achievements;
+mod engine;
+mod game;
+mod kinds;
+mod output;
+mod profile;
+mod tournament;
+mod words;
+
+use game::Difficulty;
+use labs::format::Locale;
+use output::{Output, Style};
+use profile::Profile;
+
+const USAGE: &str = "usage: guessing_game [--player NAME] [--difficulty easy|normal|hard|insane]
+       guessing_game --kind int|u128|big|date|float [--min X] [--max Y] [--tolerance T]
+       guessing_game words [--collation codepoint|unicode]
+       guessing_game tournament [--games N] [--seed N] [--difficulty LEVEL] [--timeout-ms N] BOT...
+
+any of these can also take --accessible (screen-reader friendly sentences), --bell (ring the
+terminal bell once for low, twice for high, three times for a win), --big (large ascii-art
+feedback) and --locale plain|en|de|fr|ch|in|rust (how big numbers are grouped, from LANG by
+default)";
+
+struct Options {
+    player: String,
+    difficulty: Difficulty,
+    kind: kinds::Options,
+}
+
+fn parse_difficulty(level: Option<String>) -> Result<Difficulty, String> {
+    let level = level.ok_or("--difficulty needs a level")?;
+    Difficulty::parse(&level).ok_or(format!("unknown difficulty `{}`", level))
+}
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: Option<String>) -> Result<T, String> {
+    let value = value.ok_or(format!("{} needs a number", flag))?;
+    value.parse().map_err(|_| format!("{} needs a number, got `{}`", flag, value))
+}
+
+fn parse_tournament_args(mut args: impl Iterator<Item = String>) -> Result<(Vec<String>, tournament::Settings), String> {
+    let mut settings = tournament::Settings {
+        games: 100,
+        seed: 0,
+        difficulty: Difficulty::Normal,
+        timeout: Duration::from_secs(5),
+    };
+    let mut bots = Vec::new();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--games" => settings.games = parse_number("--games", args.next())?,
+            "--seed" => settings.seed = parse_number("--seed", args.next())?,
+            "--difficulty" => settings.difficulty = parse_difficulty(args.next())?,
+            "--timeout-ms" => settings.timeout = Duration::from_millis(parse_number("--timeout-ms", args.next())?),
+            _ if arg.starts_with("--") => return Err(format!("unknown argument `{}`", arg)),
+            _ => bots.push(arg),
+        }
+    }
+
+    if bots.is_empty() {
+        return Err(String::from("a tournament needs at least one bot"));
+    }
+    Ok((bots, settings))
+}
+
+fn parse_words_args(mut args: impl Iterator<Item = String>) -> Result<words::Collation, String> {
+    let mut collation = words::Collation::Codepoint;
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--collation" => {
+                let name = args.next().ok_or("--collation needs a name")?;
+                collation = words::Collation::parse(&name).ok_or(format!("unknown collation `{}`", name))?;
+            }
+            other => return Err(format!("unknown argument `{}`", other)),
+        }
+    }
+
+    Ok(collation)
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        player: String::from("player"),
+        difficulty: Difficulty::Normal,
+        kind: kinds::Options {
+            kind: String::from("u32"),
+            min: None,
+            max: None,
+            tolerance: 0.01,
+        },
+    };
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--player" => {
+                options.player = args.next().ok_or("--player needs a name")?;
+            }
+            "--difficulty" => options.difficulty = parse_difficulty(args.next())?,
+            "--kind" => options.kind.kind = args.next().ok_or("--kind needs a kind")?,
+            "--min" => options.kind.min = Some(args.next().ok_or("--min needs a value")?),
+            "--max" => options.kind.max = Some(args.next().ok_or("--max needs a value")?),
+            "--tolerance" => options.kind.tolerance = parse_number("--tolerance", args.next())?,
+            other => return Err(format!("unknown argument `{}`", other)),
+        }
+    }
+
+    if options.kind.kind == "u32" && (options.kind.min.is_some() || options.kind.max.is_some()) {
+        return Err(String::from("the u32 game picks its range with --difficulty, --min and --max are for the other kinds"));
+    }
+
+    Ok(options)
+}
+
+fn usage_error(e: String) -> ! {
+    eprintln!("{}", e);
+    eprintln!("{}", USAGE);
+    std::process::exit(2);
+}
+
+// pulls the presentation flags out wherever they are, so every subcommand gets them for free
+fn parse_output_args(args: Vec<String>) -> Result<(Output, Vec<String>), String> {
+    let mut out = Output::standard();
+    let mut rest = Vec::new();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--accessible" => out.style = Style::Accessible,
+            "--bell" => out.bell = true,
+            "--big" => out.big = true,
+            "--locale" => {
+                let name = args.next().ok_or("--locale needs a name")?;
+                out.locale = Locale::parse(&name).ok_or(format!("unknown locale `{}`", name))?;
+            }
+            _ => rest.push(arg),
+        }
+    }
+
+    Ok((out, rest))
+}
 
 fn main() {
-    println!("guess the number");
+    let (out, args) = parse_output_args(env::args().skip(1).collect()).unwrap_or_else(|e| usage_error(e));
+    let mut args = args.into_iter().peekable();
+
+    match args.peek().map(String::as_str) {
+        Some("tournament") => {
+            args.next();
+            let (bots, settings) = parse_tournament_args(args).unwrap_or_else(|e| usage_error(e));
+            let standings = tournament::run(&bots, &settings);
+            tournament::print(&standings, &settings, &out);
+            return;
+        }
+        Some("words") => {
+            args.next();
+            let collation = parse_words_args(args).unwrap_or_else(|e| usage_error(e));
+            words::play(collation, &out);
+            return;
+        }
+        _ => {}
+    }
+
+    let options = parse_args(args).unwrap_or_else(|e| usage_error(e));
+
+    if options.kind.kind != "u32" {
+        if let Err(e) = kinds::play(&options.kind, &out) {
+            usage_error(e);
+        }
+        return;
+    }
 
-    let chicken_dinner = rand::thread_rng().gen_range(1..101); // inclusive lower bound, exclusive upper bound, can also use 1..=100
+    let all_achievements = achievements::load();
+    let mut profile = Profile::load(&options.player).expect("failed to load profile");
+    out.say(&format!("welcome {} (level {}, {} xp)", profile.name, profile.level(), profile.xp));
 
-    loop{
+    loop {
+        let record = game::play(options.difficulty, &out);
+        let level_before = profile.level();
+        profile.record(&record);
 
-        let mut guess = String::new();
-        println!("enter your guess");
+        for achievement in achievements::check(&all_achievements, &profile, &record) {
+            out.say(&format!("achievement unlocked: {}, {} (+{} xp)", achievement.name, achievement.description, achievement.xp));
+            profile.xp += achievement.xp;
+            profile.unlocked.push(achievement.id.clone());
+        }
+
+        if profile.level() > level_before {
+            out.say(&format!("level up! you are now level {}", profile.level()));
+        }
+        out.say(&format!("{} xp, win streak {}", profile.xp, profile.streak));
 
+        profile.save().expect("failed to save profile");
+
+        out.confirm("play again?");
+        let mut answer = String::new();
         io::stdin()
-            .read_line(&mut guess)
+            .read_line(&mut answer)
             .expect("failed to read line");
-    
-        let guess: u32 = match guess.trim().parse() {
-            Ok(num) => num,
-            Err(_) => {
-                println!("problem here");
-                continue;
-            }
-        };
-
-        match guess.cmp(&chicken_dinner) {
-            Ordering::Less => println!("Too small!"),
-            Ordering::Greater => println!("Too Large!"),
-            Ordering::Equal => {
-                println!("You win!");
-                break;
-            }
+        if answer.trim() != "y" {
+            break;
         }
     }
-}
\ No newline at end of file
+}
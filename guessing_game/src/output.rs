@@ -0,0 +1,201 @@
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+
+use labs::format::Locale;
+
+// everything the game shows the player goes through here instead of straight to `println!`,
+// so that how it looks can be swapped out without touching the game logic
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    // the original terse output
+    Standard,
+    // for screen readers: every line is a full sentence with the same structure, no symbols
+    // or layout that only make sense visually
+    Accessible,
+}
+
+// what the thing being guessed is measured on, which changes the wording of the feedback
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Scale {
+    Number,
+    Alphabet,
+}
+
+pub struct Output {
+    pub style: Style,
+    // ring the terminal bell after feedback: once for low, twice for high, three times for a win
+    pub bell: bool,
+    // also print the feedback as large ascii-art letters
+    pub big: bool,
+    // how the numbers the game picks are grouped, like 1,000,000
+    pub locale: Locale,
+}
+
+// every glyph is 5 rows tall. only the letters the feedback words need are here
+fn glyph(c: char) -> [&'static str; 5] {
+    match c {
+        'A' => [" ### ", "#   #", "#####", "#   #", "#   #"],
+        'E' => ["#####", "#    ", "#### ", "#    ", "#####"],
+        'G' => [" ####", "#    ", "#  ##", "#   #", " ####"],
+        'H' => ["#   #", "#   #", "#####", "#   #", "#   #"],
+        'I' => ["#####", "  #  ", "  #  ", "  #  ", "#####"],
+        'L' => ["#    ", "#    ", "#    ", "#    ", "#####"],
+        'N' => ["#   #", "##  #", "# # #", "#  ##", "#   #"],
+        'O' => [" ### ", "#   #", "#   #", "#   #", " ### "],
+        'R' => ["#### ", "#   #", "#### ", "#  # ", "#   #"],
+        'T' => ["#####", "  #  ", "  #  ", "  #  ", "  #  "],
+        'W' => ["#   #", "#   #", "# # #", "## ##", "#   #"],
+        'Y' => ["#   #", " # # ", "  #  ", "  #  ", "  #  "],
+        '!' => ["#", "#", "#", " ", "#"],
+        _ => ["   ", "   ", "   ", "   ", "   "],
+    }
+}
+
+fn banner(word: &str) -> String {
+    (0..5)
+        .map(|row| {
+            let line: Vec<&str> = word.chars().map(|c| glyph(c)[row]).collect();
+            line.join("  ").trim_end().to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// capital letter at the start, full stop at the end, so a screen reader always gets a sentence.
+// backticks go too, otherwise they get read out as "grave accent"
+fn sentence(text: &str) -> String {
+    let text = text.trim().replace('`', "");
+    let mut chars = text.chars();
+    let mut result = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect::<String>(),
+        None => return String::new(),
+    };
+    if !result.ends_with(['.', '!', '?']) {
+        result.push('.');
+    }
+    result
+}
+
+impl Output {
+    pub fn standard() -> Output {
+        Output {
+            style: Style::Standard,
+            bell: false,
+            big: false,
+            locale: Locale::from_env(),
+        }
+    }
+
+    // anything that doesn't have a more specific method below
+    pub fn say(&self, text: &str) {
+        match self.style {
+            Style::Standard => println!("{}", text),
+            Style::Accessible => println!("{}", sentence(text)),
+        }
+    }
+
+    pub fn prompt(&self) {
+        match self.style {
+            Style::Standard => println!("enter your guess (or `quit` to give up)"),
+            Style::Accessible => println!("Enter your guess, or type quit to give up."),
+        }
+    }
+
+    pub fn confirm(&self, question: &str) {
+        match self.style {
+            Style::Standard => println!("{} (y/n)", question),
+            Style::Accessible => println!("{} Type y for yes or n for no.", sentence(question)),
+        }
+    }
+
+    pub fn problem(&self, text: &str) {
+        match self.style {
+            Style::Standard => println!("problem here: {}", text),
+            Style::Accessible => println!("Problem: {}", sentence(text)),
+        }
+    }
+
+    pub fn reveal(&self, answer: &dyn Display) {
+        match self.style {
+            Style::Standard => println!("the answer was {}", answer),
+            Style::Accessible => println!("You gave up. The answer was {}.", answer),
+        }
+    }
+
+    pub fn feedback(&self, attempt: usize, guess: &dyn Display, ordering: Ordering, scale: Scale) {
+        match (self.style, scale, ordering) {
+            (Style::Standard, Scale::Number, Ordering::Less) => println!("Too small!"),
+            (Style::Standard, Scale::Number, Ordering::Greater) => println!("Too Large!"),
+            (Style::Standard, Scale::Number, Ordering::Equal) => println!("You win!"),
+            (Style::Standard, Scale::Alphabet, Ordering::Less) => println!("Too early! the word comes after {}", guess),
+            (Style::Standard, Scale::Alphabet, Ordering::Greater) => println!("Too late! the word comes before {}", guess),
+            (Style::Standard, Scale::Alphabet, Ordering::Equal) => println!("You win! it took you {} guesses", attempt),
+            (Style::Accessible, Scale::Number, Ordering::Less) => println!("Guess {}: {} is too low. The answer is higher.", attempt, guess),
+            (Style::Accessible, Scale::Number, Ordering::Greater) => println!("Guess {}: {} is too high. The answer is lower.", attempt, guess),
+            (Style::Accessible, Scale::Alphabet, Ordering::Less) => println!("Guess {}: {} is too early. The answer comes later in the alphabet.", attempt, guess),
+            (Style::Accessible, Scale::Alphabet, Ordering::Greater) => println!("Guess {}: {} is too late. The answer comes earlier in the alphabet.", attempt, guess),
+            (Style::Accessible, _, Ordering::Equal) => println!("Guess {}: {} is correct. You win.", attempt, guess),
+        }
+
+        if self.big {
+            let word = match (scale, ordering) {
+                (Scale::Number, Ordering::Less) => "LOW",
+                (Scale::Number, Ordering::Greater) => "HIGH",
+                (Scale::Alphabet, Ordering::Less) => "EARLY",
+                (Scale::Alphabet, Ordering::Greater) => "LATE",
+                (_, Ordering::Equal) => "WIN!",
+            };
+            println!("{}", banner(word));
+        }
+
+        if self.bell {
+            let rings = match ordering {
+                Ordering::Less => 1,
+                Ordering::Greater => 2,
+                Ordering::Equal => 3,
+            };
+            ring(rings);
+        }
+    }
+
+    // a table with a header row. the accessible style reads each row out as a sentence
+    // instead of relying on columns lining up
+    pub fn table(&self, header: &[&str], rows: &[Vec<String>]) {
+        match self.style {
+            Style::Standard => {
+                let widths: Vec<usize> = (0..header.len())
+                    .map(|i| rows.iter().map(|row| row[i].len()).chain(Some(header[i].len())).max().unwrap_or(0))
+                    .collect();
+                let render = |cells: Vec<&str>| {
+                    let padded: Vec<String> = cells.iter().zip(&widths).map(|(cell, width)| format!("{:<1$}", cell, width)).collect();
+                    println!("{}", padded.join("  ").trim_end());
+                };
+                render(header.to_vec());
+                for row in rows {
+                    render(row.iter().map(String::as_str).collect());
+                }
+            }
+            Style::Accessible => {
+                for row in rows {
+                    let cells: Vec<String> = header.iter().zip(row).map(|(name, cell)| format!("{} {}", name.to_lowercase(), cell)).collect();
+                    println!("{}", sentence(&cells.join(", ")));
+                }
+            }
+        }
+    }
+}
+
+fn ring(times: usize) {
+    let mut stdout = io::stdout();
+    for i in 0..times {
+        if i > 0 {
+            // far enough apart that you can count them
+            thread::sleep(Duration::from_millis(200));
+        }
+        let _ = write!(stdout, "\x07");
+        let _ = stdout.flush();
+    }
+}
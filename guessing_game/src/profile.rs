@@ -0,0 +1,87 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+use serde::{Deserialize, Serialize};
+
+use crate::game::GameRecord;
+
+// a player's progress, saved as toml between runs
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    pub xp: u32,
+    pub games: u32,
+    pub wins: u32,
+    pub streak: u32,
+    pub best_streak: u32,
+    pub unlocked: Vec<String>,
+}
+
+// profiles go in $GUESSING_GAME_HOME if it's set, otherwise ~/.guessing_game
+fn profile_dir() -> PathBuf {
+    match env::var_os("GUESSING_GAME_HOME") {
+        Some(dir) => PathBuf::from(dir),
+        None => {
+            let home = env::var_os("HOME").unwrap_or_else(|| ".".into());
+            PathBuf::from(home).join(".guessing_game")
+        }
+    }
+}
+
+// the name as a file name: letters, digits, `_` and `-` are kept, and anything else (like a
+// `/` or `..`) is dropped. a name that lost something gets a hash of all of it on the end, so
+// `a/b` and `ab` stay two players. the hash is FNV-1a, which comes out the same on every build
+fn profile_path(name: &str) -> PathBuf {
+    let mut file: String = name.chars().filter(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-').collect();
+    if file != name || file.is_empty() {
+        let hash = name.bytes().fold(0xcbf2_9ce4_8422_2325u64, |hash, byte| (hash ^ u64::from(byte)).wrapping_mul(0x100_0000_01b3));
+        file.push_str(&format!("-{:016x}", hash));
+    }
+    profile_dir().join(format!("{}.toml", file))
+}
+
+impl Profile {
+    // a missing profile just means this is a new player
+    pub fn load(name: &str) -> io::Result<Profile> {
+        match fs::read_to_string(profile_path(name)) {
+            Ok(contents) => toml::from_str(&contents)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Profile {
+                name: name.to_string(),
+                ..Profile::default()
+            }),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn save(&self) -> io::Result<()> {
+        fs::create_dir_all(profile_dir())?;
+        let contents = toml::to_string(self)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        fs::write(profile_path(&self.name), contents)
+    }
+
+    // level 2 needs 100 xp, level 3 another 200 on top of that, and so on
+    pub fn level(&self) -> u32 {
+        let mut level = 1;
+        let mut needed = 100;
+        while self.xp >= needed {
+            level += 1;
+            needed += 100 * level;
+        }
+        level
+    }
+
+    pub fn record(&mut self, game: &GameRecord) {
+        self.games += 1;
+        if game.won {
+            self.wins += 1;
+            self.streak += 1;
+            self.best_streak = self.best_streak.max(self.streak);
+            self.xp += game.difficulty.win_xp();
+        } else {
+            self.streak = 0;
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use std::io::{BufRead, BufReader, Write};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::cmp::Ordering;
+
+use crate::game::Difficulty;
+use crate::output::Output;
+
+/*
+    BOT PROTOCOL
+
+    a bot is any program that talks line by line over stdin/stdout. one process plays every
+    game of the tournament unless it crashes, in which case it gets restarted for the next game.
+    each BOT argument is the command that starts one, split on whitespace, so a script can be
+    passed as `"python3 bot.py"` (quoted, as one argument). there's no shell in between, so
+    there's no quoting or escaping inside the command either.
+
+    runner -> bot       meaning
+    --------------------------------------------------------------------------
+    start <low> <high>  a new game; the secret is in low..=high
+    low                 your last guess was too small
+    high                your last guess was too large
+    win                 your last guess was right, the game is over
+    end                 no more games, exit now
+
+    bot -> runner       meaning
+    --------------------------------------------------------------------------
+    <number>            a guess, in decimal
+
+    a game counts as failed (and is scored as MAX_ATTEMPTS) if the bot takes longer than the
+    per-game timeout, exits, prints something that isn't a number, or runs out of attempts.
+    anything a bot writes to stderr is passed straight through, so use that for debugging
+*/
+
+const MAX_ATTEMPTS: usize = 100;
+
+pub struct Settings {
+    pub games: usize,
+    pub seed: u64,
+    pub difficulty: Difficulty,
+    pub timeout: Duration,
+}
+
+// one running bot process. stdout is read on a separate thread so that we can give up on
+// a bot that's taking too long instead of blocking forever on `read_line`
+struct Bot {
+    child: Child,
+    stdin: ChildStdin,
+    lines: Receiver<String>,
+}
+
+impl Bot {
+    // `command` is the program and its arguments split on whitespace, like `python3 bot.py`
+    fn spawn(command: &str) -> Result<Bot, String> {
+        let mut words = command.split_whitespace();
+        let program = words.next().ok_or("a bot needs a command")?;
+        let mut child = Command::new(program)
+            .args(words)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("couldn't start `{}`: {}", command, e))?;
+
+        let stdin = child.stdin.take().expect("stdin was piped");
+        let stdout = child.stdout.take().expect("stdout was piped");
+
+        let (sender, lines) = mpsc::channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines() {
+                match line {
+                    Ok(line) => {
+                        if sender.send(line).is_err() {
+                            break;
+                        }
+                    }
+                    Err(_) => break,
+                }
+            }
+        });
+
+        Ok(Bot { child, stdin, lines })
+    }
+
+    fn send(&mut self, message: &str) -> Result<(), String> {
+        writeln!(self.stdin, "{}", message)
+            .and_then(|_| self.stdin.flush())
+            .map_err(|_| String::from("bot closed its stdin"))
+    }
+
+    fn next_guess(&self, deadline: Instant) -> Result<u32, String> {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match self.lines.recv_timeout(remaining) {
+            Ok(line) => line
+                .trim()
+                .parse()
+                .map_err(|_| format!("sent `{}` instead of a guess", line.trim())),
+            Err(RecvTimeoutError::Timeout) => Err(String::from("timed out")),
+            Err(RecvTimeoutError::Disconnected) => Err(String::from("exited")),
+        }
+    }
+
+    fn kill(mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+
+    fn finish(mut self) {
+        let _ = self.send("end");
+        // give it a moment to exit on its own before we pull the plug
+        thread::sleep(Duration::from_millis(50));
+        self.kill();
+    }
+}
+
+pub struct Standing {
+    pub command: String,
+    pub attempts: usize,
+    pub wins: usize,
+    pub failures: Vec<String>,
+}
+
+fn play_one(bot: &mut Bot, secret: u32, settings: &Settings) -> Result<usize, String> {
+    let deadline = Instant::now() + settings.timeout;
+    bot.send(&format!("start 1 {}", settings.difficulty.max()))?;
+
+    for attempt in 1..=MAX_ATTEMPTS {
+        let guess = bot.next_guess(deadline)?;
+        match guess.cmp(&secret) {
+            Ordering::Less => bot.send("low")?,
+            Ordering::Greater => bot.send("high")?,
+            Ordering::Equal => {
+                bot.send("win")?;
+                return Ok(attempt);
+            }
+        }
+    }
+
+    Err(format!("didn't find {} in {} attempts", secret, MAX_ATTEMPTS))
+}
+
+fn run_bot(command: &str, secrets: &[u32], settings: &Settings) -> Standing {
+    let mut standing = Standing {
+        command: command.to_string(),
+        attempts: 0,
+        wins: 0,
+        failures: Vec::new(),
+    };
+    let mut bot = None;
+
+    for (game, &secret) in secrets.iter().enumerate() {
+        let running = match bot.take() {
+            Some(running) => Ok(running),
+            None => Bot::spawn(command),
+        };
+
+        let result = running.and_then(|mut running| {
+            let result = play_one(&mut running, secret, settings);
+            match result {
+                Ok(_) => bot = Some(running),
+                // whatever state it's in now can't be trusted, start fresh next game
+                Err(_) => running.kill(),
+            }
+            result
+        });
+
+        match result {
+            Ok(attempts) => {
+                standing.attempts += attempts;
+                standing.wins += 1;
+            }
+            Err(e) => {
+                standing.attempts += MAX_ATTEMPTS;
+                standing.failures.push(format!("game {}: {}", game + 1, e));
+            }
+        }
+    }
+
+    if let Some(bot) = bot {
+        bot.finish();
+    }
+    standing
+}
+
+pub fn run(commands: &[String], settings: &Settings) -> Vec<Standing> {
+    // every bot gets exactly the same secrets, in the same order
+    let mut rng = StdRng::seed_from_u64(settings.seed);
+    let secrets: Vec<u32> = (0..settings.games)
+        .map(|_| rng.gen_range(1..=settings.difficulty.max()))
+        .collect();
+
+    let mut standings: Vec<Standing> = commands
+        .iter()
+        .map(|command| run_bot(command, &secrets, settings))
+        .collect();
+
+    standings.sort_by_key(|s| (s.attempts, s.failures.len()));
+    standings
+}
+
+pub fn print(standings: &[Standing], settings: &Settings, out: &Output) {
+    out.say(&format!("{} games, seed {}, secrets in 1..={}", settings.games, settings.seed, settings.difficulty.max()));
+    out.say("");
+
+    let rows: Vec<Vec<String>> = standings
+        .iter()
+        .enumerate()
+        .map(|(rank, standing)| {
+            let average = standing.attempts as f64 / settings.games.max(1) as f64;
+            vec![
+                (rank + 1).to_string(),
+                standing.attempts.to_string(),
+                standing.wins.to_string(),
+                format!("{:.2}", average),
+                standing.command.clone(),
+            ]
+        })
+        .collect();
+    out.table(&["RANK", "ATTEMPTS", "WINS", "AVG", "BOT"], &rows);
+
+    for standing in standings.iter().filter(|s| !s.failures.is_empty()) {
+        out.say("");
+        out.say(&format!("{} failed {} game(s):", standing.command, standing.failures.len()));
+        for failure in &standing.failures {
+            out.say(&format!("    {}", failure));
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::Bound;
+use rand::seq::SliceRandom;
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+use crate::engine::Engine;
+use crate::game;
+use crate::output::{Output, Scale};
+
+const DICTIONARY: &str = include_str!("../words.txt");
+
+// how two words get compared.
+//
+// `Codepoint` is plain `str` ordering, which is what `cmp` gives you for free. it puts every
+// accented letter after `z`, so "élan" comes after "zebra".
+// `Unicode` compares the words with their accents stripped and case folded first, and only
+// falls back to the exact text to break ties, so "élan" sits between "elbow" and "engine"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Collation {
+    Codepoint,
+    Unicode,
+}
+
+impl Collation {
+    pub fn parse(s: &str) -> Option<Collation> {
+        match s {
+            "codepoint" => Some(Collation::Codepoint),
+            "unicode" => Some(Collation::Unicode),
+            _ => None,
+        }
+    }
+
+    fn key(self, text: &str) -> String {
+        match self {
+            Collation::Codepoint => text.to_string(),
+            Collation::Unicode => text
+                .nfd()
+                .filter(|c| !is_combining_mark(*c))
+                .flat_map(char::to_lowercase)
+                .collect(),
+        }
+    }
+}
+
+// a word along with the key it sorts by
+#[derive(Debug, Clone)]
+pub struct Word {
+    text: String,
+    key: String,
+}
+
+impl Word {
+    pub fn new(text: &str, collation: Collation) -> Word {
+        Word {
+            text: text.to_string(),
+            key: collation.key(text),
+        }
+    }
+}
+
+// the word as it was typed, not its sort key
+impl fmt::Display for Word {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl PartialEq for Word {
+    fn eq(&self, other: &Word) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Word {}
+
+impl PartialOrd for Word {
+    fn partial_cmp(&self, other: &Word) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Word {
+    fn cmp(&self, other: &Word) -> Ordering {
+        self.key.cmp(&other.key).then_with(|| self.text.cmp(&other.text))
+    }
+}
+
+pub struct Dictionary {
+    words: Vec<String>,
+}
+
+impl Dictionary {
+    pub fn bundled() -> Dictionary {
+        let words = DICTIONARY
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| line.nfc().collect())
+            .collect();
+        Dictionary { words }
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.iter().any(|w| w == word)
+    }
+
+    // the `count` words with the smallest edit distance to `word`
+    pub fn nearest(&self, word: &str, count: usize) -> Vec<&str> {
+        let mut scored: Vec<(usize, &str)> = self
+            .words
+            .iter()
+            .map(|w| (edit_distance(word, w), w.as_str()))
+            .collect();
+        scored.sort();
+        scored.into_iter().take(count).map(|(_, w)| w).collect()
+    }
+}
+
+// levenshtein distance, counted in chars rather than bytes
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + if ca == *cb { 0 } else { 1 };
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+pub fn play(collation: Collation, out: &Output) {
+    let dictionary = Dictionary::bundled();
+    let secret = dictionary
+        .words
+        .choose(&mut rand::thread_rng())
+        .expect("the dictionary is empty");
+
+    out.say(&format!("guess the word ({} words in the dictionary)", dictionary.words.len()));
+    let mut engine = Engine::new(Word::new(secret, collation), Bound::Unbounded, Bound::Unbounded);
+    game::guess_loop(&mut engine, out, Scale::Alphabet, |input| {
+        let guess: String = input.nfc().flat_map(char::to_lowercase).collect();
+        if !dictionary.contains(&guess) {
+            return Err(format!("`{}` isn't in the dictionary, did you mean: {}?", guess, dictionary.nearest(&guess, 3).join(", ")));
+        }
+        Ok(Word::new(&guess, collation))
+    });
+}
@@ -0,0 +1,951 @@
+// integers with no fixed size, for when the integer table in ch3/varsandmutability.rs runs out at
+// 128 bits. a `BigUint` is a list of 32-bit limbs, lowest first, and grows as big as it needs to.
+// a `BigInt` is one of those plus a sign. the operators behave like the primitive ones: division
+// rounds toward zero, `%` takes the sign of the left side, and subtracting below zero or
+// dividing by zero panics with the same message u128 would
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+
+// below this many limbs (on the shorter side) schoolbook multiplication is faster than
+// splitting things up
+const KARATSUBA_THRESHOLD: usize = 32;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigUint {
+    // lowest limb first, never with zeros on the end, so zero is an empty list
+    limbs: Vec<u32>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BigInt {
+    // never true for zero
+    negative: bool,
+    magnitude: BigUint,
+}
+
+fn trim(limbs: &mut Vec<u32>) {
+    while limbs.last() == Some(&0) {
+        limbs.pop();
+    }
+}
+
+fn compare(a: &[u32], b: &[u32]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.iter().rev().cmp(b.iter().rev()))
+}
+
+fn add_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut out = Vec::with_capacity(long.len() + 1);
+    let mut carry = 0u64;
+    for (i, &limb) in long.iter().enumerate() {
+        let sum = limb as u64 + short.get(i).copied().unwrap_or(0) as u64 + carry;
+        out.push(sum as u32);
+        carry = sum >> 32;
+    }
+    if carry > 0 {
+        out.push(carry as u32);
+    }
+    out
+}
+
+// a - b, where a is at least as big as b
+fn sub_limbs(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut out = Vec::with_capacity(a.len());
+    let mut borrow = 0i64;
+    for (i, &limb) in a.iter().enumerate() {
+        let difference = limb as i64 - b.get(i).copied().unwrap_or(0) as i64 - borrow;
+        out.push(difference as u32);
+        borrow = if difference < 0 { 1 } else { 0 };
+    }
+    debug_assert_eq!(borrow, 0, "subtracted a bigger number");
+    trim(&mut out);
+    out
+}
+
+// adds `b` into `a` starting `offset` limbs up, growing `a` if it has to
+fn add_at(a: &mut Vec<u32>, b: &[u32], offset: usize) {
+    if a.len() < offset + b.len() {
+        a.resize(offset + b.len(), 0);
+    }
+    let mut carry = 0u64;
+    let mut i = 0;
+    while i < b.len() || carry > 0 {
+        if offset + i == a.len() {
+            a.push(0);
+        }
+        let sum = a[offset + i] as u64 + b.get(i).copied().unwrap_or(0) as u64 + carry;
+        a[offset + i] = sum as u32;
+        carry = sum >> 32;
+        i += 1;
+    }
+}
+
+fn schoolbook(a: &[u32], b: &[u32]) -> Vec<u32> {
+    let mut out = vec![0u32; a.len() + b.len()];
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u64;
+        for (j, &y) in b.iter().enumerate() {
+            let product = x as u64 * y as u64 + out[i + j] as u64 + carry;
+            out[i + j] = product as u32;
+            carry = product >> 32;
+        }
+        out[i + b.len()] = carry as u32;
+    }
+    trim(&mut out);
+    out
+}
+
+// splits both sides at `half` limbs, so a = a1·B + a0 and b = b1·B + b0, and gets the product
+// from three multiplications instead of four:
+// a·b = a1·b1·B² + ((a0 + a1)(b0 + b1) - a0·b0 - a1·b1)·B + a0·b0
+fn karatsuba(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return schoolbook(a, b);
+    }
+
+    let half = a.len().max(b.len()) / 2;
+    let split = |x: &[u32]| -> (Vec<u32>, Vec<u32>) {
+        let (low, high) = x.split_at(half.min(x.len()));
+        let mut low = low.to_vec();
+        trim(&mut low);
+        (low, high.to_vec())
+    };
+    let (a0, a1) = split(a);
+    let (b0, b1) = split(b);
+
+    let low = karatsuba(&a0, &b0);
+    let high = karatsuba(&a1, &b1);
+    let middle = karatsuba(&add_limbs(&a0, &a1), &add_limbs(&b0, &b1));
+    let middle = sub_limbs(&sub_limbs(&middle, &low), &high);
+
+    let mut out = low;
+    add_at(&mut out, &middle, half);
+    add_at(&mut out, &high, 2 * half);
+    trim(&mut out);
+    out
+}
+
+fn multiply(a: &[u32], b: &[u32]) -> Vec<u32> {
+    if a.is_empty() || b.is_empty() {
+        Vec::new()
+    } else {
+        karatsuba(a, b)
+    }
+}
+
+// (quotient, remainder) of dividing by a single limb
+fn div_small(a: &[u32], divisor: u32) -> (Vec<u32>, u32) {
+    let mut quotient = vec![0u32; a.len()];
+    let mut remainder = 0u64;
+    for i in (0..a.len()).rev() {
+        let current = (remainder << 32) | a[i] as u64;
+        quotient[i] = (current / divisor as u64) as u32;
+        remainder = current % divisor as u64;
+    }
+    trim(&mut quotient);
+    (quotient, remainder as u32)
+}
+
+fn shl_bits(a: &[u32], shift: u32) -> Vec<u32> {
+    if shift == 0 {
+        return a.to_vec();
+    }
+    let mut out = Vec::with_capacity(a.len() + 1);
+    let mut carry = 0u32;
+    for &limb in a {
+        out.push((limb << shift) | carry);
+        carry = limb >> (32 - shift);
+    }
+    out.push(carry);
+    out
+}
+
+fn shr_bits(a: &[u32], shift: u32) -> Vec<u32> {
+    let mut out = a.to_vec();
+    if shift > 0 {
+        for i in 0..out.len() {
+            let next = out.get(i + 1).copied().unwrap_or(0);
+            out[i] = (out[i] >> shift) | (next << (32 - shift));
+        }
+    }
+    trim(&mut out);
+    out
+}
+
+// long division, knuth's algorithm D. both sides are shifted so the divisor's top limb has its
+// top bit set, which keeps each guessed quotient limb at most two too big
+fn div_rem(a: &[u32], b: &[u32]) -> (Vec<u32>, Vec<u32>) {
+    if compare(a, b) == Ordering::Less {
+        return (Vec::new(), a.to_vec());
+    }
+    if b.len() == 1 {
+        let (quotient, remainder) = div_small(a, b[0]);
+        let remainder = if remainder == 0 { Vec::new() } else { vec![remainder] };
+        return (quotient, remainder);
+    }
+
+    let shift = b[b.len() - 1].leading_zeros();
+    let v = shl_bits(b, shift);
+    let v = &v[..b.len()];
+    let mut u = shl_bits(a, shift);
+    if u.len() == a.len() {
+        u.push(0);
+    }
+
+    let n = v.len();
+    let m = u.len() - n - 1;
+    let base = 1u64 << 32;
+    let (top, next) = (v[n - 1] as u64, v[n - 2] as u64);
+    let mut quotient = vec![0u32; m + 1];
+
+    for j in (0..=m).rev() {
+        let numerator = ((u[j + n] as u64) << 32) | u[j + n - 1] as u64;
+        let mut guess = numerator / top;
+        let mut rest = numerator % top;
+        while guess >= base || guess * next > ((rest << 32) | u[j + n - 2] as u64) {
+            guess -= 1;
+            rest += top;
+            if rest >= base {
+                break;
+            }
+        }
+
+        // u -= guess · v, lined up at limb j
+        let mut borrow = 0i64;
+        let mut carry = 0u64;
+        for i in 0..n {
+            let product = guess * v[i] as u64 + carry;
+            carry = product >> 32;
+            let difference = u[i + j] as i64 - borrow - (product & 0xffff_ffff) as i64;
+            u[i + j] = difference as u32;
+            borrow = if difference < 0 { 1 } else { 0 };
+        }
+        let difference = u[j + n] as i64 - borrow - carry as i64;
+        u[j + n] = difference as u32;
+
+        // the guess was one too big after all, so add one v back
+        if difference < 0 {
+            guess -= 1;
+            let mut carry = 0u64;
+            for i in 0..n {
+                let sum = u[i + j] as u64 + v[i] as u64 + carry;
+                u[i + j] = sum as u32;
+                carry = sum >> 32;
+            }
+            u[j + n] = u[j + n].wrapping_add(carry as u32);
+        }
+        quotient[j] = guess as u32;
+    }
+
+    trim(&mut quotient);
+    (quotient, shr_bits(&u[..n], shift))
+}
+
+impl BigUint {
+    pub fn zero() -> BigUint {
+        BigUint { limbs: Vec::new() }
+    }
+
+    pub fn from_limbs(mut limbs: Vec<u32>) -> BigUint {
+        trim(&mut limbs);
+        BigUint { limbs }
+    }
+
+    // lowest first
+    pub fn limbs(&self) -> &[u32] {
+        &self.limbs
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.limbs.is_empty()
+    }
+
+    // how many bits it takes to write the number down, 0 for zero
+    pub fn bits(&self) -> u64 {
+        match self.limbs.last() {
+            Some(top) => self.limbs.len() as u64 * 32 - top.leading_zeros() as u64,
+            None => 0,
+        }
+    }
+
+    pub fn to_u128(&self) -> Option<u128> {
+        if self.limbs.len() > 4 {
+            return None;
+        }
+        Some(self.limbs.iter().rev().fold(0u128, |value, &limb| (value << 32) | limb as u128))
+    }
+
+    pub fn checked_sub(&self, other: &BigUint) -> Option<BigUint> {
+        match compare(&self.limbs, &other.limbs) {
+            Ordering::Less => None,
+            _ => Some(BigUint { limbs: sub_limbs(&self.limbs, &other.limbs) }),
+        }
+    }
+
+    pub fn checked_div_rem(&self, other: &BigUint) -> Option<(BigUint, BigUint)> {
+        if other.is_zero() {
+            return None;
+        }
+        let (quotient, remainder) = div_rem(&self.limbs, &other.limbs);
+        Some((BigUint { limbs: quotient }, BigUint { limbs: remainder }))
+    }
+
+    // square and multiply
+    pub fn pow(&self, mut exponent: u32) -> BigUint {
+        let mut result = BigUint::from(1u32);
+        let mut base = self.clone();
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                result = &result * &base;
+            }
+            exponent >>= 1;
+            if exponent > 0 {
+                base = &base * &base;
+            }
+        }
+        result
+    }
+
+    // the digits in any base from 2 to 36, most significant first
+    pub fn to_str_radix(&self, radix: u32) -> String {
+        assert!((2..=36).contains(&radix), "radix must be between 2 and 36");
+        if self.is_zero() {
+            return String::from("0");
+        }
+
+        // peel off as many digits at once as fit in a limb
+        let mut per_chunk = 1;
+        let mut chunk = radix;
+        while let Some(bigger) = chunk.checked_mul(radix) {
+            chunk = bigger;
+            per_chunk += 1;
+        }
+
+        let mut digits = Vec::new();
+        let mut rest = self.limbs.clone();
+        while !rest.is_empty() {
+            let (quotient, mut remainder) = div_small(&rest, chunk);
+            rest = quotient;
+            for _ in 0..per_chunk {
+                digits.push(std::char::from_digit(remainder % radix, radix).expect("digit below the radix"));
+                remainder /= radix;
+                if rest.is_empty() && remainder == 0 {
+                    break;
+                }
+            }
+        }
+        digits.iter().rev().collect()
+    }
+
+    fn from_digits(digits: &str, radix: u32, original: &str) -> Result<BigUint, String> {
+        let mut limbs: Vec<u32> = Vec::new();
+        let mut any = false;
+        for c in digits.chars() {
+            if c == '_' {
+                continue;
+            }
+            let digit = c.to_digit(radix).ok_or_else(|| {
+                format!("invalid digit `{}` for a {} number in `{}`", c, base_name(radix), original)
+            })?;
+            any = true;
+
+            // limbs = limbs · radix + digit
+            let mut carry = digit as u64;
+            for limb in limbs.iter_mut() {
+                let value = *limb as u64 * radix as u64 + carry;
+                *limb = value as u32;
+                carry = value >> 32;
+            }
+            if carry > 0 {
+                limbs.push(carry as u32);
+            }
+        }
+        if !any {
+            return Err(format!("`{}` has no digits", original));
+        }
+        Ok(BigUint::from_limbs(limbs))
+    }
+}
+
+fn base_name(radix: u32) -> &'static str {
+    match radix {
+        2 => "binary",
+        8 => "octal",
+        16 => "hexadecimal",
+        _ => "decimal",
+    }
+}
+
+impl From<u32> for BigUint {
+    fn from(value: u32) -> BigUint {
+        BigUint::from_limbs(vec![value])
+    }
+}
+
+impl From<u64> for BigUint {
+    fn from(value: u64) -> BigUint {
+        BigUint::from(value as u128)
+    }
+}
+
+impl From<u128> for BigUint {
+    fn from(value: u128) -> BigUint {
+        BigUint::from_limbs((0..4).map(|i| (value >> (32 * i)) as u32).collect())
+    }
+}
+
+impl Ord for BigUint {
+    fn cmp(&self, other: &BigUint) -> Ordering {
+        compare(&self.limbs, &other.limbs)
+    }
+}
+
+impl PartialOrd for BigUint {
+    fn partial_cmp(&self, other: &BigUint) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+// every way of writing an integer literal: `1_000`, `0xff`, `0o77`, `0b1111_0000` and `b'A'`.
+// `digits` is the literal without the sign
+fn parse_literal(digits: &str, original: &str) -> Result<BigUint, String> {
+    if let Some(byte) = digits.strip_prefix("b'").and_then(|rest| rest.strip_suffix('\'')) {
+        let mut chars = byte.chars();
+        return match (chars.next(), chars.next()) {
+            (Some(c), None) if c.is_ascii() => Ok(BigUint::from(c as u32)),
+            _ => Err(format!("`{}` isn't a single ASCII byte", original)),
+        };
+    }
+
+    let (radix, rest) = match digits.get(..2) {
+        Some("0x") => (16, &digits[2..]),
+        Some("0o") => (8, &digits[2..]),
+        Some("0b") => (2, &digits[2..]),
+        _ => (10, digits),
+    };
+    if rest.starts_with('_') && radix == 10 {
+        return Err(format!("`{}` isn't a number, a literal can't start with `_`", original));
+    }
+    BigUint::from_digits(rest, radix, original)
+}
+
+impl FromStr for BigUint {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<BigUint, String> {
+        if s.starts_with('-') {
+            return Err(format!("`{}` is negative, which a BigUint can't be", s));
+        }
+        parse_literal(s.strip_prefix('+').unwrap_or(s), s)
+    }
+}
+
+impl fmt::Display for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(true, "", &self.to_str_radix(10))
+    }
+}
+
+impl fmt::LowerHex for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(true, "0x", &self.to_str_radix(16))
+    }
+}
+
+impl fmt::Binary for BigUint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(true, "0b", &self.to_str_radix(2))
+    }
+}
+
+impl<'a> Add<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn add(self, other: &BigUint) -> BigUint {
+        BigUint { limbs: add_limbs(&self.limbs, &other.limbs) }
+    }
+}
+
+impl<'a> Sub<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    // the same as u128 in a debug build
+    fn sub(self, other: &BigUint) -> BigUint {
+        self.checked_sub(other).expect("attempt to subtract with overflow")
+    }
+}
+
+impl<'a> Mul<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn mul(self, other: &BigUint) -> BigUint {
+        BigUint { limbs: multiply(&self.limbs, &other.limbs) }
+    }
+}
+
+impl<'a> Div<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn div(self, other: &BigUint) -> BigUint {
+        self.checked_div_rem(other).expect("attempt to divide by zero").0
+    }
+}
+
+impl<'a> Rem<&'a BigUint> for &'a BigUint {
+    type Output = BigUint;
+
+    fn rem(self, other: &BigUint) -> BigUint {
+        self.checked_div_rem(other).expect("attempt to calculate the remainder with a divisor of zero").1
+    }
+}
+
+impl BigInt {
+    pub fn zero() -> BigInt {
+        BigInt::default()
+    }
+
+    fn signed(negative: bool, magnitude: BigUint) -> BigInt {
+        BigInt {
+            negative: negative && !magnitude.is_zero(),
+            magnitude,
+        }
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.magnitude.is_zero()
+    }
+
+    pub fn magnitude(&self) -> &BigUint {
+        &self.magnitude
+    }
+
+    pub fn to_i128(&self) -> Option<i128> {
+        let magnitude = self.magnitude.to_u128()?;
+        if self.negative {
+            0i128.checked_sub_unsigned(magnitude)
+        } else {
+            i128::try_from(magnitude).ok()
+        }
+    }
+
+    pub fn pow(&self, exponent: u32) -> BigInt {
+        BigInt::signed(self.negative && exponent % 2 == 1, self.magnitude.pow(exponent))
+    }
+
+    // quotient rounded toward zero and a remainder with the sign of `self`, like `/` and `%`
+    pub fn checked_div_rem(&self, other: &BigInt) -> Option<(BigInt, BigInt)> {
+        let (quotient, remainder) = self.magnitude.checked_div_rem(&other.magnitude)?;
+        Some((
+            BigInt::signed(self.negative != other.negative, quotient),
+            BigInt::signed(self.negative, remainder),
+        ))
+    }
+}
+
+impl From<BigUint> for BigInt {
+    fn from(magnitude: BigUint) -> BigInt {
+        BigInt::signed(false, magnitude)
+    }
+}
+
+impl From<i64> for BigInt {
+    fn from(value: i64) -> BigInt {
+        BigInt::from(value as i128)
+    }
+}
+
+impl From<i128> for BigInt {
+    fn from(value: i128) -> BigInt {
+        BigInt::signed(value < 0, BigUint::from(value.unsigned_abs()))
+    }
+}
+
+impl From<u128> for BigInt {
+    fn from(value: u128) -> BigInt {
+        BigInt::from(BigUint::from(value))
+    }
+}
+
+impl Ord for BigInt {
+    fn cmp(&self, other: &BigInt) -> Ordering {
+        match (self.negative, other.negative) {
+            (false, false) => self.magnitude.cmp(&other.magnitude),
+            (true, true) => other.magnitude.cmp(&self.magnitude),
+            (false, true) => Ordering::Greater,
+            (true, false) => Ordering::Less,
+        }
+    }
+}
+
+impl PartialOrd for BigInt {
+    fn partial_cmp(&self, other: &BigInt) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl FromStr for BigInt {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<BigInt, String> {
+        let (negative, digits) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        Ok(BigInt::signed(negative, parse_literal(digits, s)?))
+    }
+}
+
+impl fmt::Display for BigInt {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.pad_integral(!self.negative, "", &self.magnitude.to_str_radix(10))
+    }
+}
+
+impl Neg for BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        BigInt::signed(!self.negative, self.magnitude)
+    }
+}
+
+impl Neg for &BigInt {
+    type Output = BigInt;
+
+    fn neg(self) -> BigInt {
+        -self.clone()
+    }
+}
+
+impl<'a> Add<&'a BigInt> for &'a BigInt {
+    type Output = BigInt;
+
+    fn add(self, other: &BigInt) -> BigInt {
+        if self.negative == other.negative {
+            return BigInt::signed(self.negative, &self.magnitude + &other.magnitude);
+        }
+        // different signs: take the smaller magnitude from the bigger one, which keeps its sign
+        match self.magnitude.cmp(&other.magnitude) {
+            Ordering::Less => BigInt::signed(other.negative, &other.magnitude - &self.magnitude),
+            _ => BigInt::signed(self.negative, &self.magnitude - &other.magnitude),
+        }
+    }
+}
+
+impl<'a> Sub<&'a BigInt> for &'a BigInt {
+    type Output = BigInt;
+
+    fn sub(self, other: &BigInt) -> BigInt {
+        self + &-other
+    }
+}
+
+impl<'a> Mul<&'a BigInt> for &'a BigInt {
+    type Output = BigInt;
+
+    fn mul(self, other: &BigInt) -> BigInt {
+        BigInt::signed(self.negative != other.negative, &self.magnitude * &other.magnitude)
+    }
+}
+
+impl<'a> Div<&'a BigInt> for &'a BigInt {
+    type Output = BigInt;
+
+    fn div(self, other: &BigInt) -> BigInt {
+        self.checked_div_rem(other).expect("attempt to divide by zero").0
+    }
+}
+
+impl<'a> Rem<&'a BigInt> for &'a BigInt {
+    type Output = BigInt;
+
+    fn rem(self, other: &BigInt) -> BigInt {
+        self.checked_div_rem(other).expect("attempt to calculate the remainder with a divisor of zero").1
+    }
+}
+
+// the by-value versions, and mixes of value and reference, all go through the reference ones
+macro_rules! forward {
+    ($t:ty: $($trait:ident $method:ident),*) => {
+        $(
+            impl $trait<$t> for $t {
+                type Output = $t;
+
+                fn $method(self, other: $t) -> $t {
+                    (&self).$method(&other)
+                }
+            }
+
+            impl<'a> $trait<&'a $t> for $t {
+                type Output = $t;
+
+                fn $method(self, other: &$t) -> $t {
+                    (&self).$method(other)
+                }
+            }
+
+            impl<'a> $trait<$t> for &'a $t {
+                type Output = $t;
+
+                fn $method(self, other: $t) -> $t {
+                    self.$method(&other)
+                }
+            }
+        )*
+    };
+}
+
+forward!(BigUint: Add add, Sub sub, Mul mul, Div div, Rem rem);
+forward!(BigInt: Add add, Sub sub, Mul mul, Div div, Rem rem);
+
+// what the same operation does on i128, or on u128 for operands only it can hold
+macro_rules! primitive {
+    ($t:ty, $a:expr, $op:expr, $b:expr) => {{
+        let (a, b): ($t, $t) = ($a, $b);
+        let result = match $op {
+            "+" => a.checked_add(b),
+            "-" => a.checked_sub(b),
+            "*" => a.checked_mul(b),
+            "/" => a.checked_div(b),
+            "%" => a.checked_rem(b),
+            _ => u32::try_from(b).ok().and_then(|b| a.checked_pow(b)),
+        };
+        match result {
+            Some(value) => format!("{} agrees: {}", stringify!($t), value),
+            None if b == 0 && $op != "**" => format!("{} panics too", stringify!($t)),
+            None => format!("{} would overflow here", stringify!($t)),
+        }
+    }};
+}
+
+// evaluates a line like `2 ** 200`, `0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff + 1` or
+// `-7 % 3`, with both sides as BigInts
+pub fn evaluate(line: &str) -> Result<String, String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let (a, op, b) = match tokens.as_slice() {
+        [a] => return Ok(BigInt::from_str(a)?.to_string()),
+        [a, op, b] => (BigInt::from_str(a)?, *op, BigInt::from_str(b)?),
+        _ => return Err(String::from("expected `A OP B` (like `2 ** 200`) or a single number")),
+    };
+
+    let result = match op {
+        "+" => &a + &b,
+        "-" => &a - &b,
+        "*" => &a * &b,
+        "/" | "%" => {
+            let (quotient, remainder) = a.checked_div_rem(&b).ok_or(if op == "/" {
+                "attempt to divide by zero"
+            } else {
+                "attempt to calculate the remainder with a divisor of zero"
+            })?;
+            if op == "/" { quotient } else { remainder }
+        }
+        "**" => {
+            let exponent = b.to_i128().and_then(|b| u32::try_from(b).ok())
+                .ok_or("the exponent has to be a u32")?;
+            a.pow(exponent)
+        }
+        "<" | "<=" | ">" | ">=" | "==" | "!=" => {
+            let ordering = a.cmp(&b);
+            let holds = match op {
+                "<" => ordering == Ordering::Less,
+                "<=" => ordering != Ordering::Greater,
+                ">" => ordering == Ordering::Greater,
+                ">=" => ordering != Ordering::Less,
+                "==" => ordering == Ordering::Equal,
+                _ => ordering != Ordering::Equal,
+            };
+            return Ok(holds.to_string());
+        }
+        other => return Err(format!("unknown operator `{}`, try + - * / % ** or a comparison", other)),
+    };
+
+    let mut out = format!("{}\n    {} bits", result, result.magnitude().bits());
+    if result.magnitude().bits() > 64 {
+        out.push_str(&format!(", {:#x}", result.magnitude()));
+    }
+    if let (Some(a), Some(b)) = (a.to_i128(), b.to_i128()) {
+        out.push_str(&format!("\n    {}", primitive!(i128, a, op, b)));
+    } else if let (false, false, Some(a), Some(b)) = (a.is_negative(), b.is_negative(), a.magnitude().to_u128(), b.magnitude().to_u128()) {
+        out.push_str(&format!("\n    {}", primitive!(u128, a, op, b)));
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // seeded the same every time so a failure can be run again
+    fn xorshift(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    // random bits cut to a random length, so small operands come up as often as huge ones
+    fn random_u128(state: &mut u64) -> u128 {
+        let bits = (u128::from(xorshift(state)) << 64) | u128::from(xorshift(state));
+        bits >> (xorshift(state) % 128)
+    }
+
+    fn random_limbs(state: &mut u64, count: usize) -> BigUint {
+        let mut limbs: Vec<u32> = (0..count).map(|_| xorshift(state) as u32).collect();
+        if let Some(top) = limbs.last_mut() {
+            *top |= 1 << 31;
+        }
+        BigUint::from_limbs(limbs)
+    }
+
+    // zero, one and the edges of each limb size
+    fn unsigned_edges() -> Vec<u128> {
+        let mut values = vec![0, 1, 2];
+        for bits in [32, 64, 96, 128].iter() {
+            let top = u128::MAX >> (128 - bits);
+            values.extend([top - 1, top, top / 2 + 1]);
+        }
+        values
+    }
+
+    fn signed_edges() -> Vec<i128> {
+        vec![0, 1, -1, 2, -2, i128::MAX, i128::MIN, i128::MIN + 1, i64::MIN.into(), u64::MAX.into()]
+    }
+
+    fn check_unsigned(a: u128, b: u128) {
+        let (x, y) = (BigUint::from(a), BigUint::from(b));
+        assert_eq!((&x + &y).to_u128(), a.checked_add(b), "{} + {}", a, b);
+        assert_eq!(x.checked_sub(&y).and_then(|d| d.to_u128()), a.checked_sub(b), "{} - {}", a, b);
+        assert_eq!((&x * &y).to_u128(), a.checked_mul(b), "{} * {}", a, b);
+        let (quotient, remainder) = match x.checked_div_rem(&y) {
+            Some((q, r)) => (q.to_u128(), r.to_u128()),
+            None => (None, None),
+        };
+        assert_eq!(quotient, a.checked_div(b), "{} / {}", a, b);
+        assert_eq!(remainder, a.checked_rem(b), "{} % {}", a, b);
+        assert_eq!(x.cmp(&y), a.cmp(&b), "{} cmp {}", a, b);
+        assert_eq!(x.to_string(), a.to_string());
+        assert_eq!(format!("{:x}", x), format!("{:x}", a));
+        assert_eq!(a.to_string().parse::<BigUint>(), Ok(x));
+    }
+
+    fn check_signed(a: i128, b: i128) {
+        let (x, y) = (BigInt::from(a), BigInt::from(b));
+        assert_eq!((&x + &y).to_i128(), a.checked_add(b), "{} + {}", a, b);
+        assert_eq!((&x - &y).to_i128(), a.checked_sub(b), "{} - {}", a, b);
+        assert_eq!((&x * &y).to_i128(), a.checked_mul(b), "{} * {}", a, b);
+        assert_eq!((-&x).to_i128(), a.checked_neg(), "-{}", a);
+        let (quotient, remainder) = match x.checked_div_rem(&y) {
+            Some((q, r)) => (q.to_i128(), r.to_i128()),
+            None => (None, None),
+        };
+        assert_eq!(quotient, a.checked_div(b), "{} / {}", a, b);
+        // i128::MIN % -1 only fails because the division beside it would overflow. the remainder
+        // itself is 0, which is what BigInt gives
+        assert_eq!(remainder, if b == 0 { None } else { Some(a.wrapping_rem(b)) }, "{} % {}", a, b);
+        assert_eq!(x.cmp(&y), a.cmp(&b), "{} cmp {}", a, b);
+        assert_eq!(x.to_string(), a.to_string());
+        assert_eq!(a.to_string().parse::<BigInt>(), Ok(x));
+    }
+
+    // past the karatsuba threshold nothing primitive can hold the operands, so this checks the
+    // product against schoolbook multiplication, against u128 arithmetic modulo a random u64 and
+    // by dividing it back out, and checks the big results print and parse
+    fn check_karatsuba(x: &BigUint, y: &BigUint, state: &mut u64) {
+        let what = format!("{}-limb × {}-limb", x.limbs.len(), y.limbs.len());
+        let product = x * y;
+        assert_eq!(product.limbs, schoolbook(&x.limbs, &y.limbs), "{}", what);
+
+        let modulus = xorshift(state) | 1;
+        let m = BigUint::from(modulus);
+        let reduce = |big: &BigUint| (big % &m).to_u128().expect("below a u64");
+        let expected = reduce(x) * reduce(y) % u128::from(modulus);
+        assert_eq!(reduce(&product), expected, "{} mod {}", what, modulus);
+        assert_eq!(reduce(&(&product + &BigUint::from(1u32))), (expected + 1) % u128::from(modulus), "{} + 1 mod {}", what, modulus);
+
+        // a remainder smaller than y on top, which dividing by y should hand back untouched
+        let extra = random_limbs(state, y.limbs.len() - 1);
+        let (quotient, remainder) = (&product + &extra).checked_div_rem(y).expect("y isn't zero");
+        assert_eq!(&quotient, x, "({} + r) / y", what);
+        assert_eq!(remainder, extra, "({} + r) % y", what);
+
+        let larger = &product + &BigUint::from(1u32);
+        assert_eq!(product.cmp(&larger), Ordering::Less, "{} cmp itself + 1", what);
+        assert_eq!(product.cmp(x), if y.limbs == [1] { Ordering::Equal } else { Ordering::Greater }, "{} cmp x", what);
+
+        // the last 30 digits are the product mod 10^30, which fits in a u128
+        let text = product.to_string();
+        let tail = (&product % &BigUint::from(10u128.pow(30))).to_u128().expect("below 10^30");
+        assert_eq!(&text[text.len() - 30..], &format!("{:030}", tail)[..], "the last digits of {}", what);
+        assert_eq!(text.parse::<BigUint>(), Ok(product.clone()), "parsing {}", what);
+
+        let negative = -BigInt::from(product.clone());
+        assert_eq!(negative.to_string().parse::<BigInt>(), Ok(negative.clone()), "parsing -({})", what);
+        let (quotient, _) = negative.checked_div_rem(&BigInt::from(y.clone())).expect("y isn't zero");
+        assert_eq!(quotient, -BigInt::from(x.clone()), "-({}) / y", what);
+    }
+
+    #[test]
+    fn unsigned_edges_agree_with_u128() {
+        let edges = unsigned_edges();
+        for &a in &edges {
+            for &b in &edges {
+                check_unsigned(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn random_unsigned_agree_with_u128() {
+        let mut state = 0x9e37_79b9_7f4a_7c15;
+        let values: Vec<u128> = (0..20_000).map(|_| random_u128(&mut state)).collect();
+        for pair in values.windows(2) {
+            check_unsigned(pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn signed_edges_agree_with_i128() {
+        let edges = signed_edges();
+        for &a in &edges {
+            for &b in &edges {
+                check_signed(a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn random_signed_agree_with_i128() {
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        let mut values = Vec::new();
+        for _ in 0..10_000 {
+            let n = random_u128(&mut state) as i128;
+            values.push(n);
+            values.push(n.wrapping_neg());
+        }
+        for pair in values.windows(2) {
+            check_signed(pair[0], pair[1]);
+        }
+    }
+
+    #[test]
+    fn karatsuba_agrees_with_schoolbook() {
+        let mut state = 0x1234_5678_9abc_def1;
+        for &(a, b) in [(32, 32), (33, 47), (64, 40), (100, 100), (150, 80), (257, 256)].iter() {
+            for _ in 0..20 {
+                let (x, y) = (random_limbs(&mut state, a), random_limbs(&mut state, b));
+                check_karatsuba(&x, &y, &mut state);
+            }
+        }
+        let x = random_limbs(&mut state, 64);
+        check_karatsuba(&x, &BigUint::from(1u32), &mut state);
+    }
+}
@@ -0,0 +1,38 @@
+// usage: big-lab [A OP B]
+// with no arguments it reads one operation per line until `quit`
+use std::env;
+use std::io;
+
+use labs::bigint;
+
+fn show(line: &str) {
+    match bigint::evaluate(line) {
+        Ok(result) => println!("{}", result),
+        Err(e) => println!("error: {}", e),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        show(&args.join(" "));
+        return;
+    }
+
+    println!("type an operation like `2 ** 200`, `0xffff_ffff_ffff_ffff_ffff_ffff_ffff_ffff + 1` or `-7 % 3` (`quit` to stop)");
+    println!("operators: + - * / % ** < <= > >= == !=");
+
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        if read == 0 || line.trim() == "quit" {
+            break;
+        }
+        if !line.trim().is_empty() {
+            show(&line);
+        }
+    }
+}
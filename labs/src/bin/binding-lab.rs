@@ -0,0 +1,49 @@
+// usage: binding-lab [FILE]
+// traces the bindings in FILE. with no arguments it goes through the examples from ch3 and then
+// reads snippets, one statement per line, each ended by an empty line (`quit` to stop)
+use std::env;
+use std::fs;
+use std::io;
+
+use labs::bindings;
+
+fn show(source: &str) {
+    println!("{}", bindings::trace(source));
+}
+
+fn main() {
+    if let Some(path) = env::args().nth(1) {
+        let source = fs::read_to_string(&path).expect("failed to read file");
+        show(&source);
+        return;
+    }
+
+    for example in bindings::EXAMPLES.iter() {
+        show(example);
+    }
+
+    println!("type a snippet and end it with an empty line (`quit` to stop)");
+    let mut source = String::new();
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        if read == 0 || trimmed == "quit" {
+            if !source.trim().is_empty() {
+                show(&source);
+            }
+            break;
+        }
+        if trimmed.trim().is_empty() {
+            if !source.trim().is_empty() {
+                show(&source);
+            }
+            source.clear();
+        } else {
+            source.push_str(&line);
+        }
+    }
+}
@@ -0,0 +1,40 @@
+// usage: bits-lab [[TYPE] A OP B | [TYPE] METHOD A | [TYPE] !A | [TYPE] from_be_bytes BYTES]
+// with no arguments it reads one operation per line until `quit`
+use std::env;
+use std::io;
+
+use labs::bits;
+
+fn show(line: &str) {
+    match bits::evaluate(line) {
+        Ok(report) => println!("{}", report),
+        Err(e) => println!("error: {}", e),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        show(&args.join(" "));
+        return;
+    }
+
+    println!("type an operation like `u8 0b1111_0000 >> 2`, `0x1234u16 ^ 0xff`, `i8 !0` or `u32 swap_bytes 0x12345678` (`quit` to stop)");
+    println!("operators: & | ^ << >> rotate_left rotate_right");
+    println!("methods: count_ones count_zeros leading_zeros trailing_zeros swap_bytes reverse_bits to_be_bytes to_le_bytes");
+    println!("and `from_be_bytes [0x12, 0x34]` or `from_le_bytes ...`, with a type in front for signed results");
+
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        if read == 0 || line.trim() == "quit" {
+            break;
+        }
+        if !line.trim().is_empty() {
+            show(&line);
+        }
+    }
+}
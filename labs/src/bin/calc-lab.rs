@@ -0,0 +1,58 @@
+// usage: calc-lab [--release] [EXPRESSION]
+// with no expression it reads one per line until `quit`. `mode debug` and `mode release` switch
+// what integer overflow does
+use std::env;
+use std::io;
+
+use labs::calc::{self, Mode};
+
+fn show(line: &str, mode: Mode) {
+    match calc::evaluate(line, mode) {
+        Ok(outcome) => println!("{}\n", outcome),
+        Err(e) => println!("{}\n", e),
+    }
+}
+
+fn main() {
+    let mut mode = Mode::Debug;
+    let mut expression = Vec::new();
+    for arg in env::args().skip(1) {
+        if arg == "--release" {
+            mode = Mode::Release;
+        } else {
+            expression.push(arg);
+        }
+    }
+
+    if !expression.is_empty() {
+        show(&expression.join(" "), mode);
+        return;
+    }
+
+    println!("type an expression like `5 + 10`, `95.5 - 4.3`, `-7 / 2`, `255u8 + 1` or `300 as u8` (`quit` to stop)");
+    println!("`mode debug` or `mode release` switches what overflow does, it's {} right now", mode);
+
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        let line = line.trim();
+        if read == 0 || line == "quit" {
+            break;
+        }
+
+        if let Some(name) = line.strip_prefix("mode") {
+            match Mode::parse(name.trim()) {
+                Some(new_mode) => {
+                    mode = new_mode;
+                    println!("overflow now behaves like a {} build\n", mode);
+                }
+                None => println!("the modes are `debug` and `release`, it's {} right now\n", mode),
+            }
+        } else if !line.is_empty() {
+            show(line, mode);
+        }
+    }
+}
@@ -0,0 +1,49 @@
+// usage: cast-lab [--markdown] [matrix | all | TYPE | TYPE TYPE]
+// with no query it reads one per line until `quit`. --markdown prints tables that can be pasted
+// into the notes
+use std::env;
+use std::io;
+
+use labs::cast;
+
+fn show(line: &str, markdown: bool) {
+    match cast::evaluate(line, markdown) {
+        Ok(report) => println!("{}", report),
+        Err(e) => println!("error: {}\n", e),
+    }
+}
+
+fn main() {
+    let mut markdown = false;
+    let mut query = Vec::new();
+    for arg in env::args().skip(1) {
+        if arg == "--markdown" {
+            markdown = true;
+        } else {
+            query.push(arg);
+        }
+    }
+
+    if !query.is_empty() {
+        show(&query.join(" "), markdown);
+        return;
+    }
+
+    println!("{}\n", cast::matrix());
+    println!("type a pair like `f64 u8` or `char as u8` to see the values, a type like `i32` for");
+    println!("everything it converts to, `matrix`, or `all` (`quit` to stop)");
+
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        if read == 0 || line.trim() == "quit" {
+            break;
+        }
+        if !line.trim().is_empty() {
+            show(&line, markdown);
+        }
+    }
+}
@@ -0,0 +1,42 @@
+// usage: char-lab [TEXT...]
+// with no arguments it goes through a few examples and then reads one string per line until
+// `quit`. escapes like `\u{301}` work in both, so invisible chars can be typed in
+use std::env;
+use std::io;
+
+use labs::chars;
+
+fn show(line: &str) {
+    match chars::unescape(line) {
+        Ok(text) => println!("{}", chars::inspect(&text)),
+        Err(e) => println!("error: {}\n", e),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        show(&args.join(" "));
+        return;
+    }
+
+    for example in chars::EXAMPLES.iter() {
+        println!("{}", chars::inspect(example));
+    }
+
+    println!("type any text, escapes like `\\u{{301}}` work too (`quit` to stop)");
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        let line = line.trim_end_matches(['\r', '\n']);
+        if read == 0 || line == "quit" {
+            break;
+        }
+        if !line.is_empty() {
+            show(line);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+// usage: compound-lab
+// a playground for tuples and arrays. it goes through the compound types examples from ch3 and
+// then runs one line at a time, keeping every binding around, until `quit`. each new value gets
+// its size and where its parts sit in memory
+use std::io;
+
+use labs::bindings::Trace;
+use labs::compound;
+
+// prints the steps from `from` on, and whatever stopped them
+fn show(trace: &mut Trace, from: usize) {
+    for step in &trace.steps[from..] {
+        print!("{}", step);
+    }
+    if let Some(stopped) = trace.stopped.take() {
+        println!("\n{}", stopped);
+    }
+    println!();
+}
+
+fn main() {
+    for example in compound::EXAMPLES.iter() {
+        let mut trace = Trace::new(true);
+        trace.feed(example);
+        show(&mut trace, 0);
+    }
+
+    println!("type statements like `let (x, y) = (1, 2.0);`, `let a = [3; 5];` or `a[7]` (`quit` to stop)");
+    let mut trace = Trace::new(true);
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        let line = line.trim();
+        if read == 0 || line == "quit" {
+            break;
+        }
+        if !line.is_empty() {
+            let from = trace.steps.len();
+            trace.feed(line);
+            show(&mut trace, from);
+        }
+    }
+}
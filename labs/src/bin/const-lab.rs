@@ -0,0 +1,48 @@
+// usage: const-lab [FILE]
+// evaluates every `const` item in FILE the way the compiler would. with no arguments it goes
+// through an example and then reads items, ended by an empty line (`quit` to stop)
+use std::env;
+use std::fs;
+use std::io;
+
+use labs::consts;
+
+fn show(source: &str) {
+    println!("{}", consts::check(source));
+}
+
+fn main() {
+    if let Some(path) = env::args().nth(1) {
+        let source = fs::read_to_string(&path).expect("failed to read file");
+        show(&source);
+        return;
+    }
+
+    println!("{}", consts::EXAMPLE);
+    show(consts::EXAMPLE);
+
+    println!("type some `const` items and end them with an empty line (`quit` to stop)");
+    let mut source = String::new();
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        let trimmed = line.trim_end_matches(['\r', '\n']);
+        if read == 0 || trimmed == "quit" {
+            if !source.trim().is_empty() {
+                show(&source);
+            }
+            break;
+        }
+        if trimmed.trim().is_empty() {
+            if !source.trim().is_empty() {
+                show(&source);
+            }
+            source.clear();
+        } else {
+            source.push_str(&line);
+        }
+    }
+}
@@ -0,0 +1,80 @@
+// usage: countdown-lab [START] [--every INTERVAL] [--hold AT[+FOR]]... [--keys KEY@TIME,...] [--on-liftoff COMMAND]
+// times look like 90, 1:30, 01:00:00, 500ms, 15m or 2h. while it counts, type p, r or a and
+// press enter to pause, resume or abort. `--keys p@2.5,r@10` plays those keys back on a
+// simulated clock instead, which finishes straight away. with no arguments it shows a few
+// simulated countdowns, then reads one countdown per line until `quit`
+use std::env;
+use std::io;
+use std::process;
+use std::sync::mpsc::{self, Receiver};
+use std::thread;
+
+use labs::countdown::{self, Outcome, SystemClock};
+
+// the countdown and the prompt both read from here, so the lines typed during a countdown go
+// to it and the rest to the prompt
+fn read_lines() -> Receiver<String> {
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        if read == 0 || sender.send(line).is_err() {
+            break;
+        }
+    });
+    receiver
+}
+
+fn launch(line: &str, keys: &Receiver<String>) -> Result<Outcome, String> {
+    let settings = countdown::parse(line)?;
+    let outcome = match settings.script {
+        Some(script) => {
+            let (lines, outcome) = countdown::simulate(&settings.countdown, script);
+            println!("{}", lines.join("\n"));
+            outcome
+        }
+        None => {
+            println!("p to pause, r to resume, a to abort (then enter)");
+            let mut clock = SystemClock::new(keys);
+            settings.countdown.run(&mut clock, &mut |_, message| println!("{}", message))
+        }
+    };
+    if let (Outcome::Liftoff, Some(hook)) = (outcome, &settings.hook) {
+        println!("{}", countdown::run_hook(hook)?);
+    }
+    Ok(outcome)
+}
+
+fn main() {
+    let lines = read_lines();
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        match launch(&args.join(" "), &lines) {
+            Ok(Outcome::Liftoff) => {}
+            Ok(Outcome::Aborted(_)) => process::exit(1),
+            Err(e) => {
+                println!("error: {}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
+
+    println!("{}\n", countdown::demo());
+    println!("type a countdown like `10`, `1:00 --every 5 --hold 30`, `5 --keys p@2,r@4` or `3 --on-liftoff echo hello` (`quit` to stop)");
+
+    for line in lines.iter() {
+        if line.trim() == "quit" {
+            break;
+        }
+        if !line.trim().is_empty() {
+            match launch(&line, &lines) {
+                Ok(_) => println!(),
+                Err(e) => println!("error: {}\n", e),
+            }
+        }
+    }
+}
@@ -0,0 +1,40 @@
+// usage: decimal-lab [A OP B [SCALE] [ROUNDING]]
+// with no arguments it shows where f64 and exact decimals disagree, then reads one operation
+// per line until `quit`
+use std::env;
+use std::io;
+
+use labs::decimal;
+
+fn show(line: &str) {
+    match decimal::evaluate(line) {
+        Ok(report) => println!("{}", report),
+        Err(e) => println!("error: {}", e),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        show(&args.join(" "));
+        return;
+    }
+
+    println!("{}\n", decimal::demo());
+    println!("type an operation like `95.5 - 4.3`, `1_000.05 * 3` or `1 / 3 5 half-up` (`quit` to stop)");
+    println!("operators: + - * and / SCALE ROUNDING, where ROUNDING is half-even, half-up, floor, ceil or truncate");
+
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        if read == 0 || line.trim() == "quit" {
+            break;
+        }
+        if !line.trim().is_empty() {
+            show(&line);
+        }
+    }
+}
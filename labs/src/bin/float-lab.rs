@@ -0,0 +1,39 @@
+// usage: float-lab [VALUE | A OP B | bits WIDTH HEX]
+// with no arguments it reads one line at a time until `quit`
+use std::env;
+use std::io;
+
+use labs::float;
+
+fn show(line: &str) {
+    match float::evaluate(line) {
+        Ok(report) => println!("{}", report),
+        Err(e) => println!("error: {}\n", e),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        show(&args.join(" "));
+        return;
+    }
+
+    println!("type a value like `0.1`, `1e-40f32` or `nan`, an operation like `56.7 / 32.2` or `0.1 + 0.2`,");
+    println!("or raw bits like `f64 bits 0x7ff0000000000001` (`quit` to stop)");
+    println!("put `f32` or `f64` in front to pick the width when there's no suffix");
+
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        if read == 0 || line.trim() == "quit" {
+            break;
+        }
+        if !line.trim().is_empty() {
+            show(&line);
+        }
+    }
+}
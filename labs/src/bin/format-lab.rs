@@ -0,0 +1,39 @@
+// usage: format-lab [[TYPE] VALUE [OPTION...]]
+// with no arguments it formats the numbers from ch3, then reads one value per line until `quit`
+use std::env;
+use std::io;
+
+use labs::format;
+
+fn show(line: &str) {
+    match format::evaluate(line) {
+        Ok(report) => println!("{}", report),
+        Err(e) => println!("error: {}", e),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        show(&args.join(" "));
+        return;
+    }
+
+    println!("{}\n", format::demo());
+    println!("type a value like `100_000`, `f32 0.1`, `decimal 9.50` or `1536 binary unit=B sig=2` (`quit` to stop)");
+    println!("options: locale=plain|en|de|fr|ch|in|rust places=N sig=N sci eng radix=N si binary unit=U round=MODE");
+
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        if read == 0 || line.trim() == "quit" {
+            break;
+        }
+        if !line.trim().is_empty() {
+            show(&line);
+        }
+    }
+}
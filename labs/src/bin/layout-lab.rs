@@ -0,0 +1,14 @@
+// usage: layout-lab [SECTION | TYPE]
+// with no arguments it prints everything: primitives, tuples, arrays, strings and niches. a
+// section name prints just that one, and a type like `(i32, f64, u8)` just that type
+use std::env;
+
+use labs::layout;
+
+fn main() {
+    let query = env::args().skip(1).collect::<Vec<String>>().join(" ");
+    match layout::evaluate(&query) {
+        Ok(report) => print!("{}", report),
+        Err(e) => println!("error: {}", e),
+    }
+}
@@ -0,0 +1,70 @@
+// usage: literal-lab [--group N] [LITERAL...]
+// --group sets how many digits go between underscores in the conversions (0 turns it off).
+// with no literals it reads one per line until `quit`
+use std::env;
+use std::io;
+use std::process;
+
+use labs::literal;
+
+fn show(source: &str, group: Option<usize>) {
+    let source = source.trim();
+    let literal = match literal::parse(source) {
+        Ok(literal) => literal,
+        Err(e) => {
+            println!("{}\n", e.render(source));
+            return;
+        }
+    };
+
+    println!("{}", literal);
+    if let Some(error) = literal.range_error() {
+        println!("{}", error.render(source));
+    }
+    for (name, converted) in literal.conversions(group) {
+        println!("{:<11} {}", name, converted);
+    }
+    println!();
+}
+
+fn main() {
+    let mut group = None;
+    let mut literals = Vec::new();
+
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        if arg == "--group" {
+            group = match args.next().map(|n| n.parse()) {
+                Some(Ok(n)) => Some(n),
+                _ => {
+                    eprintln!("--group needs a number");
+                    process::exit(2);
+                }
+            };
+        } else {
+            literals.push(arg);
+        }
+    }
+
+    if !literals.is_empty() {
+        for source in &literals {
+            show(source, group);
+        }
+        return;
+    }
+
+    println!("type a literal like `98_222`, `0xff`, `0o77`, `0b1111_0000`, `b'A'`, `255u8` or `1e3f32` (`quit` to stop)");
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        if read == 0 || line.trim() == "quit" {
+            break;
+        }
+        if !line.trim().is_empty() {
+            show(&line, group);
+        }
+    }
+}
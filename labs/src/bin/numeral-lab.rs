@@ -0,0 +1,38 @@
+// usage: numeral-lab [[SYSTEM] TEXT]
+// with no arguments it reads one number per line until `quit`
+use std::env;
+use std::io;
+
+use labs::numerals;
+
+fn show(line: &str) {
+    match numerals::evaluate(line) {
+        Ok(report) => println!("{}\n", report),
+        Err(e) => println!("error: {}\n", e),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        show(&args.join(" "));
+        return;
+    }
+
+    println!("type a number in any of the systems, like `98222`, `MCMXCIV`, `forty two`, `42nd`, `twenty-first` or `1T0` (`quit` to stop)");
+    println!("put roman, words, ordinal, ternary or number in front if it's read the wrong way");
+
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        if read == 0 || line.trim() == "quit" {
+            break;
+        }
+        if !line.trim().is_empty() {
+            show(&line);
+        }
+    }
+}
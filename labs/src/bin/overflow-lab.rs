@@ -0,0 +1,39 @@
+// usage: overflow-lab [TYPE A OP B]
+// with no arguments it reads one operation per line until `quit`
+use std::env;
+use std::io;
+
+use labs::overflow;
+
+fn show(line: &str) {
+    match overflow::evaluate(line) {
+        Ok(report) => println!("{}", report),
+        Err(e) => println!("error: {}", e),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        show(&args.join(" "));
+        return;
+    }
+
+    println!("type an operation like `u8 250 + 10`, `i32 1 << 40` or `i8 - -128` (`quit` to stop)");
+    println!("types: {}", overflow::TYPES.join(" "));
+    println!("operators: + - * / % << >> and unary -");
+
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        if read == 0 || line.trim() == "quit" {
+            break;
+        }
+        if !line.trim().is_empty() {
+            show(&line);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// usage: policy-lab [TYPE A OP B ...]
+//        policy-lab bench [ROUNDS]
+// runs the operations under each overflow policy. with no arguments it reads one line at a time
+// until `quit`. `bench` times the policies against each other, best with --release
+use std::env;
+use std::io;
+use std::process;
+
+use labs::overflow;
+use labs::policy;
+
+fn show(line: &str) {
+    match policy::evaluate(line) {
+        Ok(report) => println!("{}", report),
+        Err(e) => println!("error: {}", e),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().map(String::as_str) == Some("bench") {
+        let rounds = match args.get(1).map(|rounds| rounds.parse()) {
+            Some(Ok(rounds)) => rounds,
+            Some(Err(_)) => {
+                println!("error: ROUNDS has to be a number, like `bench 1000000`");
+                process::exit(1);
+            }
+            None => 10_000_000,
+        };
+        println!("{}", policy::bench(rounds));
+        return;
+    }
+    if !args.is_empty() {
+        show(&args.join(" "));
+        return;
+    }
+
+    println!("type an operation like `u8 200 + 100 - 60` or `i32 1 << 40` (`quit` to stop)");
+    println!("types: {}", overflow::TYPES.join(" "));
+    println!("operators: + - * / % << >>, applied left to right");
+
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        if read == 0 || line.trim() == "quit" {
+            break;
+        }
+        if !line.trim().is_empty() {
+            show(&line);
+        }
+    }
+}
@@ -0,0 +1,39 @@
+// usage: prime-lab [N | factor N | prime N | totient N | gcd A B | lcm A B | sieve N]
+// with no arguments it explains the divisors of 360, then reads one line at a time until `quit`
+use std::env;
+use std::io;
+
+use labs::primes;
+
+fn show(line: &str) {
+    match primes::evaluate(line) {
+        Ok(report) => println!("{}\n", report),
+        Err(e) => println!("error: {}\n", e),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        show(&args.join(" "));
+        return;
+    }
+
+    show("360");
+    println!("type a number to see all its divisors and why, or `factor N`, `prime N`, `totient N`,");
+    println!("`gcd A B`, `lcm A B` or `sieve N` (`quit` to stop)");
+
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        if read == 0 || line.trim() == "quit" {
+            break;
+        }
+        if !line.trim().is_empty() {
+            show(&line);
+        }
+    }
+}
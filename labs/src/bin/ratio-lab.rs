@@ -0,0 +1,40 @@
+// usage: ratio-lab [[TYPE] A OP B | [TYPE] A | [TYPE] approx X [TOLERANCE]]
+// with no arguments it redoes the ch3 division as a fraction and pulls fractions out of f64s,
+// then reads one line at a time until `quit`
+use std::env;
+use std::io;
+
+use labs::ratio;
+
+fn show(line: &str) {
+    match ratio::evaluate(line) {
+        Ok(report) => println!("{}", report),
+        Err(e) => println!("error: {}", e),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        show(&args.join(" "));
+        return;
+    }
+
+    println!("{}\n", ratio::demo());
+    println!("type something like `56.7 / 32.2`, `u8 3/4 + 1/3`, `-7/3`, `1/3 < 0.333` or `approx 3.14159 1e-3` (`quit` to stop)");
+    println!("operators: + - * / % and the comparisons, on i64 unless a type comes first");
+
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        if read == 0 || line.trim() == "quit" {
+            break;
+        }
+        if !line.trim().is_empty() {
+            show(&line);
+        }
+    }
+}
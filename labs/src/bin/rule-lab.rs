@@ -0,0 +1,40 @@
+// usage: rule-lab [[check] FILE|NAME [N | FROM TO | FROM..TO | FROM..=TO]]
+// NAME is one of the bundled rule sets: controlflow, fizzbuzz, fizzbuzzbazz, team or reordered.
+// `check` lists the rules with how often each fires and flags the ones that never can. with no
+// arguments it runs a few of the bundled sets, then reads one line at a time until `quit`
+use std::env;
+use std::io;
+
+use labs::rules;
+
+fn show(line: &str) {
+    match rules::evaluate(line) {
+        Ok(report) => println!("{}\n", report),
+        Err(e) => println!("error: {}\n", e),
+    }
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if !args.is_empty() {
+        show(&args.join(" "));
+        return;
+    }
+
+    println!("{}\n", rules::demo());
+    println!("type a rule file or a bundled set, like `fizzbuzzbazz 100 110`, `team` or `check my-rules.toml` (`quit` to stop)");
+
+    loop {
+        let mut line = String::new();
+        let read = io::stdin()
+            .read_line(&mut line)
+            .expect("failed to read line");
+
+        if read == 0 || line.trim() == "quit" {
+            break;
+        }
+        if !line.trim().is_empty() {
+            show(&line);
+        }
+    }
+}
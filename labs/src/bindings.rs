@@ -0,0 +1,1585 @@
+// steps through a snippet like ch3/varsandmutability.rs and keeps a timeline of every binding:
+// each `let` makes a new one (even with a name that's already taken), assignment changes the
+// value inside an existing one, and a block's bindings disappear at its `}`.
+//
+// it only knows the part of rust that file uses: `let`, `let mut`, `const`, assignment (`=`,
+// `+=` and friends), blocks, `println!`, integer/float/bool/char/string literals, arithmetic,
+// `.len()`, `.trim()`, `.parse()` and `.expect()`, and the compound types: tuples, arrays,
+// `[3; 5]`, destructuring, `tup.0` and `a[i]`, which are evaluated in compound.rs. anything else
+// stops the trace. every binding gets a number, so `y#2` is the second binding called `y`
+
+use std::fmt;
+
+use crate::compound;
+use crate::literal::{self, integer_type, Value as LiteralValue, INTEGER_TYPES};
+use crate::overflow::Op;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum Ty {
+    Int(&'static str),
+    Float(&'static str),
+    // an integer or float literal nobody has pinned to a type yet
+    IntVar,
+    FloatVar,
+    Bool,
+    Char,
+    Str,
+    Tuple(Vec<Ty>),
+    Array(Box<Ty>, usize),
+    // what `.parse()` gives back, and what `.expect()` on it gives back before the type is known
+    ParseResult,
+    Unknown,
+}
+
+impl Ty {
+    fn named(name: &str) -> Option<Ty> {
+        match name {
+            "f32" => Some(Ty::Float("f32")),
+            "f64" => Some(Ty::Float("f64")),
+            "bool" => Some(Ty::Bool),
+            "char" => Some(Ty::Char),
+            "&str" => Some(Ty::Str),
+            _ => INTEGER_TYPES.iter().find(|t| **t == name).map(|t| Ty::Int(t)),
+        }
+    }
+
+    pub(crate) fn or_default(&self) -> Ty {
+        match self {
+            Ty::IntVar => Ty::Int("i32"),
+            Ty::FloatVar => Ty::Float("f64"),
+            Ty::Tuple(fields) => Ty::Tuple(fields.iter().map(Ty::or_default).collect()),
+            Ty::Array(element, length) => Ty::Array(Box::new(element.or_default()), *length),
+            ty => ty.clone(),
+        }
+    }
+
+    // true while some part of it still waits for `.parse()` to be told its type
+    fn unresolved(&self) -> bool {
+        match self {
+            Ty::ParseResult | Ty::Unknown => true,
+            Ty::Tuple(fields) => fields.iter().any(Ty::unresolved),
+            Ty::Array(element, _) => element.unresolved(),
+            _ => false,
+        }
+    }
+
+    // the type both sides can have, so `{integer}` and `u8` make `u8`
+    pub(crate) fn unify(&self, other: &Ty) -> Option<Ty> {
+        match (self, other) {
+            (a, b) if a == b => Some(a.clone()),
+            (Ty::IntVar, Ty::Int(_)) | (Ty::FloatVar, Ty::Float(_)) => Some(other.clone()),
+            (Ty::Int(_), Ty::IntVar) | (Ty::Float(_), Ty::FloatVar) => Some(self.clone()),
+            (Ty::Tuple(a), Ty::Tuple(b)) if a.len() == b.len() => {
+                a.iter().zip(b).map(|(a, b)| a.unify(b)).collect::<Option<Vec<Ty>>>().map(Ty::Tuple)
+            }
+            (Ty::Array(a, n), Ty::Array(b, m)) if n == m => a.unify(b).map(|t| Ty::Array(Box::new(t), *n)),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Ty::Int(name) | Ty::Float(name) => write!(f, "{}", name),
+            Ty::IntVar => write!(f, "{{integer}}"),
+            Ty::FloatVar => write!(f, "{{float}}"),
+            Ty::Bool => write!(f, "bool"),
+            Ty::Char => write!(f, "char"),
+            Ty::Str => write!(f, "&str"),
+            Ty::Tuple(fields) if fields.len() == 1 => write!(f, "({},)", fields[0]),
+            Ty::Tuple(fields) => write!(f, "({})", fields.iter().map(Ty::to_string).collect::<Vec<String>>().join(", ")),
+            Ty::Array(element, length) => write!(f, "[{}; {}]", element, length),
+            Ty::ParseResult => write!(f, "Result<_, _>"),
+            Ty::Unknown => write!(f, "_"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Value {
+    Int(i128),
+    Float(f64),
+    Bool(bool),
+    Char(char),
+    Str(String),
+    Tuple(Vec<Value>),
+    Array(Vec<Value>),
+    // text waiting to be parsed once its type is known, and the `.expect()` message if any
+    Parse(String, Option<String>),
+}
+
+// what `{:?}` would print
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let list = |values: &[Value]| values.iter().map(Value::to_string).collect::<Vec<String>>().join(", ");
+        match self {
+            Value::Int(v) => write!(f, "{}", v),
+            Value::Float(v) => write!(f, "{:?}", v),
+            Value::Bool(v) => write!(f, "{}", v),
+            Value::Char(v) => write!(f, "{:?}", v),
+            Value::Str(v) => write!(f, "{:?}", v),
+            Value::Tuple(values) if values.len() == 1 => write!(f, "({},)", values[0]),
+            Value::Tuple(values) => write!(f, "({})", list(values)),
+            Value::Array(values) => write!(f, "[{}]", list(values)),
+            Value::Parse(text, None) => write!(f, "{:?}.parse()", text),
+            Value::Parse(text, Some(message)) => write!(f, "{:?}.parse().expect({:?})", text, message),
+        }
+    }
+}
+
+impl Value {
+    // the value for a note, with long arrays cut short so `[0u64; 100]` doesn't print 100 zeros
+    pub(crate) fn brief(&self) -> String {
+        let list = |values: &[Value]| values.iter().map(Value::brief).collect::<Vec<String>>().join(", ");
+        match self {
+            Value::Array(values) if values.len() > 8 && values.iter().all(|v| *v == values[0]) => format!("[{}; {}]", values[0].brief(), values.len()),
+            Value::Array(values) if values.len() > 8 => format!("[{}, ... {} more]", list(&values[..6]), values.len() - 6),
+            Value::Array(values) => format!("[{}]", list(values)),
+            Value::Tuple(values) if values.len() == 1 => format!("({},)", values[0].brief()),
+            Value::Tuple(values) => format!("({})", list(values)),
+            value => value.to_string(),
+        }
+    }
+}
+
+// what stops the trace
+pub(crate) enum Problem {
+    // doesn't compile: (code, message, notes)
+    Error(Option<&'static str>, String, Vec<String>),
+    Panic(String),
+    // outside of what the tracer understands
+    Unsupported(String),
+}
+
+pub(crate) fn error(code: &'static str, message: String, notes: Vec<String>) -> Problem {
+    Problem::Error(Some(code), message, notes)
+}
+
+pub(crate) fn mismatch(expected: &Ty, found: &Ty) -> Problem {
+    error("E0308", String::from("mismatched types"), vec![format!("expected `{}`, found `{}`", expected, found)])
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Token {
+    Ident(String),
+    Number(String),
+    Str(String),
+    Char(char),
+    Punct(&'static str),
+    // anything else, which stops the trace if a statement gets to it
+    Unknown(char),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Ident(text) | Token::Number(text) => write!(f, "{}", text),
+            Token::Str(text) => write!(f, "{:?}", text),
+            Token::Char(c) => write!(f, "{:?}", c),
+            Token::Punct(p) => write!(f, "{}", p),
+            Token::Unknown(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+// longest first, so `+=` wins over `+`. everything rust lexes is here, even the operators the
+// tracer can't run, so `1 << 3` stops the trace as unsupported instead of as a parse error
+const PUNCTUATION: [&str; 45] = [
+    "<<=", ">>=", "..=", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "<<", ">>", "==", "!=", "<=", ">=", "&&", "||", "..", "::", "->",
+    "=>", "+", "-", "*", "/", "%", "=", ";", ":", ",", ".", "(", ")", "[", "]", "{", "}", "!", "&", "|", "^", "<", ">",
+];
+
+// keywords and operators that lex fine but that the tracer doesn't run
+const KEYWORDS: [&str; 16] = [
+    "as", "if", "else", "while", "loop", "for", "match", "return", "break", "continue", "struct", "enum", "impl", "use", "static", "unsafe",
+];
+
+const OPERATORS: [&str; 19] = ["<<=", ">>=", "&=", "|=", "^=", "<<", ">>", "==", "!=", "<=", ">=", "&&", "||", "!", "&", "|", "^", "<", ">"];
+
+fn unsupported(token: &Token) -> Option<Problem> {
+    let what = match token {
+        Token::Unknown(c) => format!("`{}`", c),
+        Token::Ident(word) if KEYWORDS.contains(&word.as_str()) => format!("`{}`", word),
+        Token::Punct(p @ ("..=" | "..")) => format!("ranges like `0{}5`", p),
+        Token::Punct(p @ ("::" | "->" | "=>")) => format!("`{}`", p),
+        Token::Punct(p) if OPERATORS.contains(p) => format!("the `{}` operator", p),
+        _ => return None,
+    };
+    Some(Problem::Unsupported(what))
+}
+
+pub(crate) struct Spanned {
+    pub(crate) token: Token,
+    pub(crate) line: usize,
+    pub(crate) start: usize,
+    pub(crate) end: usize,
+}
+
+fn unescape(c: char) -> Option<char> {
+    match c {
+        'n' => Some('\n'),
+        't' => Some('\t'),
+        'r' => Some('\r'),
+        '0' => Some('\0'),
+        '\\' => Some('\\'),
+        '\'' => Some('\''),
+        '"' => Some('"'),
+        _ => None,
+    }
+}
+
+// `punctuation` is every operator and delimiter the caller's parser knows, longest first. any
+// other symbol is `Token::Unknown`
+pub(crate) fn tokenize(source: &str, punctuation: &[&'static str]) -> Result<Vec<Spanned>, (usize, Problem)> {
+    let chars: Vec<(usize, char)> = source.char_indices().collect();
+    let at = |i: usize| chars.get(i).map(|(_, c)| *c);
+    let offset = |i: usize| chars.get(i).map(|(o, _)| *o).unwrap_or(source.len());
+
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut i = 0;
+    while let Some(c) = at(i) {
+        let start = i;
+        let start_line = line;
+
+        if c == '\n' {
+            line += 1;
+            i += 1;
+            continue;
+        }
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+        if c == '/' && at(i + 1) == Some('/') {
+            while at(i).is_some_and(|c| c != '\n') {
+                i += 1;
+            }
+            continue;
+        }
+        if c == '/' && at(i + 1) == Some('*') {
+            i += 2;
+            while at(i).is_some() && !(at(i) == Some('*') && at(i + 1) == Some('/')) {
+                if at(i) == Some('\n') {
+                    line += 1;
+                }
+                i += 1;
+            }
+            i += 2;
+            continue;
+        }
+
+        let token = if c == '"' {
+            let mut text = String::new();
+            i += 1;
+            loop {
+                match at(i) {
+                    None => return Err((start_line, Problem::Error(Some("E0765"), String::from("unterminated double quote string"), Vec::new()))),
+                    Some('"') => break,
+                    Some('\\') => {
+                        let escaped = at(i + 1).and_then(unescape).ok_or_else(|| {
+                            (line, Problem::Error(None, format!("unknown character escape: `{}`", at(i + 1).unwrap_or(' ')), Vec::new()))
+                        })?;
+                        text.push(escaped);
+                        i += 1;
+                    }
+                    Some(c) => {
+                        if c == '\n' {
+                            line += 1;
+                        }
+                        text.push(c);
+                    }
+                }
+                i += 1;
+            }
+            i += 1;
+            Token::Str(text)
+        } else if c == '\'' {
+            let (value, length) = match (at(i + 1), at(i + 2)) {
+                (Some('\\'), Some(e)) => (unescape(e), 3),
+                (Some(c), _) => (Some(c), 2),
+                _ => (None, 1),
+            };
+            if at(i + length) != Some('\'') || value.is_none() {
+                return Err((line, Problem::Error(None, String::from("character literal may only contain one codepoint"), Vec::new())));
+            }
+            i += length + 1;
+            Token::Char(value.expect("checked above"))
+        } else if c.is_ascii_digit() {
+            while let Some(c) = at(i) {
+                let point = c == '.' && at(i + 1).is_some_and(|n| n.is_ascii_digit());
+                let sign = matches!(c, '+' | '-') && matches!(at(i - 1), Some('e') | Some('E')) && !source[offset(start)..offset(i)].starts_with("0x");
+                if c.is_ascii_alphanumeric() || c == '_' || point || sign {
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            Token::Number(source[offset(start)..offset(i)].to_string())
+        } else if c.is_alphabetic() || c == '_' {
+            while at(i).is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                i += 1;
+            }
+            Token::Ident(source[offset(start)..offset(i)].to_string())
+        } else if let Some(p) = punctuation.iter().find(|p| source[offset(i)..].starts_with(**p)) {
+            i += p.len();
+            Token::Punct(p)
+        } else {
+            i += 1;
+            Token::Unknown(c)
+        };
+
+        tokens.push(Spanned { token, line: start_line, start: offset(start), end: offset(i) });
+    }
+    Ok(tokens)
+}
+
+#[derive(Debug)]
+pub(crate) enum Expr {
+    Literal(literal::Literal),
+    Bool(bool),
+    Char(char),
+    Str(String),
+    Var(String),
+    Neg(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+    Method(Box<Expr>, String, Vec<Expr>),
+    Tuple(Vec<Expr>),
+    Array(Vec<Expr>),
+    // `[value; count]`
+    Repeat(Box<Expr>, Box<Expr>),
+    Field(Box<Expr>, usize),
+    Index(Box<Expr>, Box<Expr>),
+}
+
+#[derive(Debug)]
+pub(crate) enum Pattern {
+    Name { mutable: bool, name: String },
+    Wildcard,
+    Tuple(Vec<Pattern>),
+    Array(Vec<Pattern>),
+}
+
+// one step from a binding into a part of it, on the left of an `=`
+#[derive(Debug)]
+pub(crate) enum Access {
+    Field(usize),
+    Index(Expr),
+}
+
+#[derive(Debug)]
+enum Stmt {
+    Let { pattern: Pattern, ty: Option<Ty>, value: Expr },
+    Const { name: String, ty: Option<Ty>, value: Expr },
+    // `op` is there for `+=` and friends
+    Assign { name: String, path: Vec<Access>, op: Option<Op>, value: Expr },
+    Print { format: String, args: Vec<Expr> },
+    // an expression on its own, which only the playground shows anything for
+    Expr(Expr),
+    Open,
+    Close,
+}
+
+struct Parser<'a> {
+    source: &'a str,
+    tokens: Vec<Spanned>,
+    position: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|t| &t.token)
+    }
+
+    fn at(&self, punct: &str) -> bool {
+        matches!(self.peek(), Some(Token::Punct(p)) if *p == punct)
+    }
+
+    fn line(&self) -> usize {
+        match self.tokens.get(self.position).or_else(|| self.tokens.last()) {
+            Some(t) => t.line,
+            None => 1,
+        }
+    }
+
+    fn next(&mut self) -> Option<Token> {
+        let token = self.tokens.get(self.position).map(|t| t.token.clone());
+        self.position += 1;
+        token
+    }
+
+    fn found(&self) -> String {
+        match self.peek() {
+            Some(token) => format!("`{}`", token),
+            None => String::from("the end"),
+        }
+    }
+
+    // a parse error, unless what's there is rust the tracer just doesn't run
+    fn unexpected(&self, expected: &str) -> Problem {
+        self.peek()
+            .and_then(unsupported)
+            .unwrap_or_else(|| Problem::Error(None, format!("expected {}, found {}", expected, self.found()), Vec::new()))
+    }
+
+    fn expect(&mut self, punct: &str) -> Result<(), Problem> {
+        if self.at(punct) {
+            self.position += 1;
+            return Ok(());
+        }
+        Err(self.unexpected(&format!("`{}`", punct)))
+    }
+
+    fn ident(&mut self) -> Result<String, Problem> {
+        match self.next() {
+            Some(Token::Ident(name)) if !KEYWORDS.contains(&name.as_str()) => Ok(name),
+            _ => {
+                self.position -= 1;
+                Err(self.unexpected("identifier"))
+            }
+        }
+    }
+
+    // a comma separated list up to `close`, trailing comma allowed
+    fn list<T>(&mut self, close: &str, item: fn(&mut Self) -> Result<T, Problem>) -> Result<(Vec<T>, bool), Problem> {
+        let mut items = Vec::new();
+        let mut trailing = false;
+        while !self.at(close) {
+            items.push(item(self)?);
+            trailing = self.at(",");
+            if !trailing {
+                break;
+            }
+            self.position += 1;
+        }
+        self.expect(close)?;
+        Ok((items, trailing))
+    }
+
+    fn length(&mut self) -> Result<usize, Problem> {
+        match self.next() {
+            Some(Token::Number(text)) => match literal::parse(&text) {
+                Ok(literal::Literal { value: LiteralValue::Integer(n), .. }) if n <= usize::MAX as u128 => Ok(n as usize),
+                _ => Err(error("E0308", String::from("mismatched types"), vec![format!("expected `usize`, found `{}`", text)])),
+            },
+            _ => Err(Problem::Unsupported(String::from("array lengths that aren't a plain number"))),
+        }
+    }
+
+    fn ty(&mut self) -> Result<Ty, Problem> {
+        if self.at("(") {
+            self.position += 1;
+            let (fields, trailing) = self.list(")", Self::ty)?;
+            if fields.len() == 1 && !trailing {
+                return Ok(fields.into_iter().next().expect("one field"));
+            }
+            return Ok(Ty::Tuple(fields));
+        }
+        if self.at("[") {
+            self.position += 1;
+            let element = self.ty()?;
+            if !self.at(";") {
+                return Err(Problem::Unsupported(String::from("slices")));
+            }
+            self.position += 1;
+            let length = self.length()?;
+            self.expect("]")?;
+            return Ok(Ty::Array(Box::new(element), length));
+        }
+        let reference = self.at("&");
+        if reference {
+            self.position += 1;
+        }
+        let name = if reference { String::from("&") } else { String::new() } + &self.ident()?;
+        Ty::named(&name).ok_or_else(|| Problem::Unsupported(format!("the type `{}`", name)))
+    }
+
+    fn pattern(&mut self) -> Result<Pattern, Problem> {
+        if self.at("(") {
+            self.position += 1;
+            let (parts, trailing) = self.list(")", Self::pattern)?;
+            if parts.len() == 1 && !trailing {
+                return Ok(parts.into_iter().next().expect("one part"));
+            }
+            return Ok(Pattern::Tuple(parts));
+        }
+        if self.at("[") {
+            self.position += 1;
+            return Ok(Pattern::Array(self.list("]", Self::pattern)?.0));
+        }
+        let mutable = self.peek() == Some(&Token::Ident(String::from("mut")));
+        if mutable {
+            self.position += 1;
+        }
+        match self.ident()? {
+            name if name == "_" && !mutable => Ok(Pattern::Wildcard),
+            name => Ok(Pattern::Name { mutable, name }),
+        }
+    }
+
+    // the source text from token `start` up to where the parser is now, on one line
+    fn text(&self, start: usize) -> String {
+        let from = self.tokens[start].start;
+        let to = self.tokens[(self.position - 1).min(self.tokens.len() - 1)].end;
+        self.source[from..to].lines().map(str::trim).collect::<Vec<&str>>().join(" ")
+    }
+
+    // the next statement with its line and text, or None at the end
+    fn statement(&mut self) -> Option<(usize, String, Result<Stmt, Problem>)> {
+        let start = self.position;
+        let line = self.line();
+        self.peek()?;
+        let result = self.parse_statement();
+        if result.is_err() {
+            // point at the rest of the line, there's nothing to gain from parsing further
+            while self.position < self.tokens.len() && self.tokens[self.position].line == line {
+                self.position += 1;
+            }
+        }
+        let text = self.text(start.min(self.position.saturating_sub(1)).min(self.tokens.len() - 1));
+        self.position = if result.is_err() { self.tokens.len() } else { self.position };
+        Some((line, text, result))
+    }
+
+    // `;` can be left off the last statement, the way a playground line would be typed
+    fn end(&mut self) -> Result<(), Problem> {
+        if self.peek().is_none() {
+            return Ok(());
+        }
+        self.expect(";")
+    }
+
+    fn parse_statement(&mut self) -> Result<Stmt, Problem> {
+        let word = match self.peek() {
+            Some(Token::Ident(word)) if KEYWORDS.contains(&word.as_str()) => return Err(self.unexpected("a statement")),
+            Some(Token::Ident(word)) => word.clone(),
+            Some(Token::Punct("{")) => {
+                self.position += 1;
+                return Ok(Stmt::Open);
+            }
+            Some(Token::Punct("}")) => {
+                self.position += 1;
+                return Ok(Stmt::Close);
+            }
+            _ => {
+                let value = self.expression()?;
+                self.end()?;
+                return Ok(Stmt::Expr(value));
+            }
+        };
+        self.position += 1;
+
+        match word.as_str() {
+            // `fn main() {` is just the outermost block
+            "fn" => {
+                self.ident()?;
+                self.expect("(")?;
+                self.expect(")")?;
+                self.expect("{")?;
+                Ok(Stmt::Open)
+            }
+            "let" => {
+                let pattern = self.pattern()?;
+                let ty = self.annotation()?;
+                self.expect("=")?;
+                let value = self.expression()?;
+                self.end()?;
+                Ok(Stmt::Let { pattern, ty, value })
+            }
+            "const" => {
+                let name = self.ident()?;
+                let ty = self.annotation()?;
+                self.expect("=")?;
+                let value = self.expression()?;
+                self.end()?;
+                Ok(Stmt::Const { name, ty, value })
+            }
+            "println" => {
+                self.expect("!")?;
+                self.expect("(")?;
+                let format = match self.next() {
+                    Some(Token::Str(format)) => format,
+                    _ => return Err(Problem::Error(None, String::from("format argument must be a string literal"), Vec::new())),
+                };
+                let mut args = Vec::new();
+                while self.at(",") {
+                    self.position += 1;
+                    if self.at(")") {
+                        break;
+                    }
+                    args.push(self.expression()?);
+                }
+                self.expect(")")?;
+                self.end()?;
+                Ok(Stmt::Print { format, args })
+            }
+            _ => {
+                let start = self.position - 1;
+                let path = self.place()?;
+                let op = match self.peek() {
+                    Some(Token::Punct("=")) => None,
+                    Some(Token::Punct("+=")) => Some(Op::Add),
+                    Some(Token::Punct("-=")) => Some(Op::Sub),
+                    Some(Token::Punct("*=")) => Some(Op::Mul),
+                    Some(Token::Punct("/=")) => Some(Op::Div),
+                    Some(Token::Punct("%=")) => Some(Op::Rem),
+                    _ => {
+                        // not an assignment after all, so read it again as an expression
+                        self.position = start;
+                        let value = self.expression()?;
+                        self.end()?;
+                        return Ok(Stmt::Expr(value));
+                    }
+                };
+                self.position += 1;
+                let value = self.expression()?;
+                self.end()?;
+                Ok(Stmt::Assign { name: word, path, op, value })
+            }
+        }
+    }
+
+    // the `.0` and `[i]` after a name on the left of an `=`
+    fn place(&mut self) -> Result<Vec<Access>, Problem> {
+        let mut path = Vec::new();
+        loop {
+            if self.at(".") {
+                match self.tokens.get(self.position + 1).map(|t| &t.token) {
+                    Some(Token::Number(_)) => self.position += 1,
+                    _ => return Ok(path),
+                }
+                path.extend(self.fields()?.into_iter().map(Access::Field));
+            } else if self.at("[") {
+                self.position += 1;
+                path.push(Access::Index(self.expression()?));
+                self.expect("]")?;
+            } else {
+                return Ok(path);
+            }
+        }
+    }
+
+    // the number after a `.`, which is two fields when it's lexed as a float like `0.1`
+    fn fields(&mut self) -> Result<Vec<usize>, Problem> {
+        let text = match self.next() {
+            Some(Token::Number(text)) => text,
+            _ => unreachable!("only called on a number"),
+        };
+        text.split('.')
+            .map(|field| field.parse().map_err(|_| Problem::Error(None, format!("invalid tuple field `{}`", field), Vec::new())))
+            .collect()
+    }
+
+    fn annotation(&mut self) -> Result<Option<Ty>, Problem> {
+        if !self.at(":") {
+            return Ok(None);
+        }
+        self.position += 1;
+        self.ty().map(Some)
+    }
+
+    fn expression(&mut self) -> Result<Expr, Problem> {
+        let mut left = self.term()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Punct("+")) => Op::Add,
+                Some(Token::Punct("-")) => Op::Sub,
+                _ => return Ok(left),
+            };
+            self.position += 1;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.term()?));
+        }
+    }
+
+    fn term(&mut self) -> Result<Expr, Problem> {
+        let mut left = self.unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(Token::Punct("*")) => Op::Mul,
+                Some(Token::Punct("/")) => Op::Div,
+                Some(Token::Punct("%")) => Op::Rem,
+                _ => return Ok(left),
+            };
+            self.position += 1;
+            left = Expr::Binary(op, Box::new(left), Box::new(self.unary()?));
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, Problem> {
+        if self.at("-") {
+            self.position += 1;
+            return Ok(Expr::Neg(Box::new(self.unary()?)));
+        }
+        let mut expr = self.primary()?;
+        loop {
+            if self.at("[") {
+                self.position += 1;
+                let index = self.expression()?;
+                self.expect("]")?;
+                expr = Expr::Index(Box::new(expr), Box::new(index));
+                continue;
+            }
+            if !self.at(".") {
+                return Ok(expr);
+            }
+            self.position += 1;
+            if let Some(Token::Number(_)) = self.peek() {
+                for field in self.fields()? {
+                    expr = Expr::Field(Box::new(expr), field);
+                }
+                continue;
+            }
+            let method = self.ident()?;
+            self.expect("(")?;
+            let args = self.list(")", Self::expression)?.0;
+            expr = Expr::Method(Box::new(expr), method, args);
+        }
+    }
+
+    fn primary(&mut self) -> Result<Expr, Problem> {
+        match self.next() {
+            Some(Token::Number(text)) => literal::parse(&text)
+                .map(Expr::Literal)
+                .map_err(|e| Problem::Error(None, e.message, e.notes)),
+            Some(Token::Str(text)) => Ok(Expr::Str(text)),
+            Some(Token::Char(c)) => Ok(Expr::Char(c)),
+            Some(Token::Ident(word)) if word == "true" => Ok(Expr::Bool(true)),
+            Some(Token::Ident(word)) if word == "false" => Ok(Expr::Bool(false)),
+            Some(Token::Ident(name)) if !KEYWORDS.contains(&name.as_str()) => {
+                if self.at("!") || self.at("(") {
+                    return Err(Problem::Unsupported(format!("calling `{}`", name)));
+                }
+                Ok(Expr::Var(name))
+            }
+            Some(Token::Punct("(")) => {
+                let (values, trailing) = self.list(")", Self::expression)?;
+                if values.len() == 1 && !trailing {
+                    return Ok(values.into_iter().next().expect("one value"));
+                }
+                Ok(Expr::Tuple(values))
+            }
+            Some(Token::Punct("[")) => {
+                if self.at("]") {
+                    return Err(Problem::Unsupported(String::from("empty arrays")));
+                }
+                let first = self.expression()?;
+                if self.at(";") {
+                    self.position += 1;
+                    let count = self.expression()?;
+                    self.expect("]")?;
+                    return Ok(Expr::Repeat(Box::new(first), Box::new(count)));
+                }
+                let mut values = vec![first];
+                if self.at(",") {
+                    self.position += 1;
+                    values.extend(self.list("]", Self::expression)?.0);
+                } else {
+                    self.expect("]")?;
+                }
+                Ok(Expr::Array(values))
+            }
+            Some(Token::Punct("*")) => Err(Problem::Unsupported(String::from("dereferencing with `*`"))),
+            _ => {
+                self.position -= 1;
+                Err(self.unexpected("expression"))
+            }
+        }
+    }
+}
+
+pub struct Binding {
+    pub name: String,
+    // how many bindings with this name came before it, plus one
+    pub number: usize,
+    pub line: usize,
+    pub mutable: bool,
+    pub constant: bool,
+    // false once the value depends on something only known when the program runs, like the
+    // result of `.parse()`. the compiler can catch an out of bounds index only while this holds
+    pub known: bool,
+    ty: Ty,
+    value: Value,
+    // the binding it shadows, and whether that one is in the same block
+    shadows: Option<(usize, bool)>,
+    // the step that made it and which of that step's notes describe it, so they can be written
+    // again once a `{integer}` binding gets its type
+    notes: (usize, usize, usize),
+    // the line whose use gave a `{integer}` binding its type
+    pinned: Option<usize>,
+}
+
+impl Binding {
+    fn label(&self) -> String {
+        format!("{}#{}", self.name, self.number)
+    }
+}
+
+pub struct Step {
+    pub line: usize,
+    pub text: String,
+    pub notes: Vec<String>,
+}
+
+impl fmt::Display for Step {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:>3} | {}", self.line, self.text)?;
+        for note in &self.notes {
+            writeln!(f, "    | {}", note)?;
+        }
+        Ok(())
+    }
+}
+
+pub struct Trace {
+    pub steps: Vec<Step>,
+    // why the trace stopped early, already worded
+    pub stopped: Option<String>,
+    pub bindings: Vec<Binding>,
+    // indices into `bindings` for each open block, innermost last
+    scopes: Vec<Vec<usize>>,
+    // whether every new value gets its size and memory layout noted
+    layouts: bool,
+    // how many lines have been fed in so far, so the next snippet carries on counting
+    lines: usize,
+}
+
+impl Trace {
+    pub fn new(layouts: bool) -> Trace {
+        Trace { steps: Vec::new(), stopped: None, bindings: Vec::new(), scopes: Vec::new(), layouts, lines: 0 }
+    }
+
+    // runs more statements with everything bound so far still around. a problem stops the rest
+    // of this snippet and is left in `stopped`
+    pub fn feed(&mut self, source: &str) {
+        let first = self.lines;
+        self.lines += source.lines().count().max(1);
+        let tokens = match tokenize(source, &PUNCTUATION) {
+            Ok(tokens) => tokens,
+            Err((line, problem)) => {
+                self.stopped = Some(format!("line {}: {}", first + line, describe(problem)));
+                return;
+            }
+        };
+
+        let mut parser = Parser { source, tokens, position: 0 };
+        while let Some((line, text, stmt)) = parser.statement() {
+            let line = first + line;
+            self.steps.push(Step { line, text, notes: Vec::new() });
+            if let Err(problem) = stmt.and_then(|stmt| self.run(line, stmt)) {
+                self.stopped = Some(format!("line {}: {}", line, describe(problem)));
+                break;
+            }
+        }
+    }
+
+    pub(crate) fn note(&mut self, note: String) {
+        self.steps.last_mut().expect("a step is started first").notes.push(note);
+    }
+
+    // the binding a name refers to right now: the newest one in the innermost scope that has it
+    fn lookup(&self, name: &str) -> Option<usize> {
+        self.scopes.iter().rev().flat_map(|scope| scope.iter().rev()).copied().find(|i| self.bindings[*i].name == name)
+    }
+
+    fn lookup_or_error(&self, name: &str) -> Result<usize, Problem> {
+        self.lookup(name)
+            .ok_or_else(|| error("E0425", format!("cannot find value `{}` in this scope", name), Vec::new()))
+    }
+
+    // whether the compiler can work the value out without running the program
+    pub(crate) fn known(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Literal(_) | Expr::Bool(_) | Expr::Char(_) | Expr::Str(_) => true,
+            Expr::Var(name) => self.lookup(name).is_some_and(|i| self.bindings[i].known),
+            Expr::Neg(inner) | Expr::Field(inner, _) => self.known(inner),
+            Expr::Binary(_, a, b) | Expr::Index(a, b) | Expr::Repeat(a, b) => self.known(a) && self.known(b),
+            Expr::Tuple(values) | Expr::Array(values) => values.iter().all(|v| self.known(v)),
+            Expr::Method(receiver, method, args) => method != "parse" && self.known(receiver) && args.iter().all(|a| self.known(a)),
+        }
+    }
+
+    // whether the expression is allowed where rust wants a constant, like the count in `[3; 5]`
+    pub(crate) fn constant(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Literal(_) => true,
+            Expr::Var(name) => self.lookup(name).is_some_and(|i| self.bindings[i].constant),
+            Expr::Neg(inner) => self.constant(inner),
+            Expr::Binary(_, a, b) => self.constant(a) && self.constant(b),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn eval(&mut self, expr: &Expr) -> Result<(Value, Ty), Problem> {
+        self.eval_as(expr, &Ty::Unknown)
+    }
+
+    // how many notes the current step has so far
+    pub(crate) fn noted(&self) -> usize {
+        self.steps.last().expect("a step is started first").notes.len()
+    }
+
+    // drops the notes after the first `noted`, for an expression that's evaluated again once a
+    // type is known so its reads aren't noted twice
+    pub(crate) fn forget(&mut self, noted: usize) {
+        self.steps.last_mut().expect("a step is started first").notes.truncate(noted);
+    }
+
+    // `expected` is the type the value is going to have, `_` if that isn't known yet. unsuffixed
+    // literals take it, so `let x: u8 = 250 + 10;` adds two u8s and overflows, the way rustc does
+    pub(crate) fn eval_as(&mut self, expr: &Expr, expected: &Ty) -> Result<(Value, Ty), Problem> {
+        match expr {
+            Expr::Literal(token) => literal(token, false, expected),
+            Expr::Bool(v) => Ok((Value::Bool(*v), Ty::Bool)),
+            Expr::Char(c) => Ok((Value::Char(*c), Ty::Char)),
+            Expr::Str(s) => Ok((Value::Str(s.clone()), Ty::Str)),
+            Expr::Var(name) => {
+                let index = self.lookup_or_error(name)?;
+                let binding = &self.bindings[index];
+                let read = format!("reads {} ({})", binding.label(), binding.value.brief());
+                let result = (binding.value.clone(), binding.ty.clone());
+                // the first use that needs a type gives a `{integer}` binding that type for good,
+                // so `let y: u8 = x; let z: i64 = x;` makes `x` a u8 and the second one fails
+                if let ((Value::Int(v), Ty::IntVar), Ty::Int(to)) = (&result, expected) {
+                    if !fits(*v, to) {
+                        let (min, max) = range(to);
+                        return Err(Problem::Error(None, format!("`{}` doesn't fit into `{}`", name, to), vec![
+                            format!("note: {} was made on line {} as `{{integer}}` {}, and this makes it a `{}` whose range is `{}..={}`", binding.label(), binding.line, v, to, min, max),
+                            String::from("note: rustc works out the type before anything runs, so it reports this where the value was made"),
+                        ]));
+                    }
+                    let pinned = format!("{}, the first use that needs a type, which gives {} its type: {}", read, binding.label(), to);
+                    self.pin(index, expected.clone());
+                    self.bindings[index].pinned = Some(self.steps.last().expect("a step is started first").line);
+                    self.note(pinned);
+                    return Ok((Value::Int(*v), expected.clone()));
+                }
+                self.note(read);
+                Ok(result)
+            }
+            Expr::Neg(inner) => {
+                // `-128i8` is one literal, even though `128i8` on its own doesn't fit
+                if let Expr::Literal(token) = &**inner {
+                    return literal(token, true, expected);
+                }
+                let known = self.known(inner);
+                match self.eval_as(inner, expected)? {
+                    (Value::Int(v), ty) => {
+                        let name = int_name(&ty);
+                        if integer_type(name).is_some_and(|(_, signed)| !signed) {
+                            return Err(unsigned_negation(&ty));
+                        }
+                        if v == range(name).0 {
+                            return Err(overflowed(Op::Neg, known, format!("attempt to negate `{}`, which would overflow", operand(v, name))));
+                        }
+                        Ok((Value::Int(-v), ty))
+                    }
+                    (Value::Float(v), ty) => Ok((Value::Float(-v), ty)),
+                    (_, ty) => Err(error("E0600", format!("cannot apply unary operator `-` to type `{}`", ty), Vec::new())),
+                }
+            }
+            Expr::Binary(op, left, right) => {
+                let known = self.known(expr);
+                let noted = self.noted();
+                let mut a = self.eval_as(left, expected)?;
+                let mut b = self.eval_as(right, if typed(&a.1) { &a.1 } else { expected })?;
+                // in `300 + x` with `x: u8` the type only turns up on the right
+                if !typed(&a.1) && typed(&b.1) {
+                    let ty = b.1.clone();
+                    self.forget(noted);
+                    a = self.eval_as(left, &ty)?;
+                    b = self.eval_as(right, &ty)?;
+                }
+                arithmetic(*op, a, b, known)
+            }
+            Expr::Tuple(_) | Expr::Array(_) | Expr::Repeat(..) | Expr::Field(..) | Expr::Index(..) => self.compound(expr, expected),
+            Expr::Method(receiver, method, args) => {
+                let (value, ty) = self.eval(receiver)?;
+                let mut evaluated = Vec::new();
+                for arg in args {
+                    evaluated.push(self.eval(arg)?);
+                }
+                call(value, ty, method, evaluated)
+            }
+        }
+    }
+
+    fn run(&mut self, line: usize, stmt: Stmt) -> Result<(), Problem> {
+        match stmt {
+            Stmt::Open => {
+                self.scopes.push(Vec::new());
+                if self.scopes.len() > 1 {
+                    self.note(String::from("a new block starts, bindings made inside it end at its `}`"));
+                }
+            }
+            Stmt::Close => {
+                let scope = self.scopes.pop().ok_or_else(|| Problem::Error(None, String::from("unexpected closing delimiter: `}`"), Vec::new()))?;
+                let dropped: Vec<String> = scope.iter().map(|i| self.bindings[*i].label()).collect();
+                if !dropped.is_empty() {
+                    let verb = if dropped.len() == 1 { "goes" } else { "go" };
+                    self.note(format!("the block ends, so {} {} away", dropped.join(", "), verb));
+                }
+                for i in &scope {
+                    let name = &self.bindings[*i].name;
+                    if let Some(visible) = self.lookup(name) {
+                        let label = self.bindings[visible].label();
+                        self.note(format!("`{}` means {} again", name, label));
+                    }
+                }
+                // the same name may be noted twice if it was shadowed twice in the block
+                self.steps.last_mut().expect("a step is started first").notes.dedup();
+            }
+            Stmt::Let { pattern, ty, value: expr } => {
+                let known = self.known(&expr);
+                let (value, value_ty) = self.eval_as(&expr, ty.as_ref().unwrap_or(&Ty::Unknown))?;
+                let (value, ty) = match ty {
+                    Some(ty) => settle(value, &value_ty, &ty).map_err(|problem| self.explain_pin(problem, &expr))?,
+                    None if value_ty.unresolved() => {
+                        let name = match &pattern {
+                            Pattern::Name { name, .. } => name.as_str(),
+                            _ => "x",
+                        };
+                        return Err(error("E0284", String::from("type annotations needed"), vec![
+                            format!("help: consider giving `{}` an explicit type: `let {}: u32 = ...`", name, name),
+                            String::from("note: `.parse()` can produce any type, so rust has to be told which one"),
+                        ]));
+                    }
+                    // `{integer}` stays that way so a later use can still pick the type, the way
+                    // `let index = 10; a[index]` makes `index` a usize. it's i32 if nothing does
+                    None => (settle(value, &value_ty, &value_ty.or_default())?.0, value_ty),
+                };
+                let mut parts = Vec::new();
+                compound::destructure(&pattern, value, ty, &mut parts)?;
+                let mut names: Vec<&str> = parts.iter().map(|(name, ..)| name.as_str()).collect();
+                names.sort_unstable();
+                if let Some(twice) = names.windows(2).find(|pair| pair[0] == pair[1]) {
+                    return Err(error("E0416", format!("identifier `{}` is bound more than once in the same pattern", twice[0]), Vec::new()));
+                }
+                if parts.len() > 1 {
+                    self.note(format!("the pattern takes the value apart into {} new bindings", parts.len()));
+                }
+                for (name, mutable, ty, value) in parts {
+                    self.bind(name, line, mutable, false, known, ty, value);
+                }
+            }
+            Stmt::Const { name, ty, value } => {
+                let ty = ty.ok_or_else(|| Problem::Error(None, String::from("missing type for `const` item"), vec![
+                    format!("help: provide a type for the constant: `{}: u32`", name),
+                ]))?;
+                if !self.constant(&value) {
+                    return Err(error("E0435", String::from("attempt to use a non-constant value in a constant"), Vec::new()));
+                }
+                // an overflow in a const isn't a lint, the const fails to evaluate
+                let (value, value_ty) = self.eval_as(&value, &ty).map_err(|problem| match problem {
+                    Problem::Error(None, message, notes) if message == OVERFLOW => {
+                        error("E0080", notes[0].clone(), vec![format!("note: evaluation of `{}` failed here", name)])
+                    }
+                    other => other,
+                })?;
+                let (value, ty) = settle(value, &value_ty, &ty)?;
+                self.bind(name, line, false, true, true, ty, value);
+            }
+            Stmt::Assign { name, path, op, value } => self.assign(name, path, op, value)?,
+            Stmt::Print { format, args } => {
+                let mut values = Vec::new();
+                for arg in &args {
+                    values.push(self.eval(arg)?);
+                }
+                let printed = self.format(&format, values)?;
+                self.note(format!("prints: {}", printed));
+            }
+            Stmt::Expr(expr) => {
+                let (value, ty) = self.eval(&expr)?;
+                let ty = ty.or_default();
+                self.note(format!("= {}: {}", value.brief(), ty));
+                if self.layouts {
+                    for line in compound::layout(&ty, &value) {
+                        self.note(line);
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn assign(&mut self, name: String, path: Vec<Access>, op: Option<Op>, value: Expr) -> Result<(), Problem> {
+        let index = self.lookup_or_error(&name)?;
+
+        // the part of the binding being assigned, written the way rustc writes it
+        let mut place = name.clone();
+        for access in &path {
+            match access {
+                Access::Field(field) => place.push_str(&format!(".{}", field)),
+                Access::Index(_) => place.push_str("[_]"),
+            }
+        }
+        let binding = &self.bindings[index];
+        if binding.constant {
+            return Err(error("E0070", String::from("invalid left-hand side of assignment"), vec![
+                format!("note: `{}` is a constant, it can't be assigned to at all", name),
+            ]));
+        }
+        if !binding.mutable && path.is_empty() {
+            return Err(error("E0384", format!("cannot assign twice to immutable variable `{}`", name), vec![
+                format!("note: first assignment to `{}` on line {}", name, binding.line),
+                format!("help: consider making this binding mutable: `let mut {}`", name),
+                format!("help: or shadow it with a new binding: `let {} = ...`", name),
+            ]));
+        }
+        if !binding.mutable {
+            return Err(error("E0594", format!("cannot assign to `{}`, as `{}` is not declared as mutable", place, name), vec![
+                format!("help: consider changing this to be mutable: `let mut {}`", name),
+                String::from("note: the parts of a binding are only as mutable as the binding itself"),
+            ]));
+        }
+
+        let (label, line, declared) = (binding.label(), binding.line, binding.ty.clone());
+        let whole = binding.value.clone();
+        let (target_ty, target, positions, mut known) = self.part(declared.clone(), whole, &path)?;
+        known &= self.known(&value) && (op.is_none() || self.bindings[index].known);
+        let (new, new_ty) = self.eval_as(&value, &target_ty)?;
+        let (new, new_ty) = match op {
+            Some(op) => arithmetic(op, (target, target_ty.clone()), (new, new_ty), known)?,
+            None => (new, new_ty),
+        };
+        let (new, new_ty) = match settle(new, &new_ty, &target_ty) {
+            Ok(settled) => settled,
+            Err(Problem::Error(Some("E0308"), message, mut notes)) => {
+                if path.is_empty() {
+                    notes.push(format!(
+                        "note: {} was made on line {} as `{}`. assignment puts a new value into that same binding, and a binding's type is fixed when it's made",
+                        label, line, declared.or_default()
+                    ));
+                    notes.push(format!("help: shadowing makes a new binding, which can have a new type: `let {} = ...`", name));
+                } else {
+                    notes.push(format!("note: `{}` is part of a `{}`, so it stays a `{}`", place, declared, target_ty));
+                }
+                return Err(error("E0308", message, notes));
+            }
+            Err(other) => return Err(other),
+        };
+
+        // `let mut x = 5; x = 3u8;` makes `x` a u8 all along
+        if path.is_empty() && declared == Ty::IntVar && new_ty != Ty::IntVar {
+            self.pin(index, new_ty.clone());
+        }
+        let binding = &mut self.bindings[index];
+        compound::replace(&mut binding.value, &positions, new.clone());
+        if path.is_empty() {
+            binding.ty = new_ty;
+            binding.known = known;
+            self.note(format!("{} changes to {}, still the same binding (no new one is made)", label, new.brief()));
+        } else {
+            binding.known &= known;
+            let whole = binding.value.brief();
+            self.note(format!("`{}` changes to {}, so {} is now {}, still the same binding", place, new.brief(), label, whole));
+        }
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn bind(&mut self, name: String, line: usize, mutable: bool, constant: bool, known: bool, ty: Ty, value: Value) {
+        let shadows = self.lookup(&name).map(|old| (old, self.scopes.last().is_some_and(|scope| scope.contains(&old))));
+        let number = self.bindings.iter().filter(|b| b.name == name).count() + 1;
+        let step = self.steps.len() - 1;
+        let start = self.noted();
+        let binding = Binding { name, number, line, mutable, constant, known, ty, value, shadows, notes: (step, start, start), pinned: None };
+        self.bindings.push(binding);
+        let index = self.bindings.len() - 1;
+        for note in self.introduce(index) {
+            self.note(note);
+        }
+        self.bindings[index].notes.2 = self.noted();
+
+        if self.scopes.is_empty() {
+            self.scopes.push(Vec::new());
+        }
+        self.scopes.last_mut().expect("pushed above").push(index);
+    }
+
+    // what the trace says about a binding where it's made
+    fn introduce(&self, index: usize) -> Vec<String> {
+        let binding = &self.bindings[index];
+        let kind = match (binding.constant, binding.mutable) {
+            (true, _) => "new constant",
+            (false, true) => "new mutable binding",
+            (false, false) => "new binding",
+        };
+        let mut notes = vec![format!("{} is a {}: {} = {}", binding.label(), kind, binding.ty.or_default(), binding.value.brief())];
+        if let Some((shadowed, same_block)) = binding.shadows {
+            let old = &self.bindings[shadowed];
+            let lifetime = if same_block { "still exists until the end of this block but can't be named" } else { "can't be named until this block ends" };
+            let mut note = format!("{} shadows {} from line {}, which {}", binding.label(), old.label(), old.line, lifetime);
+            let (old_ty, new_ty) = (old.ty.or_default(), binding.ty.or_default());
+            if old_ty != new_ty {
+                note.push_str(&format!(". the type changes from {} to {}, which is fine for a new binding", old_ty, new_ty));
+            }
+            notes.push(note);
+        }
+        if self.layouts {
+            notes.extend(compound::layout(&binding.ty, &binding.value));
+        }
+        notes
+    }
+
+    // a mismatch reading a binding whose type came from an earlier use says where it came from
+    fn explain_pin(&self, problem: Problem, expr: &Expr) -> Problem {
+        let pinned = match expr {
+            Expr::Var(name) => self.lookup(name).map(|i| &self.bindings[i]).filter(|b| b.pinned.is_some()),
+            _ => None,
+        };
+        match (problem, pinned) {
+            (Problem::Error(Some("E0308"), message, mut notes), Some(binding)) => {
+                notes.push(format!(
+                    "note: {} was made on line {} as `{{integer}}`, and line {} made it a `{}`. a binding's type is worked out once, from its first use that needs one",
+                    binding.label(), binding.line, binding.pinned.expect("filtered above"), binding.ty
+                ));
+                Problem::Error(Some("E0308"), message, notes)
+            }
+            (problem, _) => problem,
+        }
+    }
+
+    // gives a `{integer}` binding its type, and writes what was said about it (and about any
+    // binding shadowing it) where it was made again, since that said i32
+    fn pin(&mut self, index: usize, ty: Ty) {
+        self.bindings[index].ty = ty;
+        let shadowing: Vec<usize> = (0..self.bindings.len()).filter(|&i| self.bindings[i].shadows.is_some_and(|(old, _)| old == index)).collect();
+        for i in std::iter::once(index).chain(shadowing) {
+            let (step, start, end) = self.bindings[i].notes;
+            let notes = self.introduce(i);
+            let grown = notes.len() as isize - (end - start) as isize;
+            self.steps[step].notes.splice(start..end, notes);
+            for binding in &mut self.bindings {
+                if binding.notes.0 == step && binding.notes.1 >= end {
+                    binding.notes.1 = (binding.notes.1 as isize + grown) as usize;
+                    binding.notes.2 = (binding.notes.2 as isize + grown) as usize;
+                }
+            }
+            self.bindings[i].notes.2 = (end as isize + grown) as usize;
+        }
+    }
+
+    // `{}` takes the next argument, `{name}` a binding, `{:?}` and `{name:?}` debug print it
+    fn format(&mut self, format: &str, values: Vec<(Value, Ty)>) -> Result<String, Problem> {
+        let mut values = values.into_iter();
+        let mut out = String::new();
+        let mut chars = format.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    out.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    out.push('}');
+                }
+                '{' => {
+                    let spec: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                    let (name, debug) = match spec.split_once(':') {
+                        Some((name, "?")) => (name, true),
+                        Some(_) => return Err(Problem::Unsupported(format!("the format spec `{{{}}}`", spec))),
+                        None => (spec.as_str(), false),
+                    };
+                    let (value, ty) = if name.is_empty() {
+                        values.next().ok_or_else(|| Problem::Error(None, String::from("1 positional argument in format string, but no arguments were given"), Vec::new()))?
+                    } else {
+                        let index = self.lookup_or_error(name)?;
+                        let read = format!("reads {} ({})", self.bindings[index].label(), self.bindings[index].value.brief());
+                        self.note(read);
+                        (self.bindings[index].value.clone(), self.bindings[index].ty.clone())
+                    };
+                    match (value, debug) {
+                        (Value::Str(s), false) => out.push_str(&s),
+                        (Value::Char(c), false) => out.push(c),
+                        (Value::Tuple(_), false) | (Value::Array(_), false) => {
+                            return Err(error("E0277", format!("`{}` doesn't implement `std::fmt::Display`", ty.or_default()), vec![
+                                String::from("note: in format strings you may be able to use `{:?}` (or {:#?} for pretty-print) instead"),
+                            ]));
+                        }
+                        (value, _) => out.push_str(&value.to_string()),
+                    }
+                }
+                c => out.push(c),
+            }
+        }
+        if values.next().is_some() {
+            return Err(Problem::Error(None, String::from("argument never used"), Vec::new()));
+        }
+        Ok(out)
+    }
+}
+
+// checks that a value fits the type it's going to have, settling `{integer}` and `{float}`,
+// and parsing the text from `.parse().expect()` once the type is known
+pub(crate) fn settle(value: Value, from: &Ty, to: &Ty) -> Result<(Value, Ty), Problem> {
+    match (value, from, to) {
+        (Value::Int(v), Ty::IntVar, Ty::IntVar) => Ok((Value::Int(v), Ty::IntVar)),
+        (Value::Int(v), Ty::IntVar, Ty::Int(name)) | (Value::Int(v), Ty::Int(_), Ty::Int(name)) if from == to || *from == Ty::IntVar => {
+            // only a literal can still be `{integer}` with a value that doesn't fit, everything
+            // else is checked as it's worked out
+            if !fits(v, name) {
+                let (min, max) = range(name);
+                return Err(Problem::Error(None, format!("literal out of range for `{}`", name), vec![
+                    format!("note: the literal `{}` does not fit into the type `{}` whose range is `{}..={}`", v, name, min, max),
+                ]));
+            }
+            Ok((Value::Int(v), to.clone()))
+        }
+        // a binding that's still `{integer}` takes the type of what's put in it
+        (Value::Int(v), Ty::Int(_), Ty::IntVar) => Ok((Value::Int(v), from.clone())),
+        (Value::Float(v), Ty::Float(_), Ty::FloatVar) => Ok((Value::Float(v), from.clone())),
+        (Value::Float(v), Ty::FloatVar, Ty::FloatVar) => Ok((Value::Float(v), Ty::FloatVar)),
+        (Value::Float(v), Ty::FloatVar, Ty::Float(name)) | (Value::Float(v), Ty::Float(_), Ty::Float(name)) if from == to || *from == Ty::FloatVar => {
+            Ok((Value::Float(if *name == "f32" { v as f32 as f64 } else { v }), to.clone()))
+        }
+        (Value::Parse(text, Some(message)), Ty::Unknown, to) => parse(&text, &message, to),
+        (Value::Tuple(values), Ty::Tuple(froms), Ty::Tuple(tos)) if froms.len() == tos.len() => {
+            let mut settled = (Vec::new(), Vec::new());
+            for ((value, from), to) in values.into_iter().zip(froms).zip(tos) {
+                let (value, ty) = settle(value, from, to).map_err(|e| whole(e, from, to))?;
+                settled.0.push(value);
+                settled.1.push(ty);
+            }
+            Ok((Value::Tuple(settled.0), Ty::Tuple(settled.1)))
+        }
+        (Value::Array(values), Ty::Array(from_element, n), Ty::Array(to_element, m)) if n == m => {
+            let mut settled = Vec::new();
+            let mut ty = (**to_element).clone();
+            for value in values {
+                let (value, element) = settle(value, from_element, to_element).map_err(|e| whole(e, from, to))?;
+                settled.push(value);
+                ty = element;
+            }
+            Ok((Value::Array(settled), Ty::Array(Box::new(ty), *m)))
+        }
+        (value, from, to) if from == to => Ok((value, to.clone())),
+        _ => Err(mismatch(to, from)),
+    }
+}
+
+// a mismatch inside a tuple or array is reported for the whole type, the way rustc does
+fn whole(problem: Problem, from: &Ty, to: &Ty) -> Problem {
+    match problem {
+        Problem::Error(Some("E0308"), ..) => mismatch(to, from),
+        other => other,
+    }
+}
+
+// `text.parse::<to>().expect(message)`, run for real
+fn parse(text: &str, message: &str, to: &Ty) -> Result<(Value, Ty), Problem> {
+    macro_rules! parse_int {
+        ($($t:ty),*) => {
+            match to {
+                $(Ty::Int(name) if *name == stringify!($t) => text.parse::<$t>().map(|v| Value::Int(v as i128)).map_err(|e| format!("{:?}", e)),)*
+                _ => unreachable!("not an integer type"),
+            }
+        };
+    }
+    let parsed = match to {
+        Ty::Int(_) => parse_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize),
+        Ty::Float("f32") => text.parse::<f32>().map(|v| Value::Float(v as f64)).map_err(|e| format!("{:?}", e)),
+        Ty::Float(_) => text.parse::<f64>().map(Value::Float).map_err(|e| format!("{:?}", e)),
+        Ty::Bool => text.parse::<bool>().map(Value::Bool).map_err(|e| format!("{:?}", e)),
+        Ty::Char => text.parse::<char>().map(Value::Char).map_err(|e| format!("{:?}", e)),
+        _ => return Err(Problem::Unsupported(format!("parsing into `{}`", to))),
+    };
+    match parsed {
+        Ok(value) => Ok((value, to.clone())),
+        Err(e) => Err(Problem::Panic(format!("{}: {}", message, e))),
+    }
+}
+
+// a literal token, negated if there's a `-` right in front of it
+fn literal(token: &literal::Literal, negative: bool, expected: &Ty) -> Result<(Value, Ty), Problem> {
+    let ty = match (token.suffixed, token.value, expected) {
+        (true, _, _) => Ty::named(&token.ty).expect("the literal module checks suffixes"),
+        (false, LiteralValue::Integer(_), Ty::Int(_)) | (false, LiteralValue::Float(_), Ty::Float(_)) => expected.clone(),
+        (false, LiteralValue::Integer(_), _) => Ty::IntVar,
+        (false, LiteralValue::Float(_), _) => Ty::FloatVar,
+    };
+    let value = match token.value {
+        LiteralValue::Integer(v) if v > i128::MAX as u128 => return Err(Problem::Unsupported(String::from("integers above i128::MAX"))),
+        LiteralValue::Integer(_) if negative && integer_type(int_name(&ty)).is_some_and(|(_, signed)| !signed) => {
+            return Err(unsigned_negation(&ty));
+        }
+        LiteralValue::Integer(v) if negative => Value::Int(-(v as i128)),
+        LiteralValue::Integer(v) => Value::Int(v as i128),
+        LiteralValue::Float(v) if negative => Value::Float(-v),
+        LiteralValue::Float(v) => Value::Float(v),
+    };
+    settle(value, &ty, &ty)
+}
+
+fn unsigned_negation(ty: &Ty) -> Problem {
+    error("E0600", format!("cannot apply unary operator `-` to type `{}`", ty), vec![String::from("note: unsigned values cannot be negated")])
+}
+
+// the integer type a value of type `ty` ends up with, `{integer}` being i32 if nothing says otherwise
+fn int_name(ty: &Ty) -> &'static str {
+    match ty.or_default() {
+        Ty::Int(name) => name,
+        _ => "i32",
+    }
+}
+
+fn typed(ty: &Ty) -> bool {
+    matches!(ty, Ty::Int(_) | Ty::Float(_))
+}
+
+// the smallest and largest value of an integer type. u128 stops at i128::MAX, which is as far as
+// the tracer goes
+fn range(name: &str) -> (i128, i128) {
+    match integer_type(name).expect("an integer type") {
+        (bits, true) => (-(1i128 << (bits - 1)), (1i128 << (bits - 1)) - 1),
+        (128, false) => (0, i128::MAX),
+        (bits, false) => (0, (1i128 << bits) - 1),
+    }
+}
+
+fn fits(v: i128, name: &str) -> bool {
+    let (min, max) = range(name);
+    min <= v && v <= max
+}
+
+// how rustc writes a value in an overflow message: `250_u8`, `u8::MAX` or `i8::MIN`
+fn operand(v: i128, name: &str) -> String {
+    let (min, max) = range(name);
+    match v {
+        v if v == max && !matches!(name, "u128") => format!("{}::MAX", name),
+        v if v == min && min < 0 => format!("{}::MIN", name),
+        v => format!("{}_{}", v, name),
+    }
+}
+
+const OVERFLOW: &str = "this arithmetic operation will overflow";
+
+// a result that doesn't fit its type. rustc refuses to build it when it can see the operands,
+// otherwise the program panics when it gets there
+fn overflowed(op: Op, known: bool, attempt: String) -> Problem {
+    if !known {
+        return Problem::Panic(op.overflow_message().to_string());
+    }
+    Problem::Error(None, String::from(OVERFLOW), vec![attempt, String::from("note: `#[deny(arithmetic_overflow)]` on by default")])
+}
+
+// `known` is whether the compiler can see both operands
+fn arithmetic(op: Op, (a, a_ty): (Value, Ty), (b, b_ty): (Value, Ty), known: bool) -> Result<(Value, Ty), Problem> {
+    let ty = match (&a_ty, &b_ty) {
+        (Ty::Int(_), Ty::Int(_)) | (Ty::Float(_), Ty::Float(_)) if a_ty != b_ty => return Err(mismatch(&a_ty, &b_ty)),
+        _ => match a_ty.unify(&b_ty) {
+            Some(ty) => ty,
+            None => {
+                return Err(error("E0277", format!("cannot apply `{}` to `{}` and `{}`", symbol(op), a_ty, b_ty), vec![
+                    String::from("note: both sides of an arithmetic operator have to be the same numeric type"),
+                ]))
+            }
+        },
+    };
+
+    match (a, b) {
+        (Value::Int(a), Value::Int(b)) => {
+            if b == 0 && (op == Op::Div || op == Op::Rem) {
+                return Err(Problem::Panic(op.zero_message().to_string()));
+            }
+            let name = int_name(&ty);
+            let result = match op {
+                Op::Add => a.checked_add(b),
+                Op::Sub => a.checked_sub(b),
+                Op::Mul => a.checked_mul(b),
+                Op::Div => a.checked_div(b),
+                _ => a.checked_rem(b),
+            };
+            match result.filter(|v| fits(*v, name)) {
+                Some(v) => Ok((Value::Int(v), ty)),
+                None => Err(overflowed(op, known, format!(
+                    "attempt to compute `{} {} {}`, which would overflow",
+                    operand(a, name),
+                    symbol(op),
+                    operand(b, name)
+                ))),
+            }
+        }
+        (Value::Float(a), Value::Float(b)) => {
+            let result = match op {
+                Op::Add => a + b,
+                Op::Sub => a - b,
+                Op::Mul => a * b,
+                Op::Div => a / b,
+                _ => a % b,
+            };
+            settle(Value::Float(result), &ty, &ty)
+        }
+        _ => Err(error("E0369", format!("binary operation `{}` cannot be applied to type `{}`", symbol(op), a_ty), Vec::new())),
+    }
+}
+
+fn symbol(op: Op) -> &'static str {
+    match op {
+        Op::Add => "+",
+        Op::Sub => "-",
+        Op::Mul => "*",
+        Op::Div => "/",
+        _ => "%",
+    }
+}
+
+fn call(value: Value, ty: Ty, method: &str, args: Vec<(Value, Ty)>) -> Result<(Value, Ty), Problem> {
+    match (value, method, args.as_slice()) {
+        (Value::Str(s), "len", []) => Ok((Value::Int(s.len() as i128), Ty::Int("usize"))),
+        (Value::Array(values), "len", []) => Ok((Value::Int(values.len() as i128), Ty::Int("usize"))),
+        (Value::Str(s), "trim", []) => Ok((Value::Str(s.trim().to_string()), Ty::Str)),
+        (Value::Str(s), "parse", []) => Ok((Value::Parse(s, None), Ty::ParseResult)),
+        (Value::Parse(text, None), "expect", [(Value::Str(message), _)]) => Ok((Value::Parse(text, Some(message.clone())), Ty::Unknown)),
+        (_, "len", _) | (_, "trim", _) | (_, "parse", _) | (_, "expect", _) => Err(error(
+            "E0599",
+            format!("no method named `{}` found for `{}` in the current scope", method, ty.or_default()),
+            Vec::new(),
+        )),
+        _ => Err(Problem::Unsupported(format!("the method `{}`", method))),
+    }
+}
+
+pub fn trace(source: &str) -> Trace {
+    let mut trace = Trace::new(false);
+    trace.feed(source);
+    trace
+}
+
+pub(crate) fn describe(problem: Problem) -> String {
+    match problem {
+        Problem::Error(code, message, notes) => {
+            let mut out = match code {
+                Some(code) => format!("error[{}]: {}", code, message),
+                None => format!("error: {}", message),
+            };
+            for note in notes {
+                out.push_str(&format!("\n    = {}", note));
+            }
+            out
+        }
+        Problem::Panic(message) => format!("thread 'main' panicked: {}", message),
+        Problem::Unsupported(what) => format!("the tracer stops here, it doesn't handle {}", what),
+    }
+}
+
+impl fmt::Display for Trace {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for step in &self.steps {
+            write!(f, "{}", step)?;
+        }
+        if let Some(stopped) = &self.stopped {
+            writeln!(f, "\n{}", stopped)?;
+        }
+        Ok(())
+    }
+}
+
+// the examples from ch3, the last one is the one that doesn't compile
+pub const EXAMPLES: [&str; 4] = [
+    "let y = 5;\nlet y = y + 1;\nlet y = y * 2;\nprintln!(\"y is: {}\", y);",
+    "let x = 5;\nlet x = x + 1;\n{\n    let x = x * 2;\n    println!(\"the value of x in the inner scope is: {x}\");\n}\nprintln!(\"the value of x is: {x}\");",
+    "let spaces = \"   \";\nlet spaces = spaces.len();",
+    "let mut spaces = \"   \";\nspaces = spaces.len();",
+];
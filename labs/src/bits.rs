@@ -0,0 +1,514 @@
+// the bit operators and bit methods from the integer section of ch3/varsandmutability.rs, next to
+// its `0b1111_0000` literal. every operation is shown as bit patterns, before and after side by
+// side, with the byte boundaries marked by spaces. like overflow.rs, every value comes from
+// calling the real operator or method on the real type
+//
+// the shifts are where debug and release builds part ways: shifting by the width of the type or
+// more panics in debug, while release shifts by the amount modulo the width
+
+use std::convert::TryFrom;
+use std::fmt;
+
+use crate::literal::{self, LiteralError, Value};
+use crate::overflow::{Op, TYPES};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operation {
+    And,
+    Or,
+    Xor,
+    Not,
+    Shl,
+    Shr,
+    RotateLeft,
+    RotateRight,
+    CountOnes,
+    CountZeros,
+    LeadingZeros,
+    TrailingZeros,
+    SwapBytes,
+    ReverseBits,
+    ToBeBytes,
+    ToLeBytes,
+    FromBeBytes,
+    FromLeBytes,
+}
+
+impl Operation {
+    // the operators go between two operands
+    fn parse_infix(s: &str) -> Option<Operation> {
+        match s {
+            "&" => Some(Operation::And),
+            "|" => Some(Operation::Or),
+            "^" => Some(Operation::Xor),
+            "<<" => Some(Operation::Shl),
+            ">>" => Some(Operation::Shr),
+            "rotate_left" => Some(Operation::RotateLeft),
+            "rotate_right" => Some(Operation::RotateRight),
+            _ => None,
+        }
+    }
+
+    // the methods that only take `self` go in front
+    fn parse_prefix(s: &str) -> Option<Operation> {
+        match s {
+            "!" => Some(Operation::Not),
+            "count_ones" => Some(Operation::CountOnes),
+            "count_zeros" => Some(Operation::CountZeros),
+            "leading_zeros" => Some(Operation::LeadingZeros),
+            "trailing_zeros" => Some(Operation::TrailingZeros),
+            "swap_bytes" => Some(Operation::SwapBytes),
+            "reverse_bits" => Some(Operation::ReverseBits),
+            "to_be_bytes" => Some(Operation::ToBeBytes),
+            "to_le_bytes" => Some(Operation::ToLeBytes),
+            "from_be_bytes" => Some(Operation::FromBeBytes),
+            "from_le_bytes" => Some(Operation::FromLeBytes),
+            _ => None,
+        }
+    }
+
+    // how the operation is written in rust, for the headings
+    fn call(self, a: &str, b: &str) -> String {
+        match self {
+            Operation::And => format!("{} & {}", a, b),
+            Operation::Or => format!("{} | {}", a, b),
+            Operation::Xor => format!("{} ^ {}", a, b),
+            Operation::Not => format!("!{}", a),
+            Operation::Shl => format!("{} << {}", a, b),
+            Operation::Shr => format!("{} >> {}", a, b),
+            Operation::RotateLeft => format!("{}.rotate_left({})", a, b),
+            Operation::RotateRight => format!("{}.rotate_right({})", a, b),
+            Operation::CountOnes => format!("{}.count_ones()", a),
+            Operation::CountZeros => format!("{}.count_zeros()", a),
+            Operation::LeadingZeros => format!("{}.leading_zeros()", a),
+            Operation::TrailingZeros => format!("{}.trailing_zeros()", a),
+            Operation::SwapBytes => format!("{}.swap_bytes()", a),
+            Operation::ReverseBits => format!("{}.reverse_bits()", a),
+            Operation::ToBeBytes => format!("{}.to_be_bytes()", a),
+            Operation::ToLeBytes => format!("{}.to_le_bytes()", a),
+            Operation::FromBeBytes => format!("{}::from_be_bytes({})", a, b),
+            Operation::FromLeBytes => format!("{}::from_le_bytes({})", a, b),
+        }
+    }
+}
+
+trait Bits: Copy + fmt::Display {
+    const NAME: &'static str;
+    const BITS: u32;
+    const SIGNED: bool;
+
+    // `as` in both directions, so negative numbers are their two's complement
+    fn from_bits(bits: u128) -> Self;
+    fn to_bits(self) -> u128;
+
+    // & | ^, ! and the methods that give back the same type
+    fn apply(self, operation: Operation, other: Self) -> Self;
+    // `None` when the debug build panics
+    fn checked_shift(self, operation: Operation, by: u32) -> Option<Self>;
+    // what the release build does instead
+    fn wrapping_shift(self, operation: Operation, by: u32) -> Self;
+    fn rotate(self, operation: Operation, by: u32) -> Self;
+    fn count(self, operation: Operation) -> u32;
+    fn to_bytes(self, operation: Operation) -> Vec<u8>;
+    // `None` when there are the wrong number of bytes
+    fn from_bytes(operation: Operation, bytes: &[u8]) -> Option<Self>;
+}
+
+macro_rules! bits {
+    ($($t:ty, $unsigned:ty, $signed:expr);*) => {$(
+        impl Bits for $t {
+            const NAME: &'static str = stringify!($t);
+            const BITS: u32 = <$t>::BITS;
+            const SIGNED: bool = $signed;
+
+            fn from_bits(bits: u128) -> $t {
+                bits as $t
+            }
+
+            fn to_bits(self) -> u128 {
+                self as $unsigned as u128
+            }
+
+            fn apply(self, operation: Operation, other: $t) -> $t {
+                match operation {
+                    Operation::And => self & other,
+                    Operation::Or => self | other,
+                    Operation::Xor => self ^ other,
+                    Operation::Not => !self,
+                    Operation::SwapBytes => self.swap_bytes(),
+                    Operation::ReverseBits => self.reverse_bits(),
+                    _ => unreachable!("not an operation that keeps the type"),
+                }
+            }
+
+            fn checked_shift(self, operation: Operation, by: u32) -> Option<$t> {
+                match operation {
+                    Operation::Shl => self.checked_shl(by),
+                    _ => self.checked_shr(by),
+                }
+            }
+
+            fn wrapping_shift(self, operation: Operation, by: u32) -> $t {
+                match operation {
+                    Operation::Shl => self.wrapping_shl(by),
+                    _ => self.wrapping_shr(by),
+                }
+            }
+
+            fn rotate(self, operation: Operation, by: u32) -> $t {
+                match operation {
+                    Operation::RotateLeft => self.rotate_left(by),
+                    _ => self.rotate_right(by),
+                }
+            }
+
+            fn count(self, operation: Operation) -> u32 {
+                match operation {
+                    Operation::CountOnes => self.count_ones(),
+                    Operation::CountZeros => self.count_zeros(),
+                    Operation::LeadingZeros => self.leading_zeros(),
+                    _ => self.trailing_zeros(),
+                }
+            }
+
+            fn to_bytes(self, operation: Operation) -> Vec<u8> {
+                match operation {
+                    Operation::ToBeBytes => self.to_be_bytes().to_vec(),
+                    _ => self.to_le_bytes().to_vec(),
+                }
+            }
+
+            fn from_bytes(operation: Operation, bytes: &[u8]) -> Option<$t> {
+                let bytes = <[u8; std::mem::size_of::<$t>()]>::try_from(bytes).ok()?;
+                match operation {
+                    Operation::FromBeBytes => Some(<$t>::from_be_bytes(bytes)),
+                    _ => Some(<$t>::from_le_bytes(bytes)),
+                }
+            }
+        }
+    )*};
+}
+
+bits!(
+    i8, u8, true; i16, u16, true; i32, u32, true; i64, u64, true; i128, u128, true; isize, usize, true;
+    u8, u8, false; u16, u16, false; u32, u32, false; u64, u64, false; u128, u128, false; usize, usize, false
+);
+
+fn unmarked(_: u32) -> bool {
+    false
+}
+
+fn byte_bits(byte: u8) -> String {
+    format!("{:08b}", byte)
+}
+
+// four bytes to a line, most significant first, so bit 0 is always the last one shown. the bits
+// that `marked` picks get a `^` on the line below
+fn pattern_lines(bits: u128, width: u32, marked: &dyn Fn(u32) -> bool) -> Vec<(String, String)> {
+    let mut lines = Vec::new();
+    let mut top = width;
+    while top > 0 {
+        let bottom = top.saturating_sub(32);
+        let label = format!("bits {}..{}", top - 1, bottom);
+        let mut line = String::new();
+        let mut marks = String::new();
+        for bit in (bottom..top).rev() {
+            if bit != top - 1 && (bit + 1) % 8 == 0 {
+                line.push(' ');
+                marks.push(' ');
+            }
+            line.push(if bits >> bit & 1 == 1 { '1' } else { '0' });
+            marks.push(if marked(bit) { '^' } else { ' ' });
+        }
+        lines.push((label, line));
+        if marks.contains('^') {
+            lines.push((String::new(), marks.trim_end().to_string()));
+        }
+        top = bottom;
+    }
+    lines
+}
+
+// one thing shown in a column: its bit pattern, then hex, then its value
+struct Column {
+    title: String,
+    rows: Vec<(String, String)>,
+}
+
+fn value_column<T: Bits>(title: &str, value: T, marked: &dyn Fn(u32) -> bool) -> Column {
+    let mut rows = pattern_lines(value.to_bits(), T::BITS, marked);
+    rows.push((String::from("hex"), format!("{:#0width$x}", value.to_bits(), width = T::BITS as usize / 4 + 2)));
+    rows.push((String::from("value"), value.to_string()));
+    Column { title: title.to_string(), rows }
+}
+
+// a byte array in memory order, lined up with the bit rows of a value of the same width
+fn bytes_column(title: &str, bytes: &[u8]) -> Column {
+    let mut rows: Vec<(String, String)> = bytes
+        .chunks(4)
+        .enumerate()
+        .map(|(i, chunk)| {
+            let bits: Vec<String> = chunk.iter().map(|&b| byte_bits(b)).collect();
+            (format!("bytes [{}..{}]", i * 4, i * 4 + chunk.len()), bits.join(" "))
+        })
+        .collect();
+    let hex: Vec<String> = bytes.iter().map(|b| format!("{:#04x}", b)).collect();
+    let decimal: Vec<String> = bytes.iter().map(|b| b.to_string()).collect();
+    rows.push((String::from("hex"), format!("[{}]", hex.join(", "))));
+    rows.push((String::from("value"), format!("[{}]", decimal.join(", "))));
+    Column { title: title.to_string(), rows }
+}
+
+pub struct Report {
+    pub expression: String,
+    columns: Vec<Column>,
+    // anything the columns don't show: counts, panics, release behaviour
+    pub notes: Vec<String>,
+}
+
+// the row labels come from the first column, and later columns with different labels (a byte
+// array next to a value) just line up row by row
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.expression)?;
+        let height = self.columns.iter().map(|c| c.rows.len()).max().unwrap_or(0);
+        let label = |row: usize| -> &str {
+            self.columns.iter().find_map(|c| c.rows.get(row).map(|(label, _)| label.as_str()).filter(|l| !l.is_empty())).unwrap_or("")
+        };
+        let label_width = (0..height).map(|row| label(row).len()).max().unwrap_or(0);
+        let widths: Vec<usize> = self
+            .columns
+            .iter()
+            .map(|c| c.rows.iter().map(|(_, text)| text.chars().count()).chain(Some(c.title.chars().count())).max().unwrap_or(0))
+            .collect();
+
+        if self.columns.iter().any(|c| !c.title.is_empty()) {
+            let mut line = format!("    {:<width$}", "", width = label_width);
+            for (column, width) in self.columns.iter().zip(&widths) {
+                line.push_str(&format!("   {:<width$}", column.title, width = width));
+            }
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        for row in 0..height {
+            let mut line = format!("    {:<width$}", label(row), width = label_width);
+            for (column, width) in self.columns.iter().zip(&widths) {
+                let text = column.rows.get(row).map_or("", |(_, text)| text.as_str());
+                line.push_str(&format!("   {:<width$}", text, width = width));
+            }
+            writeln!(f, "{}", line.trim_end())?;
+        }
+        for note in &self.notes {
+            writeln!(f, "    {}", note)?;
+        }
+        Ok(())
+    }
+}
+
+// rustc's rendering, minus the `error: ` that bits-lab already puts in front of every error
+fn render(e: &LiteralError, text: &str) -> String {
+    let rendered = e.render(text);
+    rendered.strip_prefix("error: ").unwrap_or(&rendered).trim_end().to_string()
+}
+
+// a literal of type T, checked the way rustc checks it
+fn operand<T: Bits>(text: &str) -> Result<T, String> {
+    let mut literal = literal::parse(text).map_err(|e| render(&e, text))?;
+    let value = match literal.value {
+        Value::Integer(value) => value,
+        Value::Float(_) => return Err(format!("`{}` is a float, the bit operators need integers", text)),
+    };
+    if literal.suffixed && literal.ty != T::NAME {
+        return Err(format!("mismatched types: expected `{}`, found `{}`", T::NAME, literal.ty));
+    }
+    literal.ty = T::NAME.to_string();
+    if let Some(e) = literal.range_error() {
+        return Err(render(&e, text));
+    }
+    Ok(T::from_bits(if literal.negative { value.wrapping_neg() } else { value }))
+}
+
+fn shift_amount(text: &str) -> Result<u32, String> {
+    text.replace('_', "").parse().map_err(|_| format!("the shift amount has to be a u32, got `{}`", text))
+}
+
+// `[0x12, 0x34]`, `0x12 0x34` or `18,52`
+fn byte_array(tokens: &[&str]) -> Result<Vec<u8>, String> {
+    let joined = tokens.join(" ");
+    joined
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|s| !s.is_empty())
+        .map(operand::<u8>)
+        .collect()
+}
+
+fn run<T: Bits>(tokens: &[&str]) -> Result<Report, String> {
+    let mut notes = Vec::new();
+
+    // `!x` can be written without a space
+    let split_not;
+    let tokens = match tokens {
+        [first] if first.len() > 1 && first.starts_with('!') => {
+            split_not = ["!", &first[1..]];
+            &split_not[..]
+        }
+        _ => tokens,
+    };
+
+    let (expression, columns) = match tokens {
+        [text] => {
+            let a = operand::<T>(text)?;
+            (text.to_string(), vec![value_column("", a, &unmarked)])
+        }
+        [word, rest @ ..] if *word == "from_be_bytes" || *word == "from_le_bytes" => {
+            let operation = Operation::parse_prefix(word).expect("checked above");
+            let bytes = byte_array(rest)?;
+            let size = T::BITS as usize / 8;
+            let value = T::from_bytes(operation, &bytes).ok_or_else(|| {
+                format!("mismatched types: expected an array with a fixed size of {} elements, found one with {} elements", size, bytes.len())
+            })?;
+            let hex: Vec<String> = bytes.iter().map(|b| format!("{:#04x}", b)).collect();
+            let expression = operation.call(T::NAME, &format!("[{}]", hex.join(", ")));
+            if operation == Operation::FromLeBytes && size > 1 {
+                notes.push(String::from("the first byte is the least significant, so it ends up on the right"));
+            }
+            (expression, vec![bytes_column("before", &bytes), value_column("after", value, &unmarked)])
+        }
+        [word, text] if Operation::parse_prefix(word).is_some() => {
+            let operation = Operation::parse_prefix(word).expect("checked above");
+            let a = operand::<T>(text)?;
+            let expression = operation.call(text, "");
+            match operation {
+                Operation::Not | Operation::SwapBytes | Operation::ReverseBits => {
+                    let after = a.apply(operation, a);
+                    if operation == Operation::Not && T::SIGNED {
+                        notes.push(format!("for signed types !x is -x - 1, here {}", after));
+                    }
+                    if operation == Operation::SwapBytes && T::BITS == 8 {
+                        notes.push(format!("{} has a single byte, so there's nothing to swap", T::NAME));
+                    }
+                    (expression, vec![value_column("before", a, &unmarked), value_column("after", after, &unmarked)])
+                }
+                Operation::CountOnes | Operation::CountZeros | Operation::LeadingZeros | Operation::TrailingZeros => {
+                    let count = a.count(operation);
+                    let bits = a.to_bits();
+                    let marked = |bit: u32| match operation {
+                        Operation::CountOnes => bits >> bit & 1 == 1,
+                        Operation::CountZeros => bits >> bit & 1 == 0,
+                        Operation::LeadingZeros => bit >= T::BITS - count,
+                        _ => bit < count,
+                    };
+                    notes.push(format!("{} = {}, the bits marked with ^", expression, count));
+                    (expression, vec![value_column("", a, &marked)])
+                }
+                _ => {
+                    let bytes = a.to_bytes(operation);
+                    let native = if cfg!(target_endian = "little") { Operation::ToLeBytes } else { Operation::ToBeBytes };
+                    if operation == native && T::BITS > 8 {
+                        notes.push(format!("this machine is {}-endian, so to_ne_bytes gives the same bytes", if cfg!(target_endian = "little") { "little" } else { "big" }));
+                    }
+                    (expression, vec![value_column("before", a, &unmarked), bytes_column("after", &bytes)])
+                }
+            }
+        }
+        [text, op, b] if Operation::parse_infix(op).is_some() => {
+            let operation = Operation::parse_infix(op).expect("checked above");
+            let a = operand::<T>(text)?;
+            match operation {
+                Operation::And | Operation::Or | Operation::Xor => {
+                    let expression = operation.call(text, b);
+                    let b = operand::<T>(b)?;
+                    let after = a.apply(operation, b);
+                    (expression, vec![value_column("a", a, &unmarked), value_column("b", b, &unmarked), value_column("after", after, &unmarked)])
+                }
+                Operation::RotateLeft | Operation::RotateRight => {
+                    let by = shift_amount(b)?;
+                    let after = a.rotate(operation, by);
+                    if by >= T::BITS {
+                        notes.push(format!("rotating never panics, rotating by {} is rotating by {} % {} = {}", by, by, T::BITS, by % T::BITS));
+                    }
+                    (operation.call(text, &by.to_string()), vec![value_column("before", a, &unmarked), value_column("after", after, &unmarked)])
+                }
+                _ => {
+                    let by = shift_amount(b)?;
+                    let (op, method) = if operation == Operation::Shl { (Op::Shl, "shl") } else { (Op::Shr, "shr") };
+                    let expression = operation.call(text, &by.to_string());
+                    let after = match a.checked_shift(operation, by) {
+                        Some(after) => after,
+                        None => {
+                            let release = a.wrapping_shift(operation, by);
+                            notes.push(format!("debug build: panic: {}", op.overflow_message()));
+                            notes.push(format!("release build: shifts by {} % {} = {} instead, which is the after column", by, T::BITS, by % T::BITS));
+                            notes.push(String::from("with a constant shift amount it doesn't even compile: this arithmetic operation will overflow"));
+                            notes.push(format!("checked_{} gives None, wrapping_{} gives {}", method, method, release));
+                            release
+                        }
+                    };
+                    if operation == Operation::Shr && T::SIGNED {
+                        notes.push(format!("{} >> is an arithmetic shift: the sign bit is copied in from the left", T::NAME));
+                    }
+                    (expression, vec![value_column("before", a, &unmarked), value_column("after", after, &unmarked)])
+                }
+            }
+        }
+        _ => return Err(String::from("expected `[TYPE] A OP B`, `[TYPE] METHOD A`, `[TYPE] !A` or `[TYPE] from_be_bytes [BYTES]`")),
+    };
+
+    Ok(Report { expression: format!("{}  ({})", expression, T::NAME), columns, notes })
+}
+
+// the operand's suffix decides the type when there's no type in front, like `0xffu8 >> 2`.
+// byte arrays go by how many bytes there are
+fn infer(tokens: &[&str]) -> Result<String, String> {
+    let text = match tokens {
+        [word, rest @ ..] if *word == "from_be_bytes" || *word == "from_le_bytes" => {
+            return match byte_array(rest)?.len() {
+                1 => Ok(String::from("u8")),
+                2 => Ok(String::from("u16")),
+                4 => Ok(String::from("u32")),
+                8 => Ok(String::from("u64")),
+                16 => Ok(String::from("u128")),
+                n => Err(format!("no integer type is {} bytes wide", n)),
+            };
+        }
+        // both sides of & | ^ have the same type, so a suffix on the right counts too. shifts
+        // and rotates take their amount as whatever type it is, so only the left side counts
+        [a, op, b] if ["&", "|", "^"].contains(op) => {
+            let a = literal::parse(a).map_err(|e| render(&e, a))?;
+            let b = literal::parse(b).map_err(|e| render(&e, b))?;
+            return Ok(if !a.suffixed && b.suffixed { b.ty } else { a.ty });
+        }
+        [word, text] if Operation::parse_prefix(word).is_some() => *text,
+        [text, ..] => text.trim_start_matches('!'),
+        [] => return Err(String::from("nothing to evaluate")),
+    };
+    let literal = literal::parse(text).map_err(|e| render(&e, text))?;
+    Ok(literal.ty)
+}
+
+// evaluates a line like `u8 0b1111_0000 >> 2`, `u32 swap_bytes 0x12345678`, `!0u8` or
+// `from_le_bytes [0x78, 0x56, 0x34, 0x12]`
+pub fn evaluate(line: &str) -> Result<Report, String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let (ty, rest) = match tokens.split_first() {
+        Some((ty, rest)) if TYPES.contains(ty) => (ty.to_string(), rest),
+        _ => (infer(&tokens)?, &tokens[..]),
+    };
+
+    match ty.as_str() {
+        "i8" => run::<i8>(rest),
+        "i16" => run::<i16>(rest),
+        "i32" => run::<i32>(rest),
+        "i64" => run::<i64>(rest),
+        "i128" => run::<i128>(rest),
+        "isize" => run::<isize>(rest),
+        "u8" => run::<u8>(rest),
+        "u16" => run::<u16>(rest),
+        "u32" => run::<u32>(rest),
+        "u64" => run::<u64>(rest),
+        "u128" => run::<u128>(rest),
+        "usize" => run::<usize>(rest),
+        other => Err(format!("the bit operators need integers, not `{}`", other)),
+    }
+}
@@ -0,0 +1,696 @@
+// a calculator that follows rust's rules for the numeric operations in ch3/varsandmutability.rs.
+// unsuffixed literals are `{integer}` or `{float}` until something pins them down, and fall back
+// to i32 and f64. `1 + 2.0` is a type error, `-7 / 2` is -3, and overflow either panics (debug)
+// or wraps (release).
+//
+// everything typed in here is a constant, so rustc would actually reject `255u8 + 1` at compile
+// time with `#[deny(arithmetic_overflow)]`. the calculator acts as if the values only turned up
+// at runtime, which is what the two build modes are about
+
+use std::fmt;
+
+use crate::literal::{self, integer_type, Literal, LiteralError, Value as LiteralValue, INTEGER_TYPES};
+use crate::overflow::Op;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    // overflow panics, like `cargo build`
+    Debug,
+    // overflow wraps around, like `cargo build --release`
+    Release,
+}
+
+impl Mode {
+    pub fn parse(s: &str) -> Option<Mode> {
+        match s {
+            "debug" => Some(Mode::Debug),
+            "release" => Some(Mode::Release),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Mode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Mode::Debug => write!(f, "debug"),
+            Mode::Release => write!(f, "release"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Ty {
+    Int(&'static str),
+    Float(&'static str),
+    // a literal that hasn't been pinned to a type yet
+    IntVar,
+    FloatVar,
+}
+
+impl Ty {
+    fn named(name: &str) -> Option<Ty> {
+        match name {
+            "f32" => Some(Ty::Float("f32")),
+            "f64" => Some(Ty::Float("f64")),
+            _ => INTEGER_TYPES.iter().find(|t| **t == name).map(|t| Ty::Int(t)),
+        }
+    }
+
+    // what an unconstrained literal falls back to
+    fn or_default(self) -> Ty {
+        match self {
+            Ty::IntVar => Ty::Int("i32"),
+            Ty::FloatVar => Ty::Float("f64"),
+            ty => ty,
+        }
+    }
+
+    fn is_unsigned(self) -> bool {
+        match self {
+            Ty::Int(name) => integer_type(name).is_some_and(|(_, signed)| !signed),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Ty {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Ty::Int(name) | Ty::Float(name) => write!(f, "{}", name),
+            Ty::IntVar => write!(f, "{{integer}}"),
+            Ty::FloatVar => write!(f, "{{float}}"),
+        }
+    }
+}
+
+// the operator as it's written, and the trait behind it
+fn symbol(op: Op) -> (&'static str, &'static str) {
+    match op {
+        Op::Add => ("+", "Add"),
+        Op::Sub => ("-", "Sub"),
+        Op::Mul => ("*", "Mul"),
+        Op::Div => ("/", "Div"),
+        Op::Rem => ("%", "Rem"),
+        _ => unreachable!("the calculator has no shifts"),
+    }
+}
+
+// what rustc says when there's no impl for `left op right`
+fn no_impl_message(op: Op, left: Ty, right: Ty) -> String {
+    match (op, left, right) {
+        (Op::Add, Ty::IntVar, Ty::FloatVar) => String::from("cannot add a float to an integer"),
+        (Op::Add, Ty::FloatVar, Ty::IntVar) => String::from("cannot add an integer to a float"),
+        (Op::Add, _, _) => format!("cannot add `{}` to `{}`", right, left),
+        (Op::Sub, _, _) => format!("cannot subtract `{}` from `{}`", right, left),
+        (Op::Mul, _, _) => format!("cannot multiply `{}` by `{}`", left, right),
+        (Op::Div, _, _) => format!("cannot divide `{}` by `{}`", left, right),
+        _ => format!("cannot calculate the remainder of `{}` divided by `{}`", left, right),
+    }
+}
+
+#[derive(Debug)]
+enum Kind {
+    Literal(Literal),
+    Neg(Box<Expr>),
+    // the operator's own position is kept for errors that point at it
+    Binary(Op, (usize, usize), Box<Expr>, Box<Expr>),
+    Cast(Box<Expr>),
+}
+
+#[derive(Debug)]
+struct Expr {
+    kind: Kind,
+    ty: Ty,
+    start: usize,
+    end: usize,
+}
+
+impl Expr {
+    fn error(&self, message: String) -> LiteralError {
+        LiteralError::new(message, self.start, self.end)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Number(String),
+    Symbol(char),
+    Word(String),
+}
+
+impl fmt::Display for Token {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Token::Number(text) | Token::Word(text) => write!(f, "{}", text),
+            Token::Symbol(c) => write!(f, "{}", c),
+        }
+    }
+}
+
+fn tokenize(line: &str) -> Result<Vec<(Token, usize, usize)>, LiteralError> {
+    let chars: Vec<(usize, char)> = line.char_indices().collect();
+    let at = |i: usize| chars.get(i).map(|(_, c)| *c);
+    let offset = |i: usize| chars.get(i).map(|(o, _)| *o).unwrap_or(line.len());
+
+    let mut tokens = Vec::new();
+    let mut i = 0;
+    while let Some(c) = at(i) {
+        let start = i;
+        if c.is_whitespace() {
+            i += 1;
+            continue;
+        }
+
+        if c == 'b' && at(i + 1) == Some('\'') {
+            // a byte literal runs to the next unescaped quote
+            i += 2;
+            while let Some(c) = at(i) {
+                i += 1;
+                if c == '\\' {
+                    i += 1;
+                } else if c == '\'' {
+                    break;
+                }
+            }
+            tokens.push((Token::Number(line[offset(start)..offset(i)].to_string()), offset(start), offset(i)));
+        } else if c.is_ascii_digit() {
+            let hex = c == '0' && matches!(at(i + 1), Some('x') | Some('X'));
+            while let Some(c) = at(i) {
+                let exponent_sign = !hex && matches!(c, '+' | '-') && matches!(at(i - 1), Some('e') | Some('E'));
+                // `1.5` and `1.` are floats, `1.max(2)` would be a method call
+                let point = c == '.' && !at(i + 1).is_some_and(|n| n.is_alphabetic() || n == '_' || n == '.');
+                if c.is_ascii_alphanumeric() || c == '_' || point || exponent_sign {
+                    i += 1;
+                } else {
+                    break;
+                }
+            }
+            tokens.push((Token::Number(line[offset(start)..offset(i)].to_string()), offset(start), offset(i)));
+        } else if c.is_alphabetic() || c == '_' {
+            while at(i).is_some_and(|c| c.is_alphanumeric() || c == '_') {
+                i += 1;
+            }
+            tokens.push((Token::Word(line[offset(start)..offset(i)].to_string()), offset(start), offset(i)));
+        } else if "+-*/%()".contains(c) {
+            i += 1;
+            tokens.push((Token::Symbol(c), offset(start), offset(i)));
+        } else {
+            return Err(LiteralError::new(format!("unknown start of token: {}", c), offset(i), offset(i + 1)));
+        }
+    }
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<(Token, usize, usize)>,
+    position: usize,
+    // where the line ends, for errors about running out of input
+    end: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(token, _, _)| token)
+    }
+
+    fn next(&mut self) -> Option<(Token, usize, usize)> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn unexpected(&self, expected: &str) -> LiteralError {
+        match self.tokens.get(self.position) {
+            Some((token, start, end)) => LiteralError::new(format!("expected {}, found `{}`", expected, token), *start, *end)
+                .label(&format!("expected {}", expected)),
+            None => LiteralError::new(format!("expected {}, found `<eof>`", expected), self.end, self.end)
+                .label(&format!("expected {}", expected)),
+        }
+    }
+
+    // expr := term (('+' | '-') term)*
+    fn expression(&mut self) -> Result<Expr, LiteralError> {
+        let mut left = self.term()?;
+        while let Some(Token::Symbol(c)) = self.peek() {
+            let op = match c {
+                '+' => Op::Add,
+                '-' => Op::Sub,
+                _ => break,
+            };
+            left = self.binary(op, left, Parser::term)?;
+        }
+        Ok(left)
+    }
+
+    // term := cast (('*' | '/' | '%') cast)*
+    fn term(&mut self) -> Result<Expr, LiteralError> {
+        let mut left = self.cast()?;
+        while let Some(Token::Symbol(c)) = self.peek() {
+            let op = match c {
+                '*' => Op::Mul,
+                '/' => Op::Div,
+                '%' => Op::Rem,
+                _ => break,
+            };
+            left = self.binary(op, left, Parser::cast)?;
+        }
+        Ok(left)
+    }
+
+    fn binary(&mut self, op: Op, left: Expr, operand: fn(&mut Parser) -> Result<Expr, LiteralError>) -> Result<Expr, LiteralError> {
+        let (_, op_start, op_end) = self.next().expect("peeked the operator");
+        let right = operand(self)?;
+        Ok(Expr {
+            start: left.start,
+            end: right.end,
+            ty: Ty::IntVar,
+            kind: Kind::Binary(op, (op_start, op_end), Box::new(left), Box::new(right)),
+        })
+    }
+
+    // cast := unary ('as' TYPE)*. unary minus binds tighter than `as`, so `-1 as u8` is
+    // `(-1) as u8`
+    fn cast(&mut self) -> Result<Expr, LiteralError> {
+        let mut expr = self.unary()?;
+        while self.peek() == Some(&Token::Word(String::from("as"))) {
+            self.next();
+            let (token, start, end) = match self.next() {
+                Some(next) => next,
+                None => {
+                    self.position -= 1;
+                    return Err(self.unexpected("type"));
+                }
+            };
+            let ty = match &token {
+                Token::Word(name) => match (Ty::named(name), name.as_str()) {
+                    (Some(ty), _) => ty,
+                    (None, "bool") | (None, "char") => {
+                        return Err(LiteralError::new(format!("the calculator only works with numeric types, not `{}`", name), start, end))
+                    }
+                    (None, _) => {
+                        return Err(LiteralError::new(format!("cannot find type `{}` in this scope", name), start, end)
+                            .code("E0412")
+                            .label("not found in this scope"))
+                    }
+                },
+                _ => {
+                    self.position -= 1;
+                    return Err(self.unexpected("type"));
+                }
+            };
+            expr = Expr {
+                start: expr.start,
+                end,
+                ty,
+                kind: Kind::Cast(Box::new(expr)),
+            };
+        }
+        Ok(expr)
+    }
+
+    // unary := '-' unary | primary
+    fn unary(&mut self) -> Result<Expr, LiteralError> {
+        if self.peek() == Some(&Token::Symbol('-')) {
+            let (_, start, _) = self.next().expect("peeked the minus");
+            let inner = self.unary()?;
+            return Ok(Expr {
+                start,
+                end: inner.end,
+                ty: Ty::IntVar,
+                kind: Kind::Neg(Box::new(inner)),
+            });
+        }
+        self.primary()
+    }
+
+    // primary := NUMBER | '(' expr ')'
+    fn primary(&mut self) -> Result<Expr, LiteralError> {
+        match self.peek().cloned() {
+            Some(Token::Number(text)) => {
+                let (_, start, end) = self.next().expect("peeked the number");
+                let literal = literal::parse(&text).map_err(|mut e| {
+                    e.start += start;
+                    e.end += start;
+                    e
+                })?;
+                Ok(Expr { start, end, ty: Ty::IntVar, kind: Kind::Literal(literal) })
+            }
+            Some(Token::Symbol('(')) => {
+                let (_, start, _) = self.next().expect("peeked the paren");
+                let mut inner = self.expression()?;
+                match self.next() {
+                    Some((Token::Symbol(')'), _, end)) => {
+                        // the parens don't change anything, but errors should underline them too
+                        inner.start = start;
+                        inner.end = end;
+                        Ok(inner)
+                    }
+                    _ => {
+                        self.position -= 1;
+                        Err(self.unexpected("`)`"))
+                    }
+                }
+            }
+            Some(Token::Word(name)) => {
+                let (_, start, end) = self.next().expect("peeked the word");
+                Err(LiteralError::new(format!("cannot find value `{}` in this scope", name), start, end)
+                    .code("E0425")
+                    .label("not found in this scope"))
+            }
+            _ => Err(self.unexpected("expression")),
+        }
+    }
+}
+
+// works out a type for every part of the expression, bottom up. `hint` is the type an `as` cast
+// is going to, which rustc uses for a bare literal: that's why `300 as u8` is an out of range
+// literal and `-1 as u8` can't be negated
+fn check(expr: &mut Expr, hint: Option<Ty>) -> Result<Ty, LiteralError> {
+    let ty = match &mut expr.kind {
+        Kind::Literal(literal) => match (&literal.value, literal.suffixed, hint) {
+            (_, true, _) => Ty::named(&literal.ty).expect("the literal module checks suffixes"),
+            (LiteralValue::Integer(_), false, Some(Ty::Int(name))) => Ty::Int(name),
+            (LiteralValue::Integer(_), false, _) => Ty::IntVar,
+            (LiteralValue::Float(_), false, Some(Ty::Float(name))) => Ty::Float(name),
+            (LiteralValue::Float(_), false, _) => Ty::FloatVar,
+        },
+        Kind::Neg(inner) => {
+            let ty = check(inner, hint)?;
+            if ty.is_unsigned() {
+                return Err(expr.error(format!("cannot apply unary operator `-` to type `{}`", ty))
+                    .code("E0600")
+                    .label("cannot apply unary operator `-`")
+                    .note(String::from("note: unsigned values cannot be negated")));
+            }
+            ty
+        }
+        Kind::Binary(op, (op_start, op_end), left, right) => {
+            let left_ty = check(left, None)?;
+            let right_ty = check(right, None)?;
+            match (left_ty, right_ty) {
+                (a, b) if a == b => a,
+                (Ty::IntVar, Ty::Int(name)) | (Ty::Int(name), Ty::IntVar) => Ty::Int(name),
+                (Ty::FloatVar, Ty::Float(name)) | (Ty::Float(name), Ty::FloatVar) => Ty::Float(name),
+                (Ty::Int(a), Ty::Int(b)) | (Ty::Float(a), Ty::Float(b)) => {
+                    return Err(right.error(String::from("mismatched types"))
+                        .code("E0308")
+                        .label(&format!("expected `{}`, found `{}`", a, b)));
+                }
+                (a, b) => {
+                    let (symbol, trait_name) = symbol(*op);
+                    let mut error = LiteralError::new(no_impl_message(*op, a, b), *op_start, *op_end)
+                        .code("E0277")
+                        .label(&format!("no implementation for `{} {} {}`", a, symbol, b))
+                        .note(format!("help: the trait `{}<{}>` is not implemented for `{}`", trait_name, b, a));
+                    if a == Ty::IntVar && matches!(b, Ty::Float(_) | Ty::FloatVar) {
+                        error = error.note(String::from("help: consider using a floating-point literal by writing it with `.0`"));
+                    } else {
+                        error = error.note(String::from("help: use `as` to convert one side to the other's type"));
+                    }
+                    return Err(error);
+                }
+            }
+        }
+        Kind::Cast(inner) => {
+            check(inner, Some(expr.ty))?;
+            expr.ty
+        }
+    };
+    expr.ty = ty;
+    Ok(ty)
+}
+
+// settles every `{integer}` and `{float}` top down, now that the type of the whole thing is
+// known, and runs the literal range checks against the settled types
+fn settle(expr: &mut Expr, ty: Ty) -> Result<(), LiteralError> {
+    if matches!(expr.ty, Ty::IntVar | Ty::FloatVar) {
+        expr.ty = ty;
+    }
+
+    match &mut expr.kind {
+        Kind::Literal(literal) => settle_literal(literal, expr.ty, false, expr.start),
+        Kind::Neg(inner) => {
+            // only reachable when the type came from somewhere else, like `-1 + 2u8`
+            if expr.ty.is_unsigned() {
+                return Err(LiteralError::new(format!("the trait bound `{}: Neg` is not satisfied", expr.ty), expr.start, inner.end)
+                    .code("E0277")
+                    .label(&format!("the trait `Neg` is not implemented for `{}`", expr.ty))
+                    .note(String::from("help: consider specifying an integer type that can be negative")));
+            }
+            match &mut inner.kind {
+                // `-128i8` is fine even though `128i8` on its own isn't
+                Kind::Literal(literal) => settle_literal(literal, expr.ty, true, inner.start),
+                _ => settle(inner, expr.ty),
+            }
+        }
+        Kind::Binary(_, _, left, right) => {
+            settle(left, expr.ty)?;
+            settle(right, expr.ty)
+        }
+        Kind::Cast(inner) => {
+            let ty = inner.ty.or_default();
+            settle(inner, ty)
+        }
+    }
+}
+
+fn settle_literal(literal: &mut Literal, ty: Ty, negative: bool, start: usize) -> Result<(), LiteralError> {
+    let name = match ty {
+        Ty::Int(name) | Ty::Float(name) => name,
+        _ => unreachable!("settled before the literal"),
+    };
+
+    // an unsuffixed float that turned out to be an f32 is rounded straight to f32
+    if name == "f32" && !literal.suffixed {
+        if let Ok(reparsed) = literal::parse(&format!("{}f32", literal.source)) {
+            literal.value = reparsed.value;
+        }
+    }
+    literal.ty = name.to_string();
+    literal.negative = negative;
+
+    match literal.range_error() {
+        Some(mut error) => {
+            error.start += start;
+            error.end += start;
+            Err(error)
+        }
+        None => Ok(()),
+    }
+}
+
+// the operations the calculator needs from every integer type, all done with the real methods
+trait Int: Copy + fmt::Display {
+    // values are kept as u128 bits, sign extended, so `bits as T` gets the value back
+    fn from_bits(bits: u128) -> Self;
+    fn to_bits(self) -> u128;
+    // `as` from a float: rounds toward zero, saturates at the ends and turns NaN into 0
+    fn from_f64(value: f64) -> Self;
+    fn to_f64(self) -> f64;
+    fn to_f32(self) -> f32;
+    fn arithmetic(a: Self, op: Op, b: Self, mode: Mode) -> Result<Self, &'static str>;
+}
+
+macro_rules! int {
+    ($($t:ty),*) => {
+        $(
+            impl Int for $t {
+                fn from_bits(bits: u128) -> $t {
+                    bits as $t
+                }
+
+                fn to_bits(self) -> u128 {
+                    self as i128 as u128
+                }
+
+                fn from_f64(value: f64) -> $t {
+                    value as $t
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                fn to_f32(self) -> f32 {
+                    self as f32
+                }
+
+                fn arithmetic(a: $t, op: Op, b: $t, mode: Mode) -> Result<$t, &'static str> {
+                    let checked = match op {
+                        Op::Add => a.checked_add(b),
+                        Op::Sub => a.checked_sub(b),
+                        Op::Mul => a.checked_mul(b),
+                        Op::Div => a.checked_div(b),
+                        Op::Rem => a.checked_rem(b),
+                        Op::Neg => a.checked_neg(),
+                        _ => unreachable!("the calculator has no shifts"),
+                    };
+                    if let Some(value) = checked {
+                        return Ok(value);
+                    }
+
+                    // dividing by zero and MIN / -1 panic in release builds too
+                    if op == Op::Div || op == Op::Rem {
+                        return Err(if b == 0 { op.zero_message() } else { op.overflow_message() });
+                    }
+                    match mode {
+                        Mode::Debug => Err(op.overflow_message()),
+                        Mode::Release => Ok(match op {
+                            Op::Add => a.wrapping_add(b),
+                            Op::Sub => a.wrapping_sub(b),
+                            Op::Mul => a.wrapping_mul(b),
+                            _ => a.wrapping_neg(),
+                        }),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+// runs `$body` with `$t` standing for the integer type called `$name`
+macro_rules! with_int {
+    ($name:expr, $t:ident => $body:expr) => {
+        match $name {
+            "i8" => { type $t = i8; $body }
+            "i16" => { type $t = i16; $body }
+            "i32" => { type $t = i32; $body }
+            "i64" => { type $t = i64; $body }
+            "i128" => { type $t = i128; $body }
+            "isize" => { type $t = isize; $body }
+            "u8" => { type $t = u8; $body }
+            "u16" => { type $t = u16; $body }
+            "u32" => { type $t = u32; $body }
+            "u64" => { type $t = u64; $body }
+            "u128" => { type $t = u128; $body }
+            "usize" => { type $t = usize; $body }
+            other => unreachable!("`{}` isn't an integer type", other),
+        }
+    };
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    Int(&'static str, u128),
+    // f32 values are kept as the f64 with the same value, which is always exact
+    Float(&'static str, f64),
+}
+
+impl Value {
+    pub fn ty(&self) -> &'static str {
+        match self {
+            Value::Int(ty, _) | Value::Float(ty, _) => ty,
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            Value::Int(ty, bits) => with_int!(ty, T => write!(f, "{}", T::from_bits(bits))),
+            Value::Float("f32", value) => write!(f, "{:?}", value as f32),
+            Value::Float(_, value) => write!(f, "{:?}", value),
+        }
+    }
+}
+
+// `%` on floats keeps the sign of the left side, like integer `%` does
+macro_rules! float_arithmetic {
+    ($a:expr, $op:expr, $b:expr) => {
+        match $op {
+            Op::Add => $a + $b,
+            Op::Sub => $a - $b,
+            Op::Mul => $a * $b,
+            Op::Div => $a / $b,
+            _ => $a % $b,
+        }
+    };
+}
+
+fn eval(expr: &Expr, mode: Mode) -> Result<Value, &'static str> {
+    match &expr.kind {
+        Kind::Literal(literal) => Ok(literal_value(literal, expr.ty)),
+        Kind::Neg(inner) => {
+            if let Kind::Literal(literal) = &inner.kind {
+                // a negated literal is a single constant, so `-128i8` never overflows
+                return Ok(literal_value(literal, expr.ty));
+            }
+            match eval(inner, mode)? {
+                Value::Int(ty, bits) => with_int!(ty, T => T::arithmetic(T::from_bits(bits), Op::Neg, 0, mode).map(|v| Value::Int(ty, v.to_bits()))),
+                Value::Float(ty, value) => Ok(Value::Float(ty, -value)),
+            }
+        }
+        Kind::Binary(op, _, left, right) => {
+            let (op, left, right) = (*op, eval(left, mode)?, eval(right, mode)?);
+            match (left, right) {
+                (Value::Int(ty, a), Value::Int(_, b)) => {
+                    with_int!(ty, T => T::arithmetic(T::from_bits(a), op, T::from_bits(b), mode).map(|v| Value::Int(ty, v.to_bits())))
+                }
+                (Value::Float("f32", a), Value::Float(_, b)) => Ok(Value::Float("f32", float_arithmetic!(a as f32, op, b as f32) as f64)),
+                (Value::Float(ty, a), Value::Float(_, b)) => Ok(Value::Float(ty, float_arithmetic!(a, op, b))),
+                _ => unreachable!("the type check rules out mixing"),
+            }
+        }
+        Kind::Cast(inner) => {
+            let value = eval(inner, mode)?;
+            Ok(match (value, expr.ty) {
+                // truncating or sign extending is just taking the low bits of the sign extended value
+                (Value::Int(_, bits), Ty::Int(to)) => with_int!(to, T => Value::Int(to, T::from_bits(bits).to_bits())),
+                (Value::Int(from, bits), Ty::Float("f32")) => Value::Float("f32", with_int!(from, T => T::from_bits(bits).to_f32()) as f64),
+                (Value::Int(from, bits), Ty::Float(to)) => Value::Float(to, with_int!(from, T => T::from_bits(bits).to_f64())),
+                (Value::Float(_, value), Ty::Int(to)) => with_int!(to, T => Value::Int(to, T::from_f64(value).to_bits())),
+                (Value::Float(_, value), Ty::Float("f32")) => Value::Float("f32", value as f32 as f64),
+                (Value::Float(_, value), Ty::Float(to)) => Value::Float(to, value),
+                _ => unreachable!("casts always go to a concrete type"),
+            })
+        }
+    }
+}
+
+fn literal_value(literal: &Literal, ty: Ty) -> Value {
+    match (literal.value, ty) {
+        (LiteralValue::Integer(magnitude), Ty::Int(name)) => {
+            let bits = if literal.negative { magnitude.wrapping_neg() } else { magnitude };
+            with_int!(name, T => Value::Int(name, T::from_bits(bits).to_bits()))
+        }
+        (LiteralValue::Float(value), Ty::Float(name)) => Value::Float(name, if literal.negative { -value } else { value }),
+        _ => unreachable!("literals are settled to their own kind of type"),
+    }
+}
+
+pub enum Outcome {
+    Value(Value),
+    Panic(&'static str),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Value(value) => write!(f, "{}: {}", value, value.ty()),
+            Outcome::Panic(message) => write!(f, "thread 'main' panicked: {}", message),
+        }
+    }
+}
+
+// evaluates a line like `5 + 10`, `-7 / 2`, `255u8 + 1` or `95.5 - 4.3 as f32`. compile errors
+// come back already rendered against the line
+pub fn evaluate(line: &str, mode: Mode) -> Result<Outcome, String> {
+    let line = line.trim();
+    let tokens = tokenize(line).map_err(|e| e.render(line))?;
+    let mut parser = Parser { tokens, position: 0, end: line.len() };
+    let mut expr = parser.expression().map_err(|e| e.render(line))?;
+    if parser.peek().is_some() {
+        return Err(parser.unexpected("an operator").render(line));
+    }
+
+    let ty = check(&mut expr, None).map_err(|e| e.render(line))?;
+    settle(&mut expr, ty.or_default()).map_err(|e| e.render(line))?;
+
+    Ok(match eval(&expr, mode) {
+        Ok(value) => Outcome::Value(value),
+        Err(message) => Outcome::Panic(message),
+    })
+}
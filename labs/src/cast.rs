@@ -0,0 +1,488 @@
+// what `as` does between every pair of primitive types from ch3/varsandmutability.rs, and
+// whether `From` or `TryFrom` would do the same job. every value in the tables comes from running
+// the real cast or conversion, and whether an impl exists is asked of the compiler (see `Probe`)
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::marker::PhantomData;
+
+pub const TYPES: [&str; 16] = [
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32", "f64", "bool", "char",
+];
+
+// a value of any primitive type
+#[derive(Debug, Clone, Copy)]
+pub enum Prim {
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    Isize(isize),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    Usize(usize),
+    F32(f32),
+    F64(f64),
+    Bool(bool),
+    Char(char),
+}
+
+impl fmt::Display for Prim {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Prim::I8(v) => write!(f, "{}", v),
+            Prim::I16(v) => write!(f, "{}", v),
+            Prim::I32(v) => write!(f, "{}", v),
+            Prim::I64(v) => write!(f, "{}", v),
+            Prim::I128(v) => write!(f, "{}", v),
+            Prim::Isize(v) => write!(f, "{}", v),
+            Prim::U8(v) => write!(f, "{}", v),
+            Prim::U16(v) => write!(f, "{}", v),
+            Prim::U32(v) => write!(f, "{}", v),
+            Prim::U64(v) => write!(f, "{}", v),
+            Prim::U128(v) => write!(f, "{}", v),
+            Prim::Usize(v) => write!(f, "{}", v),
+            Prim::F32(v) => write!(f, "{:?}", v),
+            Prim::F64(v) => write!(f, "{:?}", v),
+            Prim::Bool(v) => write!(f, "{}", v),
+            Prim::Char(v) => write!(f, "{:?} (U+{:04X})", v, *v as u32),
+        }
+    }
+}
+
+// casts to every numeric type in the list, or None if `target` isn't one of them
+macro_rules! to {
+    ($v:expr, $target:expr, [$($variant:ident $t:ty),*]) => {
+        match $target {
+            $(stringify!($t) => Some(Prim::$variant($v as $t)),)*
+            _ => None,
+        }
+    };
+}
+
+// `value as target`, or None where rustc won't compile the cast
+pub fn cast(value: Prim, target: &str) -> Option<Prim> {
+    macro_rules! integers {
+        ($v:expr) => {
+            to!($v, target, [I8 i8, I16 i16, I32 i32, I64 i64, I128 i128, Isize isize, U8 u8, U16 u16, U32 u32, U64 u64, U128 u128, Usize usize])
+        };
+    }
+    macro_rules! numbers {
+        ($v:expr) => {
+            integers!($v).or_else(|| to!($v, target, [F32 f32, F64 f64]))
+        };
+    }
+
+    match value {
+        // u8 is the only thing that can become a char with `as`, since every u8 is a valid one
+        Prim::U8(v) if target == "char" => Some(Prim::Char(v as char)),
+        Prim::I8(v) => numbers!(v),
+        Prim::I16(v) => numbers!(v),
+        Prim::I32(v) => numbers!(v),
+        Prim::I64(v) => numbers!(v),
+        Prim::I128(v) => numbers!(v),
+        Prim::Isize(v) => numbers!(v),
+        Prim::U8(v) => numbers!(v),
+        Prim::U16(v) => numbers!(v),
+        Prim::U32(v) => numbers!(v),
+        Prim::U64(v) => numbers!(v),
+        Prim::U128(v) => numbers!(v),
+        Prim::Usize(v) => numbers!(v),
+        Prim::F32(v) => numbers!(v),
+        Prim::F64(v) => numbers!(v),
+        Prim::Bool(v) if target == "bool" => Some(Prim::Bool(v)),
+        Prim::Bool(v) => integers!(v),
+        Prim::Char(v) if target == "char" => Some(Prim::Char(v)),
+        Prim::Char(v) => integers!(v),
+    }
+}
+
+// the error rustc gives for a cast it won't compile
+fn cast_error(source: &str, target: &str) -> String {
+    let float = source == "f32" || source == "f64";
+    match target {
+        "bool" if source == "char" => String::from("error[E0054]: cannot cast `char` as `bool`"),
+        "bool" => format!("error[E0054]: cannot cast `{}` as `bool` (help: compare with zero instead)", source),
+        "char" if source == "bool" => String::from("error[E0604]: only `u8` can be cast as `char`, not `bool`"),
+        "char" if float => format!("error[E0604]: only `u8` can be cast as `char`, not `{}` (help: consider using `char::from_u32` instead (via a `u32`))", source),
+        "char" => format!("error[E0604]: only `u8` can be cast as `char`, not `{}` (help: consider using `char::from_u32` instead)", source),
+        _ => format!("error[E0606]: casting `{}` as `{}` is invalid (help: cast through an integer first)", source, target),
+    }
+}
+
+// the values worth trying for each type: the ends of its range, zero and one, and the values
+// where the other types' ranges end
+trait Edges: Sized + Copy {
+    fn edges() -> Vec<Self>;
+    fn prim(self) -> Prim;
+}
+
+// the boundaries of every integer type, plus 2^24 + 1, the first integer f32 can't hold
+const INTEGER_EDGES: [i128; 18] = [
+    -129, -128, -1, 0, 1, 127, 128, 255, 256, 32767, 32768, 65535, 65536, 16_777_217,
+    2_147_483_647, 2_147_483_648, 4_294_967_295, 4_294_967_296,
+];
+
+macro_rules! integer_edges {
+    ($($variant:ident $t:ty),*) => {
+        $(
+            impl Edges for $t {
+                fn edges() -> Vec<$t> {
+                    let mut values = vec![<$t>::MIN, <$t>::MAX];
+                    values.extend(INTEGER_EDGES.iter().filter_map(|v| <$t>::try_from(*v).ok()));
+                    values.sort();
+                    values.dedup();
+                    values
+                }
+
+                fn prim(self) -> Prim {
+                    Prim::$variant(self)
+                }
+            }
+        )*
+    };
+}
+
+integer_edges!(I8 i8, I16 i16, I32 i32, I64 i64, I128 i128, Isize isize, U8 u8, U16 u16, U32 u32, U64 u64, U128 u128, Usize usize);
+
+impl Edges for f32 {
+    fn edges() -> Vec<f32> {
+        vec![
+            0.0, -0.0, 0.9, 1.5, -1.5, 255.5, 256.0, -129.0, 3e9, 1e20,
+            f32::MIN_POSITIVE, f32::MAX, f32::MIN, f32::INFINITY, f32::NEG_INFINITY, f32::NAN,
+        ]
+    }
+
+    fn prim(self) -> Prim {
+        Prim::F32(self)
+    }
+}
+
+impl Edges for f64 {
+    fn edges() -> Vec<f64> {
+        vec![
+            0.0, -0.0, 0.9, 1.5, -1.5, 255.5, 256.0, -129.0, 3e9, 1e20, 16_777_217.0, 0.1, 1e300, 1e-320,
+            f64::MIN_POSITIVE, f64::MAX, f64::MIN, f64::INFINITY, f64::NEG_INFINITY, f64::NAN,
+        ]
+    }
+
+    fn prim(self) -> Prim {
+        Prim::F64(self)
+    }
+}
+
+impl Edges for bool {
+    fn edges() -> Vec<bool> {
+        vec![false, true]
+    }
+
+    fn prim(self) -> Prim {
+        Prim::Bool(self)
+    }
+}
+
+impl Edges for char {
+    fn edges() -> Vec<char> {
+        vec!['\0', 'A', '\u{7F}', 'ÿ', 'Ā', '\u{FFFF}', '😻', char::MAX]
+    }
+
+    fn prim(self) -> Prim {
+        Prim::Char(self)
+    }
+}
+
+// asks the compiler whether `T: From<S>` and `T: TryFrom<S>`. for concrete types, method lookup
+// tries `Probe` before `&Probe`, so the impls on `Probe` win when their bounds hold and the
+// fallbacks on `&Probe` are only picked when they don't
+struct Probe<S, T>(PhantomData<(S, T)>);
+
+trait ViaFrom<S> {
+    fn convert(&self, value: S) -> Option<String>;
+}
+
+impl<S, T: From<S> + fmt::Debug> ViaFrom<S> for Probe<S, T> {
+    fn convert(&self, value: S) -> Option<String> {
+        Some(format!("{:?}", T::from(value)))
+    }
+}
+
+trait NoFrom<S> {
+    fn convert(&self, _value: S) -> Option<String> {
+        None
+    }
+}
+
+impl<S, T> NoFrom<S> for &Probe<S, T> {}
+
+trait ViaTryFrom<S> {
+    fn try_convert(&self, value: S) -> Option<String>;
+}
+
+impl<S, T: TryFrom<S> + fmt::Debug> ViaTryFrom<S> for Probe<S, T>
+where
+    T::Error: fmt::Debug,
+{
+    fn try_convert(&self, value: S) -> Option<String> {
+        Some(format!("{:?}", T::try_from(value)))
+    }
+}
+
+trait NoTryFrom<S> {
+    fn try_convert(&self, _value: S) -> Option<String> {
+        None
+    }
+}
+
+impl<S, T> NoTryFrom<S> for &Probe<S, T> {}
+
+pub struct Row {
+    pub input: String,
+    // None when the cast doesn't compile
+    pub cast: Option<String>,
+    pub from: Option<String>,
+    pub try_from: Option<String>,
+}
+
+pub struct Pair {
+    pub source: &'static str,
+    pub target: &'static str,
+    pub rows: Vec<Row>,
+}
+
+impl Pair {
+    pub fn casts(&self) -> bool {
+        self.rows[0].cast.is_some()
+    }
+
+    pub fn has_from(&self) -> bool {
+        self.rows[0].from.is_some()
+    }
+
+    pub fn has_try_from(&self) -> bool {
+        self.rows[0].try_from.is_some()
+    }
+
+    // what `as` does overall, in a few words
+    pub fn summary(&self) -> String {
+        if !self.casts() {
+            return cast_error(self.source, self.target);
+        }
+        let lossless = self.rows.iter().all(|row| match (&row.cast, &row.try_from) {
+            (_, Some(result)) => result.starts_with("Ok"),
+            _ => false,
+        });
+        if self.source == self.target {
+            String::from("does nothing")
+        } else if self.has_from() {
+            String::from("always lossless")
+        } else if lossless {
+            String::from("lossless on this platform, but only TryFrom promises it")
+        } else {
+            String::from("can lose information, see the values")
+        }
+    }
+
+    // the traits that could be used instead, like `From + TryFrom`
+    fn traits(&self) -> String {
+        match (self.has_from(), self.has_try_from()) {
+            (true, _) => String::from("From (so TryFrom too, and it never fails)"),
+            (false, true) => String::from("TryFrom"),
+            (false, false) => String::from("neither From nor TryFrom"),
+        }
+    }
+
+    pub fn markdown(&self) -> String {
+        let mut out = format!("### `{}` as `{}`\n\n", self.source, self.target);
+        out.push_str(&format!("`as`: {}. Traits: {}.\n\n", self.summary(), self.traits()));
+        if !self.casts() && !self.has_try_from() {
+            return out;
+        }
+        out.push_str(&format!("| value | `as {}` | `From` | `TryFrom` |\n|---|---|---|---|\n", self.target));
+        for row in &self.rows {
+            let cell = |value: &Option<String>| value.clone().unwrap_or_else(|| String::from("-")).replace('|', "\\|");
+            out.push_str(&format!("| {} | {} | {} | {} |\n", row.input.replace('|', "\\|"), cell(&row.cast), cell(&row.from), cell(&row.try_from)));
+        }
+        out
+    }
+}
+
+impl fmt::Display for Pair {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{} as {}: {}", self.source, self.target, self.summary())?;
+        writeln!(f, "    traits: {}", self.traits())?;
+        if !self.casts() && !self.has_try_from() {
+            return Ok(());
+        }
+
+        let dash = || String::from("-");
+        let cells: Vec<[String; 4]> = self
+            .rows
+            .iter()
+            .map(|row| {
+                [
+                    row.input.clone(),
+                    row.cast.clone().unwrap_or_else(dash),
+                    row.from.clone().unwrap_or_else(dash),
+                    row.try_from.clone().unwrap_or_else(dash),
+                ]
+            })
+            .collect();
+        let header = [String::from("value"), format!("as {}", self.target), String::from("From"), String::from("TryFrom")];
+        let widths: Vec<usize> = (0..4)
+            .map(|i| cells.iter().chain(Some(&header)).map(|row| row[i].chars().count()).max().unwrap_or(0))
+            .collect();
+        for row in Some(&header).into_iter().chain(cells.iter()) {
+            let padded: Vec<String> = row.iter().zip(&widths).map(|(cell, width)| format!("{:<1$}", cell, width)).collect();
+            writeln!(f, "    {}", padded.join("  ").trim_end())?;
+        }
+        Ok(())
+    }
+}
+
+fn pair_of<S: Edges>(source: &'static str, target: &'static str, convert: impl Fn(S) -> (Option<String>, Option<String>)) -> Pair {
+    let rows = S::edges()
+        .into_iter()
+        .map(|value| {
+            let (from, try_from) = convert(value);
+            Row {
+                input: value.prim().to_string(),
+                cast: cast(value.prim(), target).map(|result| result.to_string()),
+                from,
+                try_from,
+            }
+        })
+        .collect();
+    Pair { source, target, rows }
+}
+
+macro_rules! pair_row {
+    ($s:ty, $target:expr, [$($t:ty),*]) => {
+        match $target {
+            $(stringify!($t) => Some(pair_of::<$s>(stringify!($s), stringify!($t), |value| {
+                let probe = Probe::<$s, $t>(PhantomData);
+                ((&probe).convert(value), (&probe).try_convert(value))
+            })),)*
+            _ => None,
+        }
+    };
+}
+
+macro_rules! pairs {
+    ($source:expr, $target:expr, [$($s:ty),*], $targets:tt) => {
+        match $source {
+            $(stringify!($s) => pair_row!($s, $target, $targets),)*
+            _ => None,
+        }
+    };
+}
+
+// everything about `source as target`, or None if either isn't a primitive type
+pub fn pair(source: &str, target: &str) -> Option<Pair> {
+    pairs!(
+        source,
+        target,
+        [i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char],
+        [i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64, bool, char]
+    )
+}
+
+// one cell of the overview: `as` if the cast compiles, plus `F` for From or `T` for TryFrom
+fn cell(pair: &Pair) -> String {
+    let mut cell = String::from(if pair.casts() { "as" } else { "" });
+    if pair.has_from() {
+        cell.push_str(" F");
+    } else if pair.has_try_from() {
+        cell.push_str(" T");
+    }
+    match cell.trim() {
+        "" => String::from("-"),
+        cell => cell.to_string(),
+    }
+}
+
+const LEGEND: &str = "as: the cast compiles, F: From is implemented, T: only TryFrom is implemented, -: no conversion at all";
+
+// every source type down the side, every target along the top
+pub fn matrix() -> String {
+    let mut out = format!("{:<6}", "");
+    for target in TYPES.iter() {
+        out.push_str(&format!("{:<6}", target));
+    }
+    out = out.trim_end().to_string();
+    out.push('\n');
+    for source in TYPES.iter() {
+        let mut line = format!("{:<6}", source);
+        for target in TYPES.iter() {
+            line.push_str(&format!("{:<6}", cell(&pair(source, target).expect("both are in TYPES"))));
+        }
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out.push_str(LEGEND);
+    out
+}
+
+pub fn matrix_markdown() -> String {
+    let mut out = format!("| from \\ to | {} |\n", TYPES.join(" | "));
+    out.push_str(&format!("|---|{}\n", "---|".repeat(TYPES.len())));
+    for source in TYPES.iter() {
+        let cells: Vec<String> = TYPES.iter().map(|target| cell(&pair(source, target).expect("both are in TYPES"))).collect();
+        out.push_str(&format!("| **{}** | {} |\n", source, cells.join(" | ")));
+    }
+    out.push_str(&format!("\n{}\n", LEGEND));
+    out
+}
+
+// the conversions out of one type, one line each
+fn from_type(source: &str, markdown: bool) -> String {
+    let pairs: Vec<Pair> = TYPES.iter().map(|target| pair(source, target).expect("both are in TYPES")).collect();
+    if markdown {
+        let mut out = format!("| `{}` to | `as` | traits |\n|---|---|---|\n", source);
+        for pair in &pairs {
+            out.push_str(&format!("| `{}` | {} | {} |\n", pair.target, pair.summary(), pair.traits()));
+        }
+        return out;
+    }
+    let mut out = String::new();
+    for pair in &pairs {
+        out.push_str(&format!("{:<6} {}\n       traits: {}\n", pair.target, pair.summary(), pair.traits()));
+    }
+    out
+}
+
+// answers a query: `matrix`, a type (`i32`) for every conversion out of it, a pair (`f64 u8`)
+// for the values, or `all` for every pair. `markdown` picks the output format
+pub fn evaluate(line: &str, markdown: bool) -> Result<String, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let unknown = |ty: &str| format!("unknown type `{}`, try one of: {}", ty, TYPES.join(" "));
+
+    match words.as_slice() {
+        ["matrix"] if markdown => Ok(matrix_markdown()),
+        ["matrix"] => Ok(matrix()),
+        ["all"] => {
+            let mut out = String::new();
+            for source in TYPES.iter() {
+                for target in TYPES.iter() {
+                    let pair = pair(source, target).expect("both are in TYPES");
+                    out.push_str(&if markdown { pair.markdown() } else { pair.to_string() });
+                    out.push('\n');
+                }
+            }
+            Ok(out)
+        }
+        [source] => {
+            pair(source, source).ok_or_else(|| unknown(source))?;
+            Ok(from_type(source, markdown))
+        }
+        [source, target] | [source, "as", target] => {
+            pair(source, source).ok_or_else(|| unknown(source))?;
+            let pair = pair(source, target).ok_or_else(|| unknown(target))?;
+            Ok(if markdown { pair.markdown() } else { pair.to_string() })
+        }
+        _ => Err(String::from("expected `matrix`, `all`, a type like `i32`, or a pair like `f64 u8`")),
+    }
+}
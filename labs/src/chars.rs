@@ -0,0 +1,260 @@
+// takes a string apart char by char, and then grapheme by grapheme. ch3/varsandmutability.rs
+// says a `char` is a unicode scalar value and that "a character isn't really a concept in
+// Unicode". this shows what that means: one thing on screen can be several `char`s, and every
+// `char` can be several bytes
+
+use std::fmt;
+use unicode_general_category::{get_general_category, GeneralCategory};
+use unicode_segmentation::UnicodeSegmentation;
+
+const ZERO_WIDTH_JOINER: char = '\u{200D}';
+
+// the two-letter abbreviation unicode uses, and what it means
+fn category(c: char) -> (&'static str, &'static str) {
+    match get_general_category(c) {
+        GeneralCategory::UppercaseLetter => ("Lu", "uppercase letter"),
+        GeneralCategory::LowercaseLetter => ("Ll", "lowercase letter"),
+        GeneralCategory::TitlecaseLetter => ("Lt", "titlecase letter"),
+        GeneralCategory::ModifierLetter => ("Lm", "modifier letter"),
+        GeneralCategory::OtherLetter => ("Lo", "other letter"),
+        GeneralCategory::NonspacingMark => ("Mn", "nonspacing mark"),
+        GeneralCategory::SpacingMark => ("Mc", "spacing mark"),
+        GeneralCategory::EnclosingMark => ("Me", "enclosing mark"),
+        GeneralCategory::DecimalNumber => ("Nd", "decimal number"),
+        GeneralCategory::LetterNumber => ("Nl", "letter number"),
+        GeneralCategory::OtherNumber => ("No", "other number"),
+        GeneralCategory::ConnectorPunctuation => ("Pc", "connector punctuation"),
+        GeneralCategory::DashPunctuation => ("Pd", "dash punctuation"),
+        GeneralCategory::OpenPunctuation => ("Ps", "open punctuation"),
+        GeneralCategory::ClosePunctuation => ("Pe", "close punctuation"),
+        GeneralCategory::InitialPunctuation => ("Pi", "initial punctuation"),
+        GeneralCategory::FinalPunctuation => ("Pf", "final punctuation"),
+        GeneralCategory::OtherPunctuation => ("Po", "other punctuation"),
+        GeneralCategory::MathSymbol => ("Sm", "math symbol"),
+        GeneralCategory::CurrencySymbol => ("Sc", "currency symbol"),
+        GeneralCategory::ModifierSymbol => ("Sk", "modifier symbol"),
+        GeneralCategory::OtherSymbol => ("So", "other symbol"),
+        GeneralCategory::SpaceSeparator => ("Zs", "space separator"),
+        GeneralCategory::LineSeparator => ("Zl", "line separator"),
+        GeneralCategory::ParagraphSeparator => ("Zp", "paragraph separator"),
+        GeneralCategory::Control => ("Cc", "control"),
+        GeneralCategory::Format => ("Cf", "format"),
+        GeneralCategory::Surrogate => ("Cs", "surrogate"),
+        GeneralCategory::PrivateUse => ("Co", "private use"),
+        _ => ("Cn", "unassigned"),
+    }
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+fn is_skin_tone(c: char) -> bool {
+    ('\u{1F3FB}'..='\u{1F3FF}').contains(&c)
+}
+
+fn is_variation_selector(c: char) -> bool {
+    ('\u{FE00}'..='\u{FE0F}').contains(&c)
+}
+
+// the letter a regional indicator stands for, so 🇯 is J
+fn regional_letter(c: char) -> char {
+    (b'A' + (c as u32 - 0x1F1E6) as u8) as char
+}
+
+pub struct CharInfo {
+    // where the char starts in the string, in bytes. this is what you'd slice on
+    pub offset: usize,
+    pub c: char,
+    pub utf8: Vec<u8>,
+    pub utf16: Vec<u16>,
+    pub category: (&'static str, &'static str),
+    pub name: String,
+}
+
+impl CharInfo {
+    fn new(offset: usize, c: char) -> CharInfo {
+        let mut utf8 = [0; 4];
+        let mut utf16 = [0; 2];
+        let name = match unicode_names2::name(c) {
+            Some(name) => name.to_string(),
+            None if c.is_control() => String::from("<control>"),
+            None => String::from("<no name>"),
+        };
+
+        CharInfo {
+            offset,
+            c,
+            utf8: c.encode_utf8(&mut utf8).as_bytes().to_vec(),
+            utf16: c.encode_utf16(&mut utf16).to_vec(),
+            category: category(c),
+            name,
+        }
+    }
+
+    // marks, joiners and control chars are printed escaped, otherwise they glue themselves onto
+    // whatever is next to them on the terminal
+    pub fn shown(&self) -> String {
+        match self.category.0 {
+            "Mn" | "Mc" | "Me" | "Cc" | "Cf" | "Zl" | "Zp" | "Cn" => self.c.escape_unicode().to_string(),
+            _ if is_skin_tone(self.c) || is_variation_selector(self.c) => self.c.escape_unicode().to_string(),
+            _ => self.c.to_string(),
+        }
+    }
+}
+
+pub struct Grapheme {
+    pub offset: usize,
+    pub text: String,
+    pub chars: Vec<char>,
+}
+
+impl Grapheme {
+    // why these chars ended up as one grapheme, when there's more than one of them
+    pub fn explain(&self) -> Option<String> {
+        if self.chars.len() < 2 {
+            return None;
+        }
+
+        let names = |filter: &dyn Fn(char) -> bool| {
+            self.chars
+                .iter()
+                .filter(|c| filter(**c))
+                .map(|c| CharInfo::new(0, *c).name)
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        let mut reasons = Vec::new();
+        if self.chars.iter().all(|c| is_regional_indicator(*c)) {
+            let letters: String = self.chars.iter().map(|c| regional_letter(*c)).collect();
+            reasons.push(format!("a pair of regional indicators is a flag, this one is the country code {}", letters));
+        }
+        if self.chars.contains(&ZERO_WIDTH_JOINER) {
+            let parts = self.chars.iter().filter(|c| **c != ZERO_WIDTH_JOINER && !is_variation_selector(**c)).count();
+            reasons.push(format!("ZERO WIDTH JOINER glues {} emoji into a single picture", parts));
+        }
+        if self.chars.iter().any(|c| is_skin_tone(*c)) {
+            reasons.push(format!("{} changes the skin tone of the emoji before it", names(&is_skin_tone)));
+        }
+        if self.chars.iter().any(|c| is_variation_selector(*c)) {
+            reasons.push(format!("{} picks how the char before it is drawn (text or emoji)", names(&is_variation_selector)));
+        }
+        let is_mark = |c: char| matches!(category(c).0, "Mn" | "Mc" | "Me") && !is_variation_selector(c) && c != ZERO_WIDTH_JOINER;
+        if self.chars.iter().any(|c| is_mark(*c)) {
+            let verb = if self.chars.iter().filter(|c| is_mark(**c)).count() == 1 { "combines" } else { "combine" };
+            reasons.push(format!("{} {} with the char before instead of standing alone", names(&is_mark), verb));
+        }
+        if self.text == "\r\n" {
+            reasons.push(String::from("CR LF counts as one line break"));
+        }
+        if reasons.is_empty() {
+            reasons.push(String::from("unicode's segmentation rules keep these together"));
+        }
+        Some(reasons.join("; "))
+    }
+}
+
+pub struct Report {
+    pub text: String,
+    pub chars: Vec<CharInfo>,
+    pub graphemes: Vec<Grapheme>,
+}
+
+pub fn inspect(text: &str) -> Report {
+    Report {
+        text: text.to_string(),
+        chars: text.char_indices().map(|(offset, c)| CharInfo::new(offset, c)).collect(),
+        graphemes: text
+            .grapheme_indices(true)
+            .map(|(offset, g)| Grapheme { offset, text: g.to_string(), chars: g.chars().collect() })
+            .collect(),
+    }
+}
+
+fn hex<T: fmt::UpperHex>(units: &[T], width: usize) -> String {
+    units.iter().map(|u| format!("{:01$X}", u, width)).collect::<Vec<String>>().join(" ")
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{:?}", self.text)?;
+        writeln!(f, "    {:<6}  {:<9}  {:<11}  {:<9}  {:<24}  {:<40}  char", "byte", "code", "utf-8", "utf-16", "category", "name")?;
+        for info in &self.chars {
+            let category = format!("{} {}", info.category.0, info.category.1);
+            writeln!(
+                f,
+                "    {:<6}  U+{:<7}  {:<11}  {:<9}  {:<24}  {:<40}  {}",
+                info.offset,
+                format!("{:04X}", info.c as u32),
+                hex(&info.utf8, 2),
+                hex(&info.utf16, 4),
+                category,
+                info.name,
+                info.shown()
+            )?;
+        }
+
+        writeln!(f)?;
+        writeln!(f, "    graphemes (what a person would call a character)")?;
+        for grapheme in &self.graphemes {
+            let code_points: Vec<String> = grapheme.chars.iter().map(|c| format!("U+{:04X}", *c as u32)).collect();
+            let shown = if grapheme.text.chars().all(char::is_control) { grapheme.text.escape_debug().to_string() } else { grapheme.text.clone() };
+            writeln!(f, "    {:<6}  {:<40}  {}", grapheme.offset, code_points.join(" "), shown)?;
+            if let Some(reason) = grapheme.explain() {
+                writeln!(f, "    {:<6}  {} chars, one grapheme: {}", "", grapheme.chars.len(), reason)?;
+            }
+        }
+
+        writeln!(f)?;
+        writeln!(f, "    {:<26}  {}", ".len() (utf-8 bytes)", self.text.len())?;
+        writeln!(f, "    {:<26}  {}", ".encode_utf16().count()", self.text.encode_utf16().count())?;
+        writeln!(f, "    {:<26}  {}", ".chars().count()", self.chars.len())?;
+        writeln!(f, "    {:<26}  {}", ".graphemes(true).count()", self.graphemes.len())?;
+        Ok(())
+    }
+}
+
+// strings to try when there's nothing else to look at: the chars from ch3, then each of the
+// ways a single grapheme ends up being more than one char
+pub const EXAMPLES: [&str; 7] = [
+    "zℤ😻",
+    "café",
+    "cafe\u{301}",
+    "👨\u{200D}👩\u{200D}👧\u{200D}👦",
+    "🇯🇵🇳🇿",
+    "👍🏽",
+    "❤\u{FE0F}",
+];
+
+// lets you type chars that are hard to type or invisible, the same way a rust string literal
+// would: `\u{301}`, `\n`, `\t`, `\r`, `\0` and `\\`
+pub fn unescape(line: &str) -> Result<String, String> {
+    let mut text = String::new();
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            text.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => text.push('\n'),
+            Some('t') => text.push('\t'),
+            Some('r') => text.push('\r'),
+            Some('0') => text.push('\0'),
+            Some('\\') => text.push('\\'),
+            Some('u') => {
+                let rest: String = chars.by_ref().take_while(|c| *c != '}').collect();
+                let digits = rest.strip_prefix('{').ok_or("expected `{` after `\\u`")?;
+                let value = u32::from_str_radix(&digits.replace('_', ""), 16)
+                    .map_err(|_| format!("invalid unicode escape `\\u{}}}`", rest))?;
+                let c = std::char::from_u32(value)
+                    .ok_or(format!("invalid unicode character escape: `\\u{}}}` isn't a unicode scalar value", rest))?;
+                text.push(c);
+            }
+            Some(other) => return Err(format!("unknown character escape: `\\{}`", other)),
+            None => text.push('\\'),
+        }
+    }
+    Ok(text)
+}
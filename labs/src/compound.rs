@@ -0,0 +1,365 @@
+// the compound types from ch3 for the tracer in bindings.rs: tuples, arrays and `[3; 5]`,
+// taking them apart with a `let` pattern, `tup.0`, and `a[i]` checked the way rustc and then the
+// program check it. with layouts on, each new value also gets where its parts sit in memory,
+// measured by the layout module
+
+use crate::bindings::{error, mismatch, settle, Access, Expr, Pattern, Problem, Trace, Ty, Value};
+use crate::layout::{self, Layout};
+
+impl Trace {
+    // evaluates `index` for an array of type `array`, checking it the way rustc and then the
+    // program would
+    fn index(&mut self, array: &Ty, index: &Expr) -> Result<usize, Problem> {
+        let (element, length) = match array {
+            Ty::Array(element, length) => (element, *length),
+            _ => return Err(error("E0608", format!("cannot index into a value of type `{}`", array), Vec::new())),
+        };
+        // evaluated as a usize from the start, so `a[3 - 4]` overflows and only `a[-1]` is E0600
+        let (value, ty) = self.eval_as(index, &Ty::Int("usize"))?;
+        let value = match (value, ty.unify(&Ty::Int("usize"))) {
+            (Value::Int(v), Some(_)) => v,
+            _ => {
+                return Err(error("E0277", format!("the type `[{}]` cannot be indexed by `{}`", element, ty), vec![
+                    String::from("note: slice indices are of type `usize` or ranges of `usize`"),
+                ]))
+            }
+        };
+        if value >= length as i128 {
+            if self.known(index) {
+                return Err(Problem::Error(None, String::from("this operation will panic at runtime"), vec![
+                    format!("index out of bounds: the length is {} but the index is {}", length, value),
+                    String::from("note: `#[deny(unconditional_panic)]` on by default"),
+                    String::from("note: the compiler can see the index, so it refuses to build something that always panics"),
+                ]));
+            }
+            self.note(String::from("the index comes from `.parse()`, so it isn't known until the program runs and the bounds check happens then"));
+            return Err(Problem::Panic(format!("index out of bounds: the len is {} but the index is {}", length, value)));
+        }
+        Ok(value as usize)
+    }
+
+    // tuples, arrays, `[3; 5]`, `tup.0` and `a[i]`, with `expected` as in `eval_as`
+    pub(crate) fn compound(&mut self, expr: &Expr, expected: &Ty) -> Result<(Value, Ty), Problem> {
+        match expr {
+            Expr::Tuple(exprs) => {
+                let (mut values, mut types) = (Vec::new(), Vec::new());
+                for (i, expr) in exprs.iter().enumerate() {
+                    let expected = match expected {
+                        Ty::Tuple(fields) if fields.len() == exprs.len() => &fields[i],
+                        _ => &Ty::Unknown,
+                    };
+                    let (value, ty) = self.eval_as(expr, expected)?;
+                    values.push(value);
+                    types.push(ty);
+                }
+                Ok((Value::Tuple(values), Ty::Tuple(types)))
+            }
+            Expr::Array(exprs) => {
+                let expected = match expected {
+                    Ty::Array(element, _) => &**element,
+                    _ => &Ty::Unknown,
+                };
+                let noted = self.noted();
+                let mut evaluated = Vec::new();
+                for expr in exprs {
+                    evaluated.push(self.eval_as(expr, expected)?);
+                }
+                let mut element = evaluated[0].1.clone();
+                for (_, ty) in &evaluated[1..] {
+                    element = element.unify(ty).ok_or_else(|| mismatch(&element, ty))?;
+                }
+                // in `[300, 1u8]` the elements before the `1u8` find out their type late
+                if evaluated.iter().any(|(_, ty)| *ty != element) {
+                    self.forget(noted);
+                    evaluated.clear();
+                    for expr in exprs {
+                        evaluated.push(self.eval_as(expr, &element)?);
+                    }
+                }
+                let mut values = Vec::new();
+                for (value, ty) in evaluated {
+                    values.push(settle(value, &ty, &element)?.0);
+                }
+                Ok((Value::Array(values), Ty::Array(Box::new(element), exprs.len())))
+            }
+            Expr::Repeat(value, count) => {
+                if !self.constant(count) {
+                    return Err(error("E0435", String::from("attempt to use a non-constant value in a constant"), vec![
+                        String::from("note: the length is part of an array's type, so it has to be known when compiling"),
+                        String::from("help: consider using `const` instead of `let`"),
+                    ]));
+                }
+                let (count, count_ty) = self.eval_as(count, &Ty::Int("usize"))?;
+                let count = match settle(count, &count_ty, &Ty::Int("usize"))? {
+                    (Value::Int(n), _) if n <= 4096 => n as usize,
+                    _ => return Err(Problem::Unsupported(String::from("arrays longer than 4096"))),
+                };
+                let expected = match expected {
+                    Ty::Array(element, _) => &**element,
+                    _ => &Ty::Unknown,
+                };
+                let (value, ty) = self.eval_as(value, expected)?;
+                Ok((Value::Array(vec![value; count]), Ty::Array(Box::new(ty), count)))
+            }
+            Expr::Field(tuple, field) => {
+                let (value, ty) = self.eval(tuple)?;
+                match (value, ty) {
+                    (Value::Tuple(mut values), Ty::Tuple(mut types)) if *field < values.len() => {
+                        Ok((values.swap_remove(*field), types.swap_remove(*field)))
+                    }
+                    (_, ty) => {
+                        let mut notes = Vec::new();
+                        if let Ty::Tuple(types) = &ty {
+                            let fields: Vec<String> = (0..types.len()).map(|i| format!("`{}`", i)).collect();
+                            notes.push(format!("note: available fields are: {}", fields.join(", ")));
+                        }
+                        Err(error("E0609", format!("no field `{}` on type `{}`", field, ty), notes))
+                    }
+                }
+            }
+            Expr::Index(array, index) => {
+                let (value, ty) = self.eval(array)?;
+                let i = self.index(&ty, index)?;
+                match (value, ty) {
+                    (Value::Array(mut values), Ty::Array(element, _)) => Ok((values.swap_remove(i), *element)),
+                    _ => unreachable!("index() checks for an array"),
+                }
+            }
+            _ => unreachable!("only called on the compound expressions"),
+        }
+    }
+
+    // the part of a value `path` leads to, for an assignment like `a[1].0 = 5`: its type, its
+    // value, where it is at each step, and whether the compiler can see every index
+    pub(crate) fn part(&mut self, ty: Ty, value: Value, path: &[Access]) -> Result<(Ty, Value, Vec<usize>, bool), Problem> {
+        let (mut target_ty, mut target) = (ty, value);
+        let mut positions = Vec::new();
+        let mut known = true;
+        for access in path {
+            let i = match access {
+                Access::Field(field) => match &target_ty {
+                    Ty::Tuple(types) if *field < types.len() => *field,
+                    _ => return Err(error("E0609", format!("no field `{}` on type `{}`", field, target_ty), Vec::new())),
+                },
+                Access::Index(expr) => {
+                    known &= self.known(expr);
+                    self.index(&target_ty, expr)?
+                }
+            };
+            target_ty = match target_ty {
+                Ty::Tuple(mut types) => types.swap_remove(i),
+                Ty::Array(element, _) => *element,
+                _ => unreachable!("checked above"),
+            };
+            target = match target {
+                Value::Tuple(mut values) | Value::Array(mut values) => values.swap_remove(i),
+                _ => unreachable!("the value matches its type"),
+            };
+            positions.push(i);
+        }
+        Ok((target_ty, target, positions, known))
+    }
+}
+
+// puts `new` where `positions` lead inside `value`
+pub(crate) fn replace(value: &mut Value, positions: &[usize], new: Value) {
+    let mut slot = value;
+    for i in positions {
+        slot = match slot {
+            Value::Tuple(values) | Value::Array(values) => &mut values[*i],
+            _ => unreachable!("the value matches its type"),
+        };
+    }
+    *slot = new;
+}
+
+// splits a value up the way a `let` pattern does, into (name, mutable, type, value)
+pub(crate) fn destructure(pattern: &Pattern, value: Value, ty: Ty, parts: &mut Vec<(String, bool, Ty, Value)>) -> Result<(), Problem> {
+    match (pattern, value, ty) {
+        (Pattern::Name { mutable, name }, value, ty) => parts.push((name.clone(), *mutable, ty, value)),
+        (Pattern::Wildcard, ..) => {}
+        (Pattern::Tuple(patterns), Value::Tuple(values), Ty::Tuple(types)) => {
+            if patterns.len() != values.len() {
+                return Err(error("E0308", String::from("mismatched types"), vec![format!(
+                    "expected a tuple with {} elements, found one with {} elements",
+                    values.len(),
+                    patterns.len()
+                )]));
+            }
+            for ((pattern, value), ty) in patterns.iter().zip(values).zip(types) {
+                destructure(pattern, value, ty, parts)?;
+            }
+        }
+        (Pattern::Array(patterns), Value::Array(values), Ty::Array(element, length)) => {
+            if patterns.len() != length {
+                return Err(error("E0527", format!("pattern requires {} elements but array has {}", patterns.len(), length), Vec::new()));
+            }
+            for (pattern, value) in patterns.iter().zip(values) {
+                destructure(pattern, value, (*element).clone(), parts)?;
+            }
+        }
+        (Pattern::Tuple(patterns), _, ty) => {
+            return Err(error("E0308", String::from("mismatched types"), vec![format!("expected `{}`, found a tuple with {} elements", ty, patterns.len())]));
+        }
+        (Pattern::Array(_), _, ty) => {
+            return Err(error("E0529", format!("expected an array or slice, found `{}`", ty), Vec::new()));
+        }
+    }
+    Ok(())
+}
+
+// (size, alignment) in bytes, all measured: primitives and tuples of them by the layout module,
+// and an array is its element over and over, which rust promises. None for a tuple the layout
+// module doesn't measure
+// tuples the compiler was asked to lay out are measured. for any other tuple every field's size
+// is a multiple of its alignment, so rustc can put the most aligned fields first and leave no
+// padding except at the end, which is what it does
+fn size_align(ty: &Ty) -> Option<(usize, usize)> {
+    match ty.or_default() {
+        Ty::Tuple(fields) => match tuple_layout(&fields) {
+            Some(measured) => Some((measured.size, measured.align)),
+            None => {
+                let (mut total, mut widest) = (0usize, 1);
+                for field in &fields {
+                    let (size, align) = size_align(field)?;
+                    total += size;
+                    widest = widest.max(align);
+                }
+                Some((total.div_ceil(widest) * widest, widest))
+            }
+        },
+        Ty::Array(element, length) => size_align(&element).map(|(size, align)| (size * length, align)),
+        ty => layout::scalar(&ty.to_string()).map(|measured| (measured.size, measured.align)),
+    }
+}
+
+// whether every tuple inside was measured, so each field's offset is known
+fn mapped(ty: &Ty) -> bool {
+    match ty {
+        Ty::Tuple(fields) => tuple_layout(fields).is_some(),
+        Ty::Array(element, _) => mapped(element),
+        _ => true,
+    }
+}
+
+// why `ty` has no size, from the part of it that doesn't have one
+fn unmeasured(ty: &Ty) -> String {
+    match ty {
+        Ty::Tuple(fields) => fields.iter().find(|field| size_align(field).is_none()).map_or_else(String::new, unmeasured),
+        Ty::Array(element, _) => unmeasured(element),
+        Ty::ParseResult => String::from("the size of a `.parse()` result depends on the type it's told to produce"),
+        ty => format!("`{}` isn't known yet", ty),
+    }
+}
+
+fn tuple_layout(fields: &[Ty]) -> Option<Layout> {
+    let names: Vec<String> = fields.iter().map(|field| field.or_default().to_string()).collect();
+    layout::tuple_of(&names.iter().map(String::as_str).collect::<Vec<&str>>())
+}
+
+// one run of bytes: (start, end, which part, its type, what's in it)
+type Span = (usize, usize, String, String, String);
+
+fn spans(ty: &Ty, value: &Value, start: usize, path: String, out: &mut Vec<Span>) {
+    match (ty, value) {
+        (Ty::Tuple(fields), Value::Tuple(values)) => {
+            let measured = tuple_layout(fields).expect("layout() measures the whole type first");
+            for ((field, value), part) in fields.iter().zip(values).zip(&measured.parts) {
+                spans(field, value, start + part.offset, format!("{}{}", path, part.name), out);
+            }
+        }
+        (Ty::Array(element, _), Value::Array(values)) => {
+            let (size, _) = size_align(element).expect("layout() measures the whole type first");
+            for (i, value) in values.iter().enumerate() {
+                spans(element, value, start + i * size, format!("{}[{}]", path, i), out);
+            }
+        }
+        // a `&str` is a pointer to the text plus its length, the text itself is somewhere else
+        (Ty::Str, Value::Str(s)) => {
+            for word in layout::scalar("&str").expect("&str is measured").parts {
+                let what = if word.name == "pointer" { format!("pointer to the bytes of {:?}", s) } else { format!("length {}", s.len()) };
+                out.push((start + word.offset, start + word.offset + word.size, path.clone(), String::from("&str"), what));
+            }
+        }
+        _ => {
+            let (size, _) = size_align(ty).expect("layout() measures the whole type first");
+            out.push((start, start + size, path, ty.or_default().to_string(), value.to_string()));
+        }
+    }
+}
+
+// a few lines describing where each part of a value sits in memory
+pub(crate) fn layout(ty: &Ty, value: &Value) -> Vec<String> {
+    let (size, align) = match size_align(ty) {
+        Some(measured) => measured,
+        None => return vec![format!("`{}` isn't measured: {}", ty.or_default(), unmeasured(ty))],
+    };
+    let bytes = if size == 1 { "byte" } else { "bytes" };
+    let mut lines = vec![format!("{} {}, aligned to {}", size, bytes, align)];
+    // a number or a bool is just its bytes, there's nothing more to draw
+    if size == 0 || !matches!(ty, Ty::Tuple(_) | Ty::Array(..) | Ty::Str) {
+        return lines;
+    }
+    if !mapped(ty) {
+        lines.push(String::from(
+            "  rustc orders tuple fields most aligned first, so only the padding at the end is left. which field goes where is only measured for tuples of up to three numbers, bools, chars or &strs",
+        ));
+        return lines;
+    }
+
+    let mut parts = Vec::new();
+    spans(ty, value, 0, String::new(), &mut parts);
+    let used: usize = parts.iter().map(|(start, end, ..)| end - start).sum();
+    parts.sort_by_key(|(start, ..)| *start);
+
+    // fill the gaps with padding
+    let mut rows = Vec::new();
+    let mut offset = 0;
+    for part in parts {
+        if part.0 > offset {
+            rows.push((offset, part.0, String::new(), String::new(), String::from("padding")));
+        }
+        offset = part.1;
+        rows.push(part);
+    }
+    if offset < size {
+        rows.push((offset, size, String::new(), String::new(), String::from("padding")));
+    }
+
+    // a long array gets its middle cut out
+    let hidden = rows.len().saturating_sub(10);
+    if hidden > 0 {
+        rows.drain(6..6 + hidden + 1);
+    }
+    for (i, (start, end, path, ty, what)) in rows.into_iter().enumerate() {
+        if hidden > 0 && i == 6 {
+            lines.push(format!("  {:>13}  ({} more)", "...", hidden + 1));
+        }
+        lines.push(format!("  bytes {:>3}..{:<3}  {:<8}  {:<5}  {}", start, end, path, ty, what));
+    }
+    if used < size {
+        lines[0].push_str(&format!(", {} of them padding so every part sits at a multiple of its alignment", size - used));
+    }
+    if has_tuple(ty) {
+        lines.push(String::from(
+            "  rustc is free to reorder tuple fields, these are the offsets it picked when it built this program",
+        ));
+    }
+    lines
+}
+
+fn has_tuple(ty: &Ty) -> bool {
+    match ty {
+        Ty::Tuple(fields) => fields.len() > 1 || fields.iter().any(has_tuple),
+        Ty::Array(element, _) => has_tuple(element),
+        _ => false,
+    }
+}
+
+// the compound types section of ch3, then an index the compiler can see is out of bounds, then
+// one it can't
+pub const EXAMPLES: [&str; 3] = [
+    "let tup: (i32, f64, u8) = (500, 6.4, 1);\nlet (x, y, z) = tup;\nlet one = tup.2;\nlet a: [i32; 5] = [1, 2, 3, 4, 5];\nlet b = [3; 5];\nlet second = a[1];",
+    "let a = [1, 2, 3, 4, 5];\nlet index = 10;\nlet element = a[index];",
+    "let a = [1, 2, 3, 4, 5];\nlet index: usize = \"10\".trim().parse().expect(\"index entered was not a number\");\nlet element = a[index];",
+];
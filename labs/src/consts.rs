@@ -0,0 +1,919 @@
+// evaluates `const` items the way rustc does at compile time, like
+// `const THREE_HOURS_IN_SECONDS: u32 = 60 * 60 * 3;` from ch3/varsandmutability.rs. consts can
+// use each other in any order, so each one is evaluated when something first needs it. where a
+// running program would panic or wrap, a const is a hard error (E0080), a const that ends up
+// needing itself is a cycle (E0391), and a name that isn't SCREAMING_SNAKE_CASE gets the
+// `non_upper_case_globals` warning. only integer consts are evaluated
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::bindings::{self, Spanned, Token};
+use crate::literal::{self, integer_type, Literal, LiteralError, Value as LiteralValue, INTEGER_TYPES};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Shl,
+    Shr,
+    And,
+    Xor,
+    Or,
+}
+
+impl BinOp {
+    fn parse(s: &str) -> Option<BinOp> {
+        match s {
+            "+" => Some(BinOp::Add),
+            "-" => Some(BinOp::Sub),
+            "*" => Some(BinOp::Mul),
+            "/" => Some(BinOp::Div),
+            "%" => Some(BinOp::Rem),
+            "<<" => Some(BinOp::Shl),
+            ">>" => Some(BinOp::Shr),
+            "&" => Some(BinOp::And),
+            "^" => Some(BinOp::Xor),
+            "|" => Some(BinOp::Or),
+            _ => None,
+        }
+    }
+
+    fn symbol(self) -> &'static str {
+        match self {
+            BinOp::Add => "+",
+            BinOp::Sub => "-",
+            BinOp::Mul => "*",
+            BinOp::Div => "/",
+            BinOp::Rem => "%",
+            BinOp::Shl => "<<",
+            BinOp::Shr => ">>",
+            BinOp::And => "&",
+            BinOp::Xor => "^",
+            BinOp::Or => "|",
+        }
+    }
+
+    // from the operator precedence table in the reference, higher binds tighter. `as` sits
+    // above all of these and below the unary operators
+    fn precedence(self) -> u8 {
+        match self {
+            BinOp::Mul | BinOp::Div | BinOp::Rem => 6,
+            BinOp::Add | BinOp::Sub => 5,
+            BinOp::Shl | BinOp::Shr => 4,
+            BinOp::And => 3,
+            BinOp::Xor => 2,
+            BinOp::Or => 1,
+        }
+    }
+}
+
+const CAST_PRECEDENCE: u8 = 7;
+
+// what the binding tracer's tokenizer is given to split on, longest first so `<<` is one token
+const PUNCTUATION: [&str; 16] = ["<<", ">>", "+", "-", "*", "/", "%", "&", "^", "|", "!", "=", ";", ":", "(", ")"];
+
+enum Kind {
+    Literal(Literal),
+    Name(String),
+    // `u8::MAX`, `i64::MIN` or `u32::BITS`
+    Assoc(&'static str, &'static str),
+    Neg(Box<Expr>),
+    Not(Box<Expr>),
+    Binary(BinOp, Box<Expr>, Box<Expr>),
+    Cast(Box<Expr>, &'static str),
+}
+
+// `start..end` are byte offsets into the whole source, parentheses included
+struct Expr {
+    kind: Kind,
+    start: usize,
+    end: usize,
+}
+
+impl Expr {
+    fn error(&self, message: String) -> LiteralError {
+        LiteralError::new(message, self.start, self.end)
+    }
+
+    // the type the expression has no matter what it's used for, None if it's all unsuffixed
+    // literals that take whatever type they're needed as
+    fn fixed_type(&self, items: &Items) -> Option<&'static str> {
+        match &self.kind {
+            Kind::Literal(literal) if literal.suffixed => Some(type_name(&literal.ty)),
+            Kind::Literal(_) => None,
+            Kind::Name(name) => items.get(name).and_then(|item| item.ty),
+            Kind::Assoc(_, "BITS") => Some("u32"),
+            Kind::Assoc(ty, _) => Some(ty),
+            Kind::Neg(inner) | Kind::Not(inner) => inner.fixed_type(items),
+            Kind::Binary(BinOp::Shl, left, _) | Kind::Binary(BinOp::Shr, left, _) => left.fixed_type(items),
+            Kind::Binary(_, left, right) => left.fixed_type(items).or_else(|| right.fixed_type(items)),
+            Kind::Cast(_, ty) => Some(ty),
+        }
+    }
+
+    fn names<'a>(&'a self, out: &mut Vec<&'a str>) {
+        match &self.kind {
+            Kind::Name(name) => {
+                if !out.contains(&name.as_str()) {
+                    out.push(name);
+                }
+            }
+            Kind::Neg(inner) | Kind::Not(inner) | Kind::Cast(inner, _) => inner.names(out),
+            Kind::Binary(_, left, right) => {
+                left.names(out);
+                right.names(out);
+            }
+            Kind::Literal(_) | Kind::Assoc(..) => {}
+        }
+    }
+}
+
+// the literal module hands back an owned type name, everything here uses the static one
+fn type_name(name: &str) -> &'static str {
+    INTEGER_TYPES
+        .iter()
+        .chain(["f32", "f64"].iter())
+        .find(|ty| **ty == name)
+        .copied()
+        .unwrap_or("{unknown}")
+}
+
+struct Item {
+    name: String,
+    name_start: usize,
+    name_end: usize,
+    line: usize,
+    // the whole item, `const` to `;`
+    start: usize,
+    end: usize,
+    // None for a type this doesn't evaluate (or a missing one), which is in `type_text`
+    ty: Option<&'static str>,
+    type_text: String,
+    expr: Option<Expr>,
+}
+
+struct Items {
+    items: Vec<Item>,
+    index: HashMap<String, usize>,
+}
+
+impl Items {
+    fn get(&self, name: &str) -> Option<&Item> {
+        self.index.get(name).map(|&i| &self.items[i])
+    }
+}
+
+struct Parser<'a> {
+    tokens: &'a [Spanned],
+    source: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn peek(&self) -> Option<&'a Token> {
+        self.tokens.get(self.pos).map(|spanned| &spanned.token)
+    }
+
+    fn next(&mut self) -> Option<&'a Spanned> {
+        let spanned = self.tokens.get(self.pos);
+        self.pos += 1;
+        spanned
+    }
+
+    fn is_punct(&self, p: &str) -> bool {
+        matches!(self.peek(), Some(Token::Punct(q)) if *q == p)
+    }
+
+    fn is_word(&self, word: &str) -> bool {
+        matches!(self.peek(), Some(Token::Ident(w)) if w == word)
+    }
+
+    fn unexpected(&self, expected: &str) -> LiteralError {
+        match self.tokens.get(self.pos) {
+            Some(spanned) => LiteralError::new(format!("expected {}, found `{}`", expected, spanned.token), spanned.start, spanned.end)
+                .label(&format!("expected {}", expected)),
+            None => LiteralError::new(format!("expected {}, found end of input", expected), self.source.len(), self.source.len()),
+        }
+    }
+
+    fn expect(&mut self, p: &str) -> Result<&'a Spanned, LiteralError> {
+        if self.is_punct(p) {
+            Ok(self.next().expect("peeked the punctuation"))
+        } else {
+            Err(self.unexpected(&format!("`{}`", p)))
+        }
+    }
+
+    fn skip_past(&mut self, p: &str) {
+        while self.peek().is_some() && !self.is_punct(p) {
+            self.pos += 1;
+        }
+        self.pos += 1;
+    }
+
+    // `const NAME: TYPE = EXPR;`, with the parser on the `const`
+    fn item(&mut self) -> Result<Item, LiteralError> {
+        let keyword = self.next().expect("the caller found `const`");
+        let (name, name_start, name_end) = match self.next() {
+            Some(Spanned { token: Token::Ident(name), start, end, .. }) => (name.clone(), *start, *end),
+            _ => {
+                self.pos -= 1;
+                return Err(self.unexpected("identifier"));
+            }
+        };
+
+        if self.is_punct("=") {
+            return Err(LiteralError::new(String::from("missing type for `const` item"), name_start, name_end)
+                .note(format!("help: provide a type for the constant: `{}: i32`", name)));
+        }
+        self.expect(":")?;
+
+        // anything up to the `=` is the type, even the ones that aren't evaluated like `&str`
+        let type_start = self.pos;
+        while self.peek().is_some() && !self.is_punct("=") && !self.is_punct(";") {
+            self.pos += 1;
+        }
+        if self.pos == type_start {
+            return Err(self.unexpected("type"));
+        }
+        let type_text = self.source[self.tokens[type_start].start..self.tokens[self.pos - 1].end].to_string();
+        let ty = Some(type_text.as_str()).filter(|t| INTEGER_TYPES.contains(t)).map(type_name);
+        self.expect("=")?;
+
+        let expr = match ty {
+            Some(_) => Some(self.expression(0)?),
+            None => {
+                while self.peek().is_some() && !self.is_punct(";") {
+                    self.pos += 1;
+                }
+                None
+            }
+        };
+        let semicolon = self.expect(";")?;
+
+        Ok(Item {
+            name,
+            name_start,
+            name_end,
+            line: keyword.line,
+            start: keyword.start,
+            end: semicolon.end,
+            ty,
+            type_text,
+            expr,
+        })
+    }
+
+    fn expression(&mut self, min: u8) -> Result<Expr, LiteralError> {
+        let mut left = self.unary()?;
+        loop {
+            if self.is_word("as") && CAST_PRECEDENCE >= min {
+                self.next();
+                let (ty, end) = match self.next() {
+                    Some(Spanned { token: Token::Ident(ty), end, .. }) if INTEGER_TYPES.contains(&ty.as_str()) => (type_name(ty), *end),
+                    _ => {
+                        self.pos -= 1;
+                        return Err(self.unexpected("an integer type"));
+                    }
+                };
+                left = Expr { start: left.start, end, kind: Kind::Cast(Box::new(left), ty) };
+                continue;
+            }
+
+            let op = match self.peek() {
+                Some(Token::Punct(p)) => BinOp::parse(p),
+                _ => None,
+            };
+            match op {
+                Some(op) if op.precedence() >= min => {
+                    self.next();
+                    // one higher for the right side keeps `a - b - c` as `(a - b) - c`
+                    let right = self.expression(op.precedence() + 1)?;
+                    left = Expr { start: left.start, end: right.end, kind: Kind::Binary(op, Box::new(left), Box::new(right)) };
+                }
+                _ => return Ok(left),
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<Expr, LiteralError> {
+        if self.is_punct("-") || self.is_punct("!") {
+            let op = self.next().expect("peeked the operator");
+            let inner = Box::new(self.unary()?);
+            let end = inner.end;
+            let kind = if op.token == Token::Punct("-") { Kind::Neg(inner) } else { Kind::Not(inner) };
+            return Ok(Expr { kind, start: op.start, end });
+        }
+        self.primary()
+    }
+
+    fn primary(&mut self) -> Result<Expr, LiteralError> {
+        let spanned = match self.next() {
+            Some(spanned) => spanned,
+            None => return Err(self.unexpected("expression")),
+        };
+        let (start, end) = (spanned.start, spanned.end);
+
+        match &spanned.token {
+            Token::Number(text) => {
+                let literal = literal::parse(text).map_err(|mut error| {
+                    error.start += start;
+                    error.end += start;
+                    error
+                })?;
+                Ok(Expr { kind: Kind::Literal(literal), start, end })
+            }
+            Token::Ident(ty) if INTEGER_TYPES.contains(&ty.as_str()) && self.is_punct(":") => {
+                self.expect(":")?;
+                self.expect(":")?;
+                let (constant, end) = match self.next() {
+                    Some(Spanned { token: Token::Ident(constant), end, .. }) => (constant.as_str(), *end),
+                    _ => ("", 0),
+                };
+                let constant = match constant {
+                    "MAX" => "MAX",
+                    "MIN" => "MIN",
+                    "BITS" => "BITS",
+                    _ => {
+                        self.pos -= 1;
+                        return Err(self.unexpected("`MAX`, `MIN` or `BITS`"));
+                    }
+                };
+                Ok(Expr { kind: Kind::Assoc(type_name(ty), constant), start, end })
+            }
+            Token::Ident(name) => Ok(Expr { kind: Kind::Name(name.clone()), start, end }),
+            Token::Punct("(") => {
+                let mut inner = self.expression(0)?;
+                let close = self.expect(")")?;
+                inner.start = start;
+                inner.end = close.end;
+                Ok(inner)
+            }
+            _ => {
+                self.pos -= 1;
+                Err(self.unexpected("expression"))
+            }
+        }
+    }
+}
+
+trait Int: Copy + PartialEq + fmt::Display {
+    const NAME: &'static str;
+    const SIGNED: bool;
+    const MIN: Self;
+    const MAX: Self;
+
+    // values are kept as u128 bits, sign extended, so `bits as T` gets the value back
+    fn from_bits(bits: u128) -> Self;
+    fn to_bits(self) -> u128;
+    fn is_zero(self) -> bool;
+    fn checked(a: Self, op: BinOp, b: Self) -> Option<Self>;
+    // only called once the amount is known to be in range
+    fn shift(a: Self, op: BinOp, by: u32) -> Self;
+    fn negate(self) -> Option<Self>;
+    fn not(self) -> Self;
+}
+
+macro_rules! int {
+    ($($t:ident: $signed:expr),*) => {
+        $(
+            impl Int for $t {
+                const NAME: &'static str = stringify!($t);
+                const SIGNED: bool = $signed;
+                const MIN: $t = $t::MIN;
+                const MAX: $t = $t::MAX;
+
+                fn from_bits(bits: u128) -> $t {
+                    bits as $t
+                }
+
+                fn to_bits(self) -> u128 {
+                    self as i128 as u128
+                }
+
+                fn is_zero(self) -> bool {
+                    self == 0
+                }
+
+                fn checked(a: $t, op: BinOp, b: $t) -> Option<$t> {
+                    match op {
+                        BinOp::Add => a.checked_add(b),
+                        BinOp::Sub => a.checked_sub(b),
+                        BinOp::Mul => a.checked_mul(b),
+                        BinOp::Div => a.checked_div(b),
+                        BinOp::Rem => a.checked_rem(b),
+                        BinOp::And => Some(a & b),
+                        BinOp::Xor => Some(a ^ b),
+                        BinOp::Or => Some(a | b),
+                        BinOp::Shl | BinOp::Shr => unreachable!("shifts go through `shift`"),
+                    }
+                }
+
+                fn shift(a: $t, op: BinOp, by: u32) -> $t {
+                    match op {
+                        BinOp::Shl => a << by,
+                        _ => a >> by,
+                    }
+                }
+
+                fn negate(self) -> Option<$t> {
+                    self.checked_neg()
+                }
+
+                fn not(self) -> $t {
+                    !self
+                }
+            }
+        )*
+    };
+}
+
+int!(i8: true, i16: true, i32: true, i64: true, i128: true, isize: true);
+int!(u8: false, u16: false, u32: false, u64: false, u128: false, usize: false);
+
+macro_rules! with_int {
+    ($name:expr, $t:ident => $body:expr) => {
+        match $name {
+            "i8" => { type $t = i8; $body }
+            "i16" => { type $t = i16; $body }
+            "i32" => { type $t = i32; $body }
+            "i64" => { type $t = i64; $body }
+            "i128" => { type $t = i128; $body }
+            "isize" => { type $t = isize; $body }
+            "u8" => { type $t = u8; $body }
+            "u16" => { type $t = u16; $body }
+            "u32" => { type $t = u32; $body }
+            "u64" => { type $t = u64; $body }
+            "u128" => { type $t = u128; $body }
+            "usize" => { type $t = usize; $body }
+            other => unreachable!("`{}` isn't an integer type", other),
+        }
+    };
+}
+
+// how rustc writes a value in an E0080 message: `200_u8`, `-1_i32`, `u8::MAX` or `i8::MIN`
+fn operand<T: Int>(value: T) -> String {
+    if value == T::MAX {
+        format!("{}::MAX", T::NAME)
+    } else if T::SIGNED && value == T::MIN {
+        format!("{}::MIN", T::NAME)
+    } else {
+        format!("{}_{}", value, T::NAME)
+    }
+}
+
+fn arithmetic<T: Int>(a: u128, op: BinOp, b: u128) -> Result<u128, String> {
+    let (a, b) = (T::from_bits(a), T::from_bits(b));
+    match T::checked(a, op, b) {
+        Some(value) => Ok(value.to_bits()),
+        None if b.is_zero() && op == BinOp::Div => Err(format!("attempt to divide `{}` by zero", operand(a))),
+        None if b.is_zero() => Err(format!("attempt to calculate the remainder of `{}` with a divisor of zero", operand(a))),
+        None => Err(format!("attempt to compute `{} {} {}`, which would overflow", operand(a), op.symbol(), operand(b))),
+    }
+}
+
+fn display(ty: &str, bits: u128) -> String {
+    with_int!(ty, T => T::from_bits(bits).to_string())
+}
+
+enum Stop {
+    // the const's own expression doesn't compile or can't be evaluated
+    Error(LiteralError),
+    // a const it uses failed
+    Blocked(String),
+    // it's part of the cycle that started at this item
+    Cycle(usize),
+}
+
+impl From<LiteralError> for Stop {
+    fn from(error: LiteralError) -> Stop {
+        Stop::Error(error)
+    }
+}
+
+#[derive(Clone, Copy)]
+enum State {
+    Waiting,
+    Running,
+    Done(u128),
+    Failed,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Value(String),
+    Failed,
+    // not evaluated because this const failed
+    Blocked(String),
+    Cycle,
+    // a type this doesn't evaluate, or no type at all
+    Skipped,
+}
+
+pub struct Const {
+    pub name: String,
+    pub ty: String,
+    pub line: usize,
+    pub uses: Vec<String>,
+    pub outcome: Outcome,
+}
+
+struct Evaluator<'a> {
+    items: &'a Items,
+    states: Vec<State>,
+    outcomes: Vec<Outcome>,
+    // the consts being evaluated right now, innermost last
+    stack: Vec<usize>,
+    errors: Vec<LiteralError>,
+}
+
+impl<'a> Evaluator<'a> {
+    fn value(&mut self, index: usize) -> Result<u128, Stop> {
+        let items = self.items;
+        let item = &items.items[index];
+        match self.states[index] {
+            State::Done(bits) => return Ok(bits),
+            State::Failed => return Err(Stop::Blocked(item.name.clone())),
+            State::Running => {
+                self.errors.push(self.cycle(index));
+                return Err(Stop::Cycle(index));
+            }
+            State::Waiting => {}
+        }
+
+        let (ty, expr) = match (item.ty, &item.expr) {
+            (Some(ty), Some(expr)) => (ty, expr),
+            _ => return Err(Stop::Blocked(item.name.clone())),
+        };
+
+        self.states[index] = State::Running;
+        self.stack.push(index);
+        let result = self.eval(expr, ty);
+        self.stack.pop();
+
+        let (outcome, stop) = match result {
+            Ok(bits) => {
+                self.states[index] = State::Done(bits);
+                self.outcomes[index] = Outcome::Value(display(ty, bits));
+                return Ok(bits);
+            }
+            Err(Stop::Error(error)) => {
+                self.errors.push(error);
+                (Outcome::Failed, Stop::Blocked(item.name.clone()))
+            }
+            Err(Stop::Blocked(name)) => (Outcome::Blocked(name), Stop::Blocked(item.name.clone())),
+            // whatever is waiting on the start of the cycle is only blocked by it
+            Err(Stop::Cycle(start)) if start == index => (Outcome::Cycle, Stop::Blocked(item.name.clone())),
+            Err(Stop::Cycle(start)) => (Outcome::Cycle, Stop::Cycle(start)),
+        };
+        self.states[index] = State::Failed;
+        self.outcomes[index] = outcome;
+        Err(stop)
+    }
+
+    // E0391, with the chain of consts from `index` back round to itself
+    fn cycle(&self, index: usize) -> LiteralError {
+        let names: Vec<&str> = self.stack.iter()
+            .skip_while(|&&i| i != index)
+            .map(|&i| self.items.items[i].name.as_str())
+            .collect();
+        let item = &self.items.items[index];
+
+        let mut error = LiteralError::new(format!("cycle detected when simplifying constant for the type system `{}`", names[0]), item.start, item.end)
+            .code("E0391");
+        for (i, name) in names.iter().enumerate() {
+            if i > 0 {
+                error = error.note(format!("note: ...which requires simplifying constant for the type system `{}`...", name));
+            }
+            error = error.note(format!("note: ...which requires const-evaluating + checking `{}`...", name));
+        }
+        error.note(format!("note: ...which again requires simplifying constant for the type system `{}`, completing the cycle", names[0]))
+    }
+
+    fn failed_here(&self, expr: &Expr, message: String) -> Stop {
+        let index = *self.stack.last().expect("only called while evaluating a const");
+        let label = format!("evaluation of `{}` failed here", self.items.items[index].name);
+        Stop::Error(expr.error(message).code("E0080").label(&label))
+    }
+
+    // evaluates `expr` as a `ty`, checking the types on the way like rustc does first
+    fn eval(&mut self, expr: &Expr, ty: &'static str) -> Result<u128, Stop> {
+        match &expr.kind {
+            Kind::Literal(literal) => Ok(self.literal(literal, expr, ty, false)?),
+            Kind::Name(name) => {
+                let index = match self.items.index.get(name) {
+                    Some(&index) => index,
+                    None => {
+                        return Err(expr.error(format!("cannot find value `{}` in this scope", name))
+                            .code("E0425")
+                            .label("not found in this scope")
+                            .into());
+                    }
+                };
+                let items = self.items;
+                match items.items[index].ty {
+                    Some(found) if found != ty => Err(mismatch(expr, ty, found).into()),
+                    Some(_) => self.value(index),
+                    None => Err(mismatch(expr, ty, &items.items[index].type_text).into()),
+                }
+            }
+            Kind::Assoc(of, constant) => {
+                let found = if *constant == "BITS" { "u32" } else { of };
+                if found != ty {
+                    return Err(mismatch(expr, ty, found).into());
+                }
+                Ok(with_int!(*of, T => match *constant {
+                    "MAX" => T::MAX.to_bits(),
+                    "MIN" => T::MIN.to_bits(),
+                    _ => T::BITS as u128,
+                }))
+            }
+            Kind::Neg(inner) => {
+                if integer_type(ty).is_some_and(|(_, signed)| !signed) {
+                    return Err(expr.error(format!("cannot apply unary operator `-` to type `{}`", ty))
+                        .code("E0600")
+                        .label("cannot apply unary operator `-`")
+                        .note(String::from("note: unsigned values cannot be negated"))
+                        .into());
+                }
+                // `-128i8` is fine even though `128i8` on its own isn't
+                if let Kind::Literal(literal) = &inner.kind {
+                    return Ok(self.literal(literal, inner, ty, true)?);
+                }
+                let value = self.eval(inner, ty)?;
+                with_int!(ty, T => match T::from_bits(value).negate() {
+                    Some(negated) => Ok(negated.to_bits()),
+                    None => Err(self.failed_here(expr, format!("attempt to negate `{}`, which would overflow", operand(T::from_bits(value))))),
+                })
+            }
+            Kind::Not(inner) => {
+                let value = self.eval(inner, ty)?;
+                Ok(with_int!(ty, T => T::from_bits(value).not().to_bits()))
+            }
+            Kind::Binary(op @ (BinOp::Shl | BinOp::Shr), left, right) => {
+                let value = self.eval(left, ty)?;
+                // the amount can be any integer type, and a bare literal is an i32
+                let amount_ty = right.fixed_type(self.items).unwrap_or("i32");
+                let amount = self.eval(right, amount_ty)?;
+                let signed = integer_type(amount_ty).is_some_and(|(_, signed)| signed);
+                let (bits, _) = integer_type(ty).expect("evaluated as an integer type");
+                let in_range = if signed { (amount as i128) >= 0 && (amount as i128) < bits as i128 } else { amount < bits as u128 };
+                if !in_range {
+                    let direction = if *op == BinOp::Shl { "left" } else { "right" };
+                    let amount = with_int!(amount_ty, T => operand(T::from_bits(amount)));
+                    return Err(self.failed_here(expr, format!("attempt to shift {} by `{}`, which would overflow", direction, amount)));
+                }
+                Ok(with_int!(ty, T => T::shift(T::from_bits(value), *op, amount as u32).to_bits()))
+            }
+            Kind::Binary(op, left, right) => {
+                let a = self.eval(left, ty)?;
+                let b = self.eval(right, ty)?;
+                with_int!(ty, T => arithmetic::<T>(a, *op, b)).map_err(|message| self.failed_here(expr, message))
+            }
+            Kind::Cast(inner, to) => {
+                if *to != ty {
+                    return Err(mismatch(expr, ty, to).into());
+                }
+                let from = inner.fixed_type(self.items).unwrap_or("i32");
+                if integer_type(from).is_none() {
+                    return Err(inner.error(format!("only casts between integer types are evaluated here, not from `{}`", from)).into());
+                }
+                let value = self.eval(inner, from)?;
+                // truncating or sign extending is just taking the low bits of the sign extended value
+                Ok(with_int!(*to, T => T::from_bits(value).to_bits()))
+            }
+        }
+    }
+
+    fn literal(&self, literal: &Literal, expr: &Expr, ty: &'static str, negative: bool) -> Result<u128, LiteralError> {
+        let magnitude = match literal.value {
+            LiteralValue::Integer(magnitude) => magnitude,
+            LiteralValue::Float(_) if literal.suffixed => return Err(mismatch(expr, ty, &literal.ty)),
+            LiteralValue::Float(_) => {
+                return Err(expr.error(String::from("mismatched types"))
+                    .code("E0308")
+                    .label(&format!("expected `{}`, found floating-point number", ty)));
+            }
+        };
+        if literal.suffixed && literal.ty != ty {
+            return Err(mismatch(expr, ty, &literal.ty));
+        }
+
+        let mut settled = literal::parse(&literal.source).expect("parsed once already");
+        settled.ty = ty.to_string();
+        settled.negative = negative;
+        if let Some(mut error) = settled.range_error() {
+            error.start += expr.start;
+            error.end += expr.start;
+            return Err(error);
+        }
+        let bits = if negative { magnitude.wrapping_neg() } else { magnitude };
+        Ok(with_int!(ty, T => T::from_bits(bits).to_bits()))
+    }
+}
+
+fn mismatch(expr: &Expr, expected: &str, found: &str) -> LiteralError {
+    expr.error(String::from("mismatched types"))
+        .code("E0308")
+        .label(&format!("expected `{}`, found `{}`", expected, found))
+}
+
+// what `non_upper_case_globals` suggests: snake case first, so `maxVal` becomes `MAX_VAL`
+fn upper_case(name: &str) -> String {
+    let mut out = String::new();
+    let mut previous: Option<char> = None;
+    for c in name.chars() {
+        if c.is_uppercase() && previous.is_some_and(|p| p.is_lowercase() || p.is_ascii_digit()) {
+            out.push('_');
+        }
+        out.extend(c.to_uppercase());
+        previous = Some(c);
+    }
+    out
+}
+
+pub struct Report {
+    pub consts: Vec<Const>,
+    // rendered the way rustc prints them, in the order it would find them
+    pub diagnostics: Vec<String>,
+    pub errors: usize,
+    pub warnings: usize,
+}
+
+// finds the line `error` starts on and prints it against that line
+fn render(source: &str, error: &LiteralError) -> String {
+    let line_start = source[..error.start].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let number = source[..line_start].matches('\n').count() + 1;
+    let line = source[line_start..].lines().next().unwrap_or("");
+    error.render_line(number, line, line_start)
+}
+
+// finds every `const NAME: TYPE = EXPR;` in the source, anywhere, and skips everything else
+fn parse(source: &str, tokens: &[Spanned], errors: &mut Vec<LiteralError>) -> Vec<Item> {
+    let mut parser = Parser { tokens, source, pos: 0 };
+    let mut items = Vec::new();
+    while let Some(token) = parser.peek() {
+        let is_item = *token == Token::Ident(String::from("const"))
+            && matches!(tokens.get(parser.pos + 1), Some(Spanned { token: Token::Ident(name), .. }) if name != "fn");
+        if !is_item {
+            parser.pos += 1;
+            continue;
+        }
+        match parser.item() {
+            Ok(item) => items.push(item),
+            Err(error) => {
+                errors.push(error);
+                parser.skip_past(";");
+            }
+        }
+    }
+    items
+}
+
+pub fn check(source: &str) -> Report {
+    let tokens = match bindings::tokenize(source, &PUNCTUATION) {
+        Ok(tokens) => tokens,
+        Err((line, problem)) => {
+            return Report {
+                consts: Vec::new(),
+                diagnostics: vec![format!("line {}: {}", line, bindings::describe(problem))],
+                errors: 1,
+                warnings: 0,
+            };
+        }
+    };
+
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let mut items = Items { items: Vec::new(), index: HashMap::new() };
+    for item in parse(source, &tokens, &mut errors) {
+        if item.name.chars().any(|c| c.is_lowercase()) {
+            warnings.push(LiteralError::new(format!("constant `{}` should have an upper case name", item.name), item.name_start, item.name_end)
+                .warning()
+                .note(format!("help: convert the identifier to upper case: `{}`", upper_case(&item.name)))
+                .note(String::from("note: `#[warn(non_upper_case_globals)]` on by default")));
+        }
+        // the second one doesn't get evaluated, the first one is still the real one
+        if items.index.contains_key(&item.name) {
+            errors.push(LiteralError::new(format!("the name `{}` is defined multiple times", item.name), item.name_start, item.name_end)
+                .code("E0428")
+                .label(&format!("`{}` redefined here", item.name))
+                .note(format!("note: `{}` must be defined only once in the value namespace of this module", item.name)));
+            continue;
+        }
+        items.index.insert(item.name.clone(), items.items.len());
+        items.items.push(item);
+    }
+
+    let mut evaluator = Evaluator {
+        items: &items,
+        states: vec![State::Waiting; items.items.len()],
+        outcomes: vec![Outcome::Skipped; items.items.len()],
+        stack: Vec::new(),
+        errors,
+    };
+    for index in 0..items.items.len() {
+        // failures are already in the errors or the outcomes
+        let _ = evaluator.value(index);
+    }
+
+    let mut diagnostics: Vec<(usize, String)> = evaluator.errors.iter()
+        .chain(warnings.iter())
+        .map(|error| (error.start, render(source, error)))
+        .collect();
+    diagnostics.sort_by_key(|(start, _)| *start);
+
+    let consts = items.items.iter()
+        .zip(evaluator.outcomes)
+        .map(|(item, outcome)| {
+            let mut uses = Vec::new();
+            if let Some(expr) = &item.expr {
+                expr.names(&mut uses);
+            }
+            Const {
+                name: item.name.clone(),
+                ty: item.type_text.clone(),
+                line: item.line,
+                uses: uses.into_iter().map(String::from).collect(),
+                outcome,
+            }
+        })
+        .collect();
+
+    Report {
+        consts,
+        diagnostics: diagnostics.into_iter().map(|(_, text)| text).collect(),
+        errors: evaluator.errors.len(),
+        warnings: warnings.len(),
+    }
+}
+
+fn plural(count: usize, word: &str) -> String {
+    if count == 1 {
+        format!("{} {}", count, word)
+    } else {
+        format!("{} {}s", count, word)
+    }
+}
+
+impl fmt::Display for Const {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let declared = format!("{}: {}", self.name, self.ty);
+        match &self.outcome {
+            Outcome::Value(value) => write!(f, "{} = {}", declared, value)?,
+            Outcome::Failed => write!(f, "{}, failed", declared)?,
+            Outcome::Blocked(name) => write!(f, "{}, not evaluated: it uses `{}`, which failed", declared, name)?,
+            Outcome::Cycle => write!(f, "{}, part of a cycle", declared)?,
+            Outcome::Skipped => write!(f, "{}, not evaluated: only integer consts are", declared)?,
+        }
+        if !self.uses.is_empty() {
+            write!(f, "  (uses {})", self.uses.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for diagnostic in &self.diagnostics {
+            writeln!(f, "{}\n", diagnostic)?;
+        }
+        match (self.errors, self.warnings) {
+            (0, 0) => {}
+            (0, warnings) => writeln!(f, "warning: {} emitted\n", plural(warnings, "warning"))?,
+            (errors, 0) => writeln!(f, "error: aborting due to {}\n", plural(errors, "previous error"))?,
+            (errors, warnings) => writeln!(f, "error: aborting due to {}; {} emitted\n", plural(errors, "previous error"), plural(warnings, "warning"))?,
+        }
+
+        if self.consts.is_empty() {
+            return writeln!(f, "no `const` items found");
+        }
+        for item in &self.consts {
+            writeln!(f, "{:>4} | {}", item.line, item)?;
+        }
+        Ok(())
+    }
+}
+
+// the const from ch3 and its neighbours, declared out of order, plus one of each way to fail
+pub const EXAMPLE: &str = "\
+const MAX_VAL: u32 = 100_000;
+const THREE_HOURS_IN_SECONDS: u32 = SECONDS_PER_HOUR * 3;
+const SECONDS_PER_HOUR: u32 = 60 * SECONDS_PER_MINUTE;
+const SECONDS_PER_MINUTE: u32 = 60;
+const FLAGS: u8 = 1 << 3 | 1 << 5;
+const ALL_ONES: u64 = !0;
+const LOWEST: i16 = i16::MIN + 1;
+const TRUNCATED: u8 = MAX_VAL as u8;
+const TOO_BIG: u8 = 200 + 100;
+const ONE_LESS: u8 = TOO_BIG - 1;
+const TOO_FAR: u32 = 1 << 40;
+const PING: u32 = PONG + 1;
+const PONG: u32 = PING * 2;
+const max_retries: u8 = 3;
+const TIMEOUT: u64 = MAX_VAL;
+";
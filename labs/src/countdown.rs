@@ -0,0 +1,602 @@
+// the `while number != 0` countdown and its `(1..4).rev()` twin in ch3/controlflow.rs, grown
+// into one that can run a real launch: any start, any tick interval, holds at set points, and
+// pause, resume and abort while it runs
+//
+// it never looks at the time or the keyboard itself, it asks a `Clock`. `SystemClock` is the
+// real thing, reading keys a line at a time. `ScriptedClock` plays back keys pressed at set
+// times and jumps straight to every deadline, so an hour-long countdown takes no time at all
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+use std::process::Command;
+use std::sync::mpsc::{Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Key {
+    Pause,
+    Resume,
+    Abort,
+}
+
+impl Key {
+    pub fn parse(text: &str) -> Option<Key> {
+        match text.trim() {
+            "p" | "pause" => Some(Key::Pause),
+            "r" | "resume" => Some(Key::Resume),
+            "a" | "abort" => Some(Key::Abort),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Deadline,
+    Key(Key),
+}
+
+pub trait Clock {
+    // how long since the countdown started
+    fn now(&self) -> Duration;
+    // waits until `deadline`, unless a key comes first. with no deadline it waits for a key
+    fn wait(&mut self, deadline: Option<Duration>) -> Event;
+}
+
+// the wall clock, with keys coming in as lines from whoever holds the other end of `keys`
+pub struct SystemClock<'a> {
+    start: Instant,
+    keys: &'a Receiver<String>,
+    closed: bool,
+}
+
+impl<'a> SystemClock<'a> {
+    pub fn new(keys: &'a Receiver<String>) -> SystemClock<'a> {
+        SystemClock { start: Instant::now(), keys, closed: false }
+    }
+}
+
+impl Clock for SystemClock<'_> {
+    fn now(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    fn wait(&mut self, deadline: Option<Duration>) -> Event {
+        loop {
+            // nothing more is coming in, so a pause could never end
+            if self.closed {
+                return match deadline {
+                    Some(deadline) => {
+                        thread::sleep(deadline.saturating_sub(self.now()));
+                        Event::Deadline
+                    }
+                    None => Event::Key(Key::Abort),
+                };
+            }
+            let received = match deadline {
+                Some(deadline) => self.keys.recv_timeout(deadline.saturating_sub(self.now())),
+                None => self.keys.recv().map_err(|_| RecvTimeoutError::Disconnected),
+            };
+            match received {
+                Ok(line) => {
+                    if let Some(key) = Key::parse(&line) {
+                        return Event::Key(key);
+                    }
+                }
+                Err(RecvTimeoutError::Timeout) => return Event::Deadline,
+                Err(RecvTimeoutError::Disconnected) => self.closed = true,
+            }
+        }
+    }
+}
+
+// keys pressed at set times. a key due at the same moment as a deadline comes after it, and
+// a pause with no keys left to end it gets aborted
+pub struct ScriptedClock {
+    now: Duration,
+    keys: VecDeque<(Duration, Key)>,
+}
+
+impl ScriptedClock {
+    pub fn new(mut keys: Vec<(Duration, Key)>) -> ScriptedClock {
+        keys.sort_by_key(|&(at, _)| at);
+        ScriptedClock { now: Duration::ZERO, keys: keys.into() }
+    }
+}
+
+impl Clock for ScriptedClock {
+    fn now(&self) -> Duration {
+        self.now
+    }
+
+    fn wait(&mut self, deadline: Option<Duration>) -> Event {
+        let due = self.keys.front().filter(|&&(at, _)| deadline.is_none_or(|deadline| at < deadline));
+        if let Some(&(at, key)) = due {
+            self.keys.pop_front();
+            self.now = self.now.max(at);
+            return Event::Key(key);
+        }
+        match deadline {
+            Some(deadline) => {
+                self.now = self.now.max(deadline);
+                Event::Deadline
+            }
+            None => Event::Key(Key::Abort),
+        }
+    }
+}
+
+// the countdown stops at `at` before it gets there, for `length` or until it's resumed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hold {
+    pub at: Duration,
+    pub length: Option<Duration>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Countdown {
+    pub start: Duration,
+    pub interval: Duration,
+    // the latest first
+    pub holds: Vec<Hold>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Liftoff,
+    // with the time that was left
+    Aborted(Duration),
+}
+
+// 01:02:03, with the milliseconds only when there are some
+pub fn clock_time(time: Duration) -> String {
+    let seconds = time.as_secs();
+    let mut text = format!("{:02}:{:02}:{:02}", seconds / 3600, seconds / 60 % 60, seconds % 60);
+    if time.subsec_nanos() != 0 {
+        text.push_str(&format!(".{:03}", time.subsec_millis()));
+    }
+    text
+}
+
+pub fn t_minus(time: Duration) -> String {
+    format!("T-minus {}", clock_time(time))
+}
+
+impl Countdown {
+    pub fn new(start: Duration, interval: Duration, mut holds: Vec<Hold>) -> Result<Countdown, String> {
+        if interval.is_zero() {
+            return Err(String::from("the interval has to be longer than nothing"));
+        }
+        for hold in &holds {
+            if hold.at.is_zero() || hold.at > start {
+                return Err(format!("can't hold at {}, it has to be between the start and liftoff", t_minus(hold.at)));
+            }
+        }
+        holds.sort_by_key(|hold| Reverse(hold.at));
+        if let Some(pair) = holds.windows(2).find(|pair| pair[0].at == pair[1].at) {
+            return Err(format!("there are two holds at {}, give it just one", t_minus(pair[0].at)));
+        }
+        Ok(Countdown { start, interval, holds })
+    }
+
+    // where the countdown next stops to say something: the next tick, or a hold if one comes
+    // first. ticks are counted from the start, so a pause doesn't knock them off the beat
+    fn next_mark(&self, remaining: Duration, holds: &[Hold]) -> Duration {
+        let (start, interval) = (self.start.as_nanos(), self.interval.as_nanos());
+        let ticks = (start - remaining.as_nanos()) / interval + 1;
+        let tick = Duration::from_nanos(start.saturating_sub(ticks * interval) as u64);
+        holds.iter().map(|hold| hold.at).filter(|&at| at < remaining).fold(tick, Duration::max)
+    }
+
+    // runs the countdown, telling `say` what it's doing and when. all the waiting goes through
+    // `clock`
+    pub fn run<C: Clock>(&self, clock: &mut C, say: &mut dyn FnMut(Duration, String)) -> Outcome {
+        let mut remaining = self.start;
+        // when `remaining` was last brought up to date
+        let mut since = clock.now();
+        let mut next_hold = 0;
+        if !remaining.is_zero() {
+            say(since, t_minus(remaining));
+        }
+
+        loop {
+            while let Some(hold) = self.holds.get(next_hold).filter(|hold| hold.at >= remaining) {
+                next_hold += 1;
+                let message = match hold.length {
+                    Some(length) => format!("hold at {} for {}", t_minus(remaining), clock_time(length)),
+                    None => format!("hold at {}, r to resume", t_minus(remaining)),
+                };
+                say(clock.now(), message);
+                let until = hold.length.map(|length| clock.now() + length);
+                if !hold_until(clock, until, say) {
+                    say(clock.now(), format!("aborted at {}", t_minus(remaining)));
+                    return Outcome::Aborted(remaining);
+                }
+                since = clock.now();
+            }
+            if remaining.is_zero() {
+                say(clock.now(), String::from("LIFTOFF!!!"));
+                return Outcome::Liftoff;
+            }
+
+            let mark = self.next_mark(remaining, &self.holds[next_hold..]);
+            let deadline = since + (remaining - mark);
+            match clock.wait(Some(deadline)) {
+                Event::Deadline => {
+                    remaining = mark;
+                    since = deadline;
+                    // zero gets a liftoff instead
+                    if !remaining.is_zero() {
+                        say(since, t_minus(remaining));
+                    }
+                }
+                Event::Key(key) => {
+                    let now = clock.now();
+                    remaining -= (now - since).min(remaining - mark);
+                    since = now;
+                    match key {
+                        Key::Pause => {
+                            say(now, format!("paused at {}, r to resume", t_minus(remaining)));
+                            if !hold_until(clock, None, say) {
+                                say(clock.now(), format!("aborted at {}", t_minus(remaining)));
+                                return Outcome::Aborted(remaining);
+                            }
+                            since = clock.now();
+                        }
+                        Key::Abort => {
+                            say(now, format!("aborted at {}", t_minus(remaining)));
+                            return Outcome::Aborted(remaining);
+                        }
+                        // it's already going
+                        Key::Resume => {}
+                    }
+                }
+            }
+        }
+    }
+}
+
+// sits still until `until` or a resume, whichever comes first. false if it got aborted instead
+fn hold_until<C: Clock>(clock: &mut C, until: Option<Duration>, say: &mut dyn FnMut(Duration, String)) -> bool {
+    loop {
+        match clock.wait(until) {
+            Event::Deadline => {
+                say(clock.now(), String::from("hold over, resuming"));
+                return true;
+            }
+            Event::Key(Key::Resume) => {
+                say(clock.now(), String::from("resuming"));
+                return true;
+            }
+            Event::Key(Key::Abort) => return false,
+            Event::Key(Key::Pause) => {}
+        }
+    }
+}
+
+// runs `command` through the shell once the rocket is off
+pub fn run_hook(command: &str) -> Result<String, String> {
+    let (shell, flag) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+    let status = Command::new(shell)
+        .arg(flag)
+        .arg(command)
+        .status()
+        .map_err(|e| format!("couldn't run `{}`: {}", command, e))?;
+    if status.success() {
+        Ok(format!("ran `{}`", command))
+    } else {
+        Err(format!("`{}` failed with {}", command, status))
+    }
+}
+
+// exact decimals, so 0.1 is a tenth of a second and not the f64 nearest to it
+fn seconds(text: &str) -> Option<Duration> {
+    let (whole, fraction) = text.split_once('.').unwrap_or((text, ""));
+    if whole.is_empty() && fraction.is_empty() || fraction.len() > 9 {
+        return None;
+    }
+    let digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if !digits(whole) || !digits(fraction) {
+        return None;
+    }
+    let secs = if whole.is_empty() { 0 } else { whole.parse().ok()? };
+    let nanos = if fraction.is_empty() { 0 } else { format!("{:0<9}", fraction).parse().ok()? };
+    Some(Duration::new(secs, nanos))
+}
+
+// `90`, `2.5`, `1:30`, `01:00:00`, `500ms`, `90s`, `15m` or `2h`
+pub fn parse_duration(text: &str) -> Result<Duration, String> {
+    let bad = || format!("`{}` isn't a time, try something like 90, 1:30, 01:00:00, 500ms, 15m or 2h", text);
+    let units: [(&str, u32); 4] = [("ms", 1), ("s", 1000), ("m", 60_000), ("h", 3_600_000)];
+    for (suffix, millis) in units.iter() {
+        if let Some(number) = text.strip_suffix(suffix) {
+            // `1.5m` is 1.5 minutes, which `seconds` can read as 1.5 and scale up
+            return seconds(number).and_then(|d| d.checked_mul(*millis)).map(|d| d / 1000).ok_or_else(bad);
+        }
+    }
+    let parts: Vec<&str> = text.split(':').collect();
+    if parts.len() > 3 {
+        return Err(bad());
+    }
+    let (last, rest) = parts.split_last().ok_or_else(bad)?;
+    let mut total = seconds(last).ok_or_else(bad)?;
+    for (part, scale) in rest.iter().rev().zip([60u64, 3600].iter()) {
+        let count: u64 = part.parse().map_err(|_| bad())?;
+        let extra = count.checked_mul(*scale).ok_or_else(bad)?;
+        total = total.checked_add(Duration::from_secs(extra)).ok_or_else(bad)?;
+    }
+    Ok(total)
+}
+
+// a countdown to run, and what to do with it
+pub struct Settings {
+    pub countdown: Countdown,
+    pub hook: Option<String>,
+    // keys to play back on a `ScriptedClock` instead of running in real time
+    pub script: Option<Vec<(Duration, Key)>>,
+}
+
+// `[START] [--every INTERVAL] [--hold AT[+FOR]]... [--keys KEY@TIME,...] [--on-liftoff COMMAND]`.
+// everything after `--on-liftoff` is the command, spaces and all
+pub fn parse(line: &str) -> Result<Settings, String> {
+    let (line, hook) = match line.split_once("--on-liftoff") {
+        Some((_, hook)) if hook.trim().is_empty() => return Err(String::from("--on-liftoff needs a command after it")),
+        Some((line, hook)) => (line, Some(hook.trim().to_string())),
+        None => (line, None),
+    };
+    let (mut start, mut interval) = (Duration::from_secs(10), Duration::from_secs(1));
+    let (mut holds, mut script) = (Vec::new(), None);
+    let mut words = line.split_whitespace();
+    while let Some(word) = words.next() {
+        let mut value = || words.next().ok_or_else(|| format!("{} needs a value after it", word));
+        match word {
+            "--every" => interval = parse_duration(value()?)?,
+            "--hold" => {
+                let value = value()?;
+                let (at, length) = match value.split_once('+') {
+                    Some((at, length)) => (parse_duration(at)?, Some(parse_duration(length)?)),
+                    None => (parse_duration(value)?, None),
+                };
+                holds.push(Hold { at, length });
+            }
+            "--keys" => {
+                let mut keys = Vec::new();
+                for entry in value()?.split(',') {
+                    let (key, at) = entry.split_once('@').ok_or_else(|| format!("`{}` should look like p@2.5", entry))?;
+                    let key = Key::parse(key).ok_or_else(|| format!("`{}` isn't a key, the keys are p, r and a", key))?;
+                    keys.push((parse_duration(at)?, key));
+                }
+                script = Some(keys);
+            }
+            flag if flag.starts_with("--") => return Err(format!("there's no {} option", flag)),
+            time => start = parse_duration(time)?,
+        }
+    }
+    let countdown = Countdown::new(start, interval, holds)?;
+    Ok(Settings { countdown, hook, script })
+}
+
+// a countdown on a scripted clock, every line stamped with the time it happened
+pub fn simulate(countdown: &Countdown, keys: Vec<(Duration, Key)>) -> (Vec<String>, Outcome) {
+    let (said, outcome) = record(countdown, keys);
+    let lines = said.iter().map(|(at, message)| format!("    +{:<12}  {}", clock_time(*at), message)).collect();
+    (lines, outcome)
+}
+
+fn record(countdown: &Countdown, keys: Vec<(Duration, Key)>) -> (Vec<(Duration, String)>, Outcome) {
+    let mut clock = ScriptedClock::new(keys);
+    let mut said = Vec::new();
+    let outcome = countdown.run(&mut clock, &mut |at, message| said.push((at, message)));
+    (said, outcome)
+}
+
+pub fn demo() -> String {
+    let examples = [
+        ("3", "the `while number != 0` loop from ch3/controlflow.rs"),
+        ("10 --every 2 --hold 4+5 --keys p@3.5,r@8", "a pause, and a 5 second hold at T-minus 4"),
+        ("01:00:00 --every 15m --hold 30m --keys a@45m", "an hour, held at the half way point and scrubbed"),
+    ];
+    let mut sections = Vec::new();
+    for (line, about) in examples.iter() {
+        let settings = parse(line).expect("the examples are valid");
+        let keys = settings.script.unwrap_or_default();
+        let (lines, _) = simulate(&settings.countdown, keys);
+        sections.push(format!("{} ({})\n{}", line, about, lines.join("\n")));
+    }
+    sections.join("\n\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: u64) -> Duration {
+        Duration::from_millis(millis)
+    }
+
+    fn secs(secs: u64) -> Duration {
+        Duration::from_secs(secs)
+    }
+
+    // runs `line` on the scripted clock, with everything it said and when
+    fn run(line: &str) -> (Vec<(Duration, String)>, Outcome) {
+        let settings = parse(line).unwrap();
+        let mut clock = ScriptedClock::new(settings.script.unwrap_or_default());
+        let mut said = Vec::new();
+        let outcome = settings.countdown.run(&mut clock, &mut |at, message| said.push((at, message)));
+        (said, outcome)
+    }
+
+    fn transcript(lines: &[(Duration, &str)]) -> Vec<(Duration, String)> {
+        lines.iter().map(|&(at, message)| (at, message.to_string())).collect()
+    }
+
+    #[test]
+    fn straight_through() {
+        let (said, outcome) = run("3");
+        assert_eq!(said, transcript(&[
+            (secs(0), "T-minus 00:00:03"),
+            (secs(1), "T-minus 00:00:02"),
+            (secs(2), "T-minus 00:00:01"),
+            (secs(3), "LIFTOFF!!!"),
+        ]));
+        assert_eq!(outcome, Outcome::Liftoff);
+    }
+
+    #[test]
+    fn off_the_beat() {
+        let (said, outcome) = run("2.5");
+        assert_eq!(said, transcript(&[
+            (secs(0), "T-minus 00:00:02.500"),
+            (secs(1), "T-minus 00:00:01.500"),
+            (secs(2), "T-minus 00:00:00.500"),
+            (ms(2500), "LIFTOFF!!!"),
+        ]));
+        assert_eq!(outcome, Outcome::Liftoff);
+    }
+
+    #[test]
+    fn pause_and_resume() {
+        let (said, outcome) = run("4 --keys p@1.5,r@4");
+        assert_eq!(said, transcript(&[
+            (secs(0), "T-minus 00:00:04"),
+            (secs(1), "T-minus 00:00:03"),
+            (ms(1500), "paused at T-minus 00:00:02.500, r to resume"),
+            (secs(4), "resuming"),
+            (ms(4500), "T-minus 00:00:02"),
+            (ms(5500), "T-minus 00:00:01"),
+            (ms(6500), "LIFTOFF!!!"),
+        ]));
+        assert_eq!(outcome, Outcome::Liftoff);
+    }
+
+    #[test]
+    fn abort() {
+        let (said, outcome) = run("5 --keys a@2.25");
+        assert_eq!(said, transcript(&[
+            (secs(0), "T-minus 00:00:05"),
+            (secs(1), "T-minus 00:00:04"),
+            (secs(2), "T-minus 00:00:03"),
+            (ms(2250), "aborted at T-minus 00:00:02.750"),
+        ]));
+        assert_eq!(outcome, Outcome::Aborted(ms(2750)));
+    }
+
+    #[test]
+    fn timed_hold() {
+        let (said, outcome) = run("3 --hold 2+5");
+        assert_eq!(said, transcript(&[
+            (secs(0), "T-minus 00:00:03"),
+            (secs(1), "T-minus 00:00:02"),
+            (secs(1), "hold at T-minus 00:00:02 for 00:00:05"),
+            (secs(6), "hold over, resuming"),
+            (secs(7), "T-minus 00:00:01"),
+            (secs(8), "LIFTOFF!!!"),
+        ]));
+        assert_eq!(outcome, Outcome::Liftoff);
+    }
+
+    #[test]
+    fn hold_until_resumed() {
+        let (said, outcome) = run("3 --hold 1 --keys p@10,r@20");
+        assert_eq!(said, transcript(&[
+            (secs(0), "T-minus 00:00:03"),
+            (secs(1), "T-minus 00:00:02"),
+            (secs(2), "T-minus 00:00:01"),
+            (secs(2), "hold at T-minus 00:00:01, r to resume"),
+            (secs(20), "resuming"),
+            (secs(21), "LIFTOFF!!!"),
+        ]));
+        assert_eq!(outcome, Outcome::Liftoff);
+    }
+
+    #[test]
+    fn hold_nobody_resumes() {
+        let (said, outcome) = run("3 --hold 1");
+        assert_eq!(said, transcript(&[
+            (secs(0), "T-minus 00:00:03"),
+            (secs(1), "T-minus 00:00:02"),
+            (secs(2), "T-minus 00:00:01"),
+            (secs(2), "hold at T-minus 00:00:01, r to resume"),
+            (secs(2), "aborted at T-minus 00:00:01"),
+        ]));
+        assert_eq!(outcome, Outcome::Aborted(secs(1)));
+    }
+
+    // the tick lands first, then the pause
+    #[test]
+    fn key_on_a_tick() {
+        let (said, outcome) = run("3 --keys p@1,r@2");
+        assert_eq!(said, transcript(&[
+            (secs(0), "T-minus 00:00:03"),
+            (secs(1), "T-minus 00:00:02"),
+            (secs(1), "paused at T-minus 00:00:02, r to resume"),
+            (secs(2), "resuming"),
+            (secs(3), "T-minus 00:00:01"),
+            (secs(4), "LIFTOFF!!!"),
+        ]));
+        assert_eq!(outcome, Outcome::Liftoff);
+    }
+
+    #[test]
+    fn durations() {
+        assert_eq!(parse_duration("90"), Ok(secs(90)));
+        assert_eq!(parse_duration("2.5"), Ok(ms(2500)));
+        assert_eq!(parse_duration("0.1"), Ok(ms(100)));
+        assert_eq!(parse_duration("1:30"), Ok(secs(90)));
+        assert_eq!(parse_duration("01:00:00"), Ok(secs(3600)));
+        assert_eq!(parse_duration("500ms"), Ok(ms(500)));
+        assert_eq!(parse_duration("90s"), Ok(secs(90)));
+        assert_eq!(parse_duration("1.5m"), Ok(secs(90)));
+        assert_eq!(parse_duration("2h"), Ok(secs(7200)));
+        for bad in ["", ".", "abc", "1:2:3:4", "-5", "1.0000000001", "5x", "m"].iter() {
+            assert!(parse_duration(bad).is_err(), "`{}` parsed", bad);
+        }
+    }
+
+    #[test]
+    fn two_holds_at_the_same_time() {
+        let hold = Hold { at: secs(5), length: None };
+        let timed = Hold { at: secs(5), length: Some(secs(2)) };
+        assert_eq!(
+            Countdown::new(secs(10), secs(1), vec![hold, timed]),
+            Err(String::from("there are two holds at T-minus 00:00:05, give it just one")),
+        );
+    }
+
+    #[test]
+    fn zero_interval() {
+        assert_eq!(Countdown::new(secs(10), Duration::ZERO, Vec::new()), Err(String::from("the interval has to be longer than nothing")));
+    }
+
+    #[test]
+    fn holds_outside_the_countdown() {
+        for at in [Duration::ZERO, secs(11)].iter() {
+            assert!(Countdown::new(secs(10), secs(1), vec![Hold { at: *at, length: None }]).is_err());
+        }
+    }
+
+    #[test]
+    fn settings() {
+        let settings = parse("1:00 --every 5 --hold 30+10 --hold 10 --keys p@2,r@4,a@6 --on-liftoff echo hello world").unwrap();
+        assert_eq!(settings.countdown.start, secs(60));
+        assert_eq!(settings.countdown.interval, secs(5));
+        assert_eq!(settings.countdown.holds, vec![Hold { at: secs(30), length: Some(secs(10)) }, Hold { at: secs(10), length: None }]);
+        assert_eq!(settings.script, Some(vec![(secs(2), Key::Pause), (secs(4), Key::Resume), (secs(6), Key::Abort)]));
+        assert_eq!(settings.hook.as_deref(), Some("echo hello world"));
+
+        let settings = parse("").unwrap();
+        assert_eq!(settings.countdown, Countdown { start: secs(10), interval: secs(1), holds: Vec::new() });
+        assert_eq!(settings.hook, None);
+        assert_eq!(settings.script, None);
+    }
+
+    #[test]
+    fn bad_settings() {
+        assert_eq!(parse("10 --on-liftoff").err(), Some(String::from("--on-liftoff needs a command after it")));
+        assert_eq!(parse("10 --every").err(), Some(String::from("--every needs a value after it")));
+        assert_eq!(parse("10 --fast").err(), Some(String::from("there's no --fast option")));
+        assert_eq!(parse("10 --keys p2").err(), Some(String::from("`p2` should look like p@2.5")));
+        assert_eq!(parse("10 --keys x@2").err(), Some(String::from("`x` isn't a key, the keys are p, r and a")));
+    }
+}
@@ -0,0 +1,442 @@
+// exact decimal numbers for the float section of ch3/varsandmutability.rs, where `95.5 - 4.3`
+// can't be exact because 4.3 has no finite binary expansion. a `Decimal` is an i128 mantissa
+// and a scale, the number of digits after the point, so 95.5 is 955 with scale 1 and 95.50 is
+// 9550 with scale 2 (they compare equal). + - * are exact and panic if the mantissa would
+// overflow, like i128 does in a debug build. division has no operator, since it usually can't
+// be exact: `divide` asks for the scale of the answer and how to round it
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Mul, Neg, Sub};
+use std::str::FromStr;
+
+use crate::float::Float;
+
+// 10^38 is the biggest power of ten an i128 holds
+pub const MAX_SCALE: u32 = 38;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Rounding {
+    // ties go to the even neighbour, what f64 arithmetic itself does
+    HalfEven,
+    // ties go away from zero, what you learn at school
+    HalfUp,
+    Floor,
+    Ceil,
+    // drops the extra digits, what integer `/` does
+    Truncate,
+}
+
+impl Rounding {
+    pub fn parse(s: &str) -> Option<Rounding> {
+        match s {
+            "half-even" => Some(Rounding::HalfEven),
+            "half-up" => Some(Rounding::HalfUp),
+            "floor" => Some(Rounding::Floor),
+            "ceil" => Some(Rounding::Ceil),
+            "truncate" => Some(Rounding::Truncate),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Rounding::HalfEven => "half-even",
+            Rounding::HalfUp => "half-up",
+            Rounding::Floor => "floor",
+            Rounding::Ceil => "ceil",
+            Rounding::Truncate => "truncate",
+        }
+    }
+}
+
+pub const ROUNDINGS: [Rounding; 5] = [
+    Rounding::HalfEven,
+    Rounding::HalfUp,
+    Rounding::Floor,
+    Rounding::Ceil,
+    Rounding::Truncate,
+];
+
+#[derive(Debug, Clone, Copy)]
+pub struct Decimal {
+    mantissa: i128,
+    scale: u32,
+}
+
+fn power_of_ten(exponent: u32) -> Option<i128> {
+    10i128.checked_pow(exponent)
+}
+
+// numerator / denominator as an integer, rounded the way `rounding` says
+fn divide_rounded(numerator: i128, denominator: i128, rounding: Rounding) -> i128 {
+    let quotient = numerator / denominator;
+    let remainder = numerator % denominator;
+    if remainder == 0 {
+        return quotient;
+    }
+
+    let negative = (numerator < 0) != (denominator < 0);
+    let remainder = remainder.unsigned_abs();
+    // compares the remainder with half the denominator without computing 2 · remainder, which
+    // could overflow
+    let half = remainder.cmp(&(denominator.unsigned_abs() - remainder));
+    let away_from_zero = match rounding {
+        Rounding::Truncate => false,
+        Rounding::Floor => negative,
+        Rounding::Ceil => !negative,
+        Rounding::HalfUp => half != Ordering::Less,
+        Rounding::HalfEven => half == Ordering::Greater || (half == Ordering::Equal && quotient % 2 != 0),
+    };
+    match (away_from_zero, negative) {
+        (false, _) => quotient,
+        (true, false) => quotient + 1,
+        (true, true) => quotient - 1,
+    }
+}
+
+impl Decimal {
+    // mantissa · 10^-scale
+    pub fn new(mantissa: i128, scale: u32) -> Decimal {
+        assert!(scale <= MAX_SCALE, "the scale can be at most {}", MAX_SCALE);
+        Decimal { mantissa, scale }
+    }
+
+    pub fn mantissa(&self) -> i128 {
+        self.mantissa
+    }
+
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.mantissa < 0
+    }
+
+    // the same number with `scale` digits after the point, rounding when that's fewer digits.
+    // None if the mantissa doesn't fit any more
+    pub fn rescale(self, scale: u32, rounding: Rounding) -> Option<Decimal> {
+        if scale > MAX_SCALE {
+            return None;
+        }
+        let mantissa = match scale.cmp(&self.scale) {
+            Ordering::Equal => self.mantissa,
+            Ordering::Greater => self.mantissa.checked_mul(power_of_ten(scale - self.scale)?)?,
+            Ordering::Less => divide_rounded(self.mantissa, power_of_ten(self.scale - scale)?, rounding),
+        };
+        Some(Decimal { mantissa, scale })
+    }
+
+    // rounding to fewer digits can't overflow
+    pub fn round(self, scale: u32, rounding: Rounding) -> Decimal {
+        if scale >= self.scale {
+            return self;
+        }
+        self.rescale(scale, rounding).expect("fewer digits always fit")
+    }
+
+    // both mantissas at the bigger of the two scales
+    fn aligned(self, other: Decimal) -> Option<(i128, i128, u32)> {
+        let scale = self.scale.max(other.scale);
+        let a = self.rescale(scale, Rounding::Truncate)?;
+        let b = other.rescale(scale, Rounding::Truncate)?;
+        Some((a.mantissa, b.mantissa, scale))
+    }
+
+    pub fn checked_add(self, other: Decimal) -> Option<Decimal> {
+        let (a, b, scale) = self.aligned(other)?;
+        Some(Decimal { mantissa: a.checked_add(b)?, scale })
+    }
+
+    pub fn checked_sub(self, other: Decimal) -> Option<Decimal> {
+        let (a, b, scale) = self.aligned(other)?;
+        Some(Decimal { mantissa: a.checked_sub(b)?, scale })
+    }
+
+    // only the smallest mantissa has no opposite, and it ends in an 8, so there's no digit to drop
+    // to make room either
+    pub fn checked_neg(self) -> Option<Decimal> {
+        Some(Decimal { mantissa: self.mantissa.checked_neg()?, scale: self.scale })
+    }
+
+    // the scales add up, 1.5 · 1.25 = 1.875. a product with more than MAX_SCALE digits after
+    // the point gets the extra ones rounded off half-even rather than failing
+    pub fn checked_mul(self, other: Decimal) -> Option<Decimal> {
+        let product = Decimal { mantissa: self.mantissa.checked_mul(other.mantissa)?, scale: 0 };
+        match self.scale + other.scale {
+            scale if scale <= MAX_SCALE => Some(Decimal { scale, ..product }),
+            scale => Some(Decimal {
+                mantissa: divide_rounded(product.mantissa, power_of_ten(scale - MAX_SCALE)?, Rounding::HalfEven),
+                scale: MAX_SCALE,
+            }),
+        }
+    }
+
+    // self / other with `scale` digits after the point
+    pub fn divide(self, other: Decimal, scale: u32, rounding: Rounding) -> Result<Decimal, String> {
+        if other.mantissa == 0 {
+            return Err(String::from("attempt to divide by zero"));
+        }
+        if scale > MAX_SCALE {
+            return Err(format!("the scale can be at most {}", MAX_SCALE));
+        }
+
+        // (a / 10^sa) / (b / 10^sb) = (a · 10^(scale + sb - sa) / b) / 10^scale, and when that
+        // power is negative it moves onto the divisor instead
+        let overflow = || format!("{} / {} doesn't fit an i128 mantissa at scale {}", self, other, scale);
+        let shift = scale as i64 + other.scale as i64 - self.scale as i64;
+        let (numerator, denominator) = if shift >= 0 {
+            let factor = power_of_ten(shift as u32).ok_or_else(overflow)?;
+            (self.mantissa.checked_mul(factor).ok_or_else(overflow)?, other.mantissa)
+        } else {
+            let factor = power_of_ten((-shift) as u32).ok_or_else(overflow)?;
+            (self.mantissa, other.mantissa.checked_mul(factor).ok_or_else(overflow)?)
+        };
+        Ok(Decimal { mantissa: divide_rounded(numerator, denominator, rounding), scale })
+    }
+
+    // the f64 nearest to this number. going through the string lets the standard library do the
+    // correctly rounded conversion
+    pub fn to_f64(self) -> f64 {
+        self.to_string().parse().expect("a decimal string is a valid f64")
+    }
+
+    // the exact value of an f64, which always has a finite decimal expansion. None when it needs
+    // more digits than fit in an i128, or for infinity and NaN
+    pub fn from_f64_exact(value: f64) -> Option<Decimal> {
+        if !value.is_finite() {
+            return None;
+        }
+        Float::from_f64(value).exact_decimal().parse().ok()
+    }
+
+    fn digits(&self) -> String {
+        let magnitude = self.mantissa.unsigned_abs().to_string();
+        if self.scale == 0 {
+            return magnitude;
+        }
+        let scale = self.scale as usize;
+        let padded = format!("{:0>width$}", magnitude, width = scale + 1);
+        let (whole, fraction) = padded.split_at(padded.len() - scale);
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Decimal) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl Ord for Decimal {
+    // the whole parts first, then the fractions lined up to the same scale. the fractions are
+    // under 10^38, so lining them up can't overflow the way lining up the mantissas could
+    fn cmp(&self, other: &Decimal) -> Ordering {
+        let split = |d: &Decimal| {
+            let unit = power_of_ten(d.scale).expect("scale is at most 38");
+            (d.mantissa / unit, d.mantissa % unit)
+        };
+        let (whole_a, fraction_a) = split(self);
+        let (whole_b, fraction_b) = split(other);
+        let scale = self.scale.max(other.scale);
+        let line_up = |fraction: i128, from: u32| fraction * power_of_ten(scale - from).expect("scale is at most 38");
+        whole_a.cmp(&whole_b).then_with(|| line_up(fraction_a, self.scale).cmp(&line_up(fraction_b, other.scale)))
+    }
+}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Decimal) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<i64> for Decimal {
+    fn from(value: i64) -> Decimal {
+        Decimal::new(value as i128, 0)
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = String;
+
+    // `95.5`, `-0.001`, `1_000_000.25`. `_` can go between digits like in a rust literal, and
+    // the number of digits after the point becomes the scale
+    fn from_str(s: &str) -> Result<Decimal, String> {
+        let (negative, rest) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+        let (whole, fraction) = match rest.split_once('.') {
+            Some((whole, fraction)) => (whole, fraction),
+            None => (rest, ""),
+        };
+
+        let invalid = || format!("`{}` isn't a decimal number", s);
+        if whole.starts_with('_') || (whole.is_empty() && fraction.is_empty()) {
+            return Err(invalid());
+        }
+        let mut digits = String::new();
+        let mut scale = 0;
+        for (part, is_fraction) in [(whole, false), (fraction, true)] {
+            for c in part.chars() {
+                match c {
+                    '_' => {}
+                    '0'..='9' => {
+                        digits.push(c);
+                        if is_fraction {
+                            scale += 1;
+                        }
+                    }
+                    _ => return Err(invalid()),
+                }
+            }
+        }
+        if digits.is_empty() {
+            return Err(invalid());
+        }
+        if scale > MAX_SCALE {
+            return Err(format!("`{}` has more than {} digits after the point", s, MAX_SCALE));
+        }
+
+        let magnitude: i128 = digits.parse().map_err(|_| format!("`{}` has too many digits for an i128 mantissa", s))?;
+        Ok(Decimal::new(if negative { -magnitude } else { magnitude }, scale))
+    }
+}
+
+// every digit of the scale, or `{:.N}` to round half-even to N digits
+impl fmt::Display for Decimal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let shown = match f.precision() {
+            Some(precision) => self.rescale(precision as u32, Rounding::HalfEven).unwrap_or(*self),
+            None => *self,
+        };
+        f.pad_integral(!shown.is_negative(), "", &shown.digits())
+    }
+}
+
+impl Neg for Decimal {
+    type Output = Decimal;
+
+    fn neg(self) -> Decimal {
+        self.checked_neg().expect("attempt to negate with overflow")
+    }
+}
+
+impl Add for Decimal {
+    type Output = Decimal;
+
+    fn add(self, other: Decimal) -> Decimal {
+        self.checked_add(other).expect("attempt to add with overflow")
+    }
+}
+
+impl Sub for Decimal {
+    type Output = Decimal;
+
+    fn sub(self, other: Decimal) -> Decimal {
+        self.checked_sub(other).expect("attempt to subtract with overflow")
+    }
+}
+
+impl Mul for Decimal {
+    type Output = Decimal;
+
+    fn mul(self, other: Decimal) -> Decimal {
+        self.checked_mul(other).expect("attempt to multiply with overflow")
+    }
+}
+
+fn decimal(text: &str) -> Decimal {
+    text.parse().expect("the demo only uses valid numbers")
+}
+
+fn exact_f64(value: f64) -> String {
+    Float::from_f64(value).exact_decimal()
+}
+
+// one line of the demo: what f64 prints, what's really in it, and the exact decimal answer
+fn compare(label: &str, float: f64, exact: Decimal) -> String {
+    let differs = if Decimal::from_f64_exact(float) == Some(exact) { "same" } else { "differs" };
+    format!(
+        "{}\n    f64      {}\n    really   {}\n    Decimal  {}  ({})",
+        label, float, exact_f64(float), exact, differs
+    )
+}
+
+// the places where f64 and exact decimals part ways, starting with the subtraction from ch3
+pub fn demo() -> String {
+    let mut sections = vec![
+        compare("95.5 - 4.3 (the subtraction in ch3)", 95.5 - 4.3, decimal("95.5") - decimal("4.3")),
+        compare("0.1 + 0.2", 0.1 + 0.2, decimal("0.1") + decimal("0.2")),
+        compare("1.1 * 1.1", 1.1 * 1.1, decimal("1.1") * decimal("1.1")),
+        compare("100 * 1.15", 100.0 * 1.15, decimal("100") * decimal("1.15")),
+    ];
+
+    let tenths = (0..10).fold(0.0, |sum, _| sum + 0.1);
+    let exact_tenths = (0..10).fold(decimal("0"), |sum, _| sum + decimal("0.1"));
+    sections.push(compare("0.1 added up ten times", tenths, exact_tenths));
+
+    // 1.005 is stored as 1.00499999999999989..., so rounding the f64 goes the wrong way
+    let rounded = (1.005f64 * 100.0).round() / 100.0;
+    sections.push(format!(
+        "1.005 rounded half-up to 2 places\n    f64      {:.2} (1.005 is really {})\n    Decimal  {}",
+        rounded,
+        exact_f64(1.005),
+        decimal("1.005").round(2, Rounding::HalfUp)
+    ));
+
+    // the quotient from ch3 can't be exact either way, but with Decimal the rounding is chosen
+    let mut division = format!("56.7 / 32.2 (the division in ch3)\n    f64         {}", 56.7 / 32.2);
+    for rounding in ROUNDINGS.iter() {
+        let quotient = decimal("56.7").divide(decimal("32.2"), 20, *rounding).expect("fits at scale 20");
+        division.push_str(&format!("\n    {:<10}  {}", rounding.name(), quotient));
+    }
+    sections.push(division);
+
+    let mut ties = String::from("ties at scale 0: 2.5, -2.5, 3.5");
+    for rounding in ROUNDINGS.iter() {
+        let rounded: Vec<String> = ["2.5", "-2.5", "3.5"].iter().map(|t| decimal(t).round(0, *rounding).to_string()).collect();
+        ties.push_str(&format!("\n    {:<10}  {}", rounding.name(), rounded.join(", ")));
+    }
+    sections.push(ties);
+
+    sections.join("\n\n")
+}
+
+// evaluates `A OP B`, where `/` can be followed by the scale and the rounding, like
+// `1 / 3 5 half-up`. the f64 answer comes along for comparison
+pub fn evaluate(line: &str) -> Result<String, String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let (a, op, b, rest) = match tokens.as_slice() {
+        [a, op, b, rest @ ..] => (a.parse::<Decimal>()?, *op, b.parse::<Decimal>()?, rest),
+        _ => return Err(String::from("expected `A OP B`, like `95.5 - 4.3` or `1 / 3 5 half-up`")),
+    };
+    if op != "/" && !rest.is_empty() {
+        return Err(String::from("only `/` takes a scale and a rounding"));
+    }
+
+    let overflow = |what: &str| format!("attempt to {} with overflow", what);
+    let (result, float) = match op {
+        "+" => (a.checked_add(b).ok_or_else(|| overflow("add"))?, a.to_f64() + b.to_f64()),
+        "-" => (a.checked_sub(b).ok_or_else(|| overflow("subtract"))?, a.to_f64() - b.to_f64()),
+        "*" => (a.checked_mul(b).ok_or_else(|| overflow("multiply"))?, a.to_f64() * b.to_f64()),
+        "/" => {
+            let scale = match rest.first() {
+                Some(scale) => scale.parse().map_err(|_| format!("the scale has to be a number, got `{}`", scale))?,
+                None => 10,
+            };
+            let rounding = match rest.get(1) {
+                Some(name) => Rounding::parse(name).ok_or_else(|| {
+                    format!("unknown rounding `{}`, try one of: {}", name, ROUNDINGS.iter().map(|r| r.name()).collect::<Vec<_>>().join(" "))
+                })?,
+                None => Rounding::HalfEven,
+            };
+            (a.divide(b, scale, rounding)?, a.to_f64() / b.to_f64())
+        }
+        other => return Err(format!("unknown operator `{}`, try + - * or /", other)),
+    };
+    Ok(compare(&tokens.join(" "), float, result))
+}
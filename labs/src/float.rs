@@ -0,0 +1,555 @@
+// takes apart f32 and f64 values. ch3/varsandmutability.rs says floats follow IEEE-754 and does
+// `56.7 / 32.2`, but `println!` only ever shows the shortest decimal that round-trips, not what
+// is actually stored. everything below works on the raw bits from `to_bits`
+
+use std::cmp::Ordering;
+use std::fmt;
+
+use crate::bigint::BigUint;
+use crate::literal::{self, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Width {
+    F32,
+    F64,
+}
+
+impl Width {
+    fn name(self) -> &'static str {
+        match self {
+            Width::F32 => "f32",
+            Width::F64 => "f64",
+        }
+    }
+
+    fn total_bits(self) -> u32 {
+        match self {
+            Width::F32 => 32,
+            Width::F64 => 64,
+        }
+    }
+
+    fn exponent_bits(self) -> u32 {
+        match self {
+            Width::F32 => 8,
+            Width::F64 => 11,
+        }
+    }
+
+    fn mantissa_bits(self) -> u32 {
+        match self {
+            Width::F32 => f32::MANTISSA_DIGITS - 1,
+            Width::F64 => f64::MANTISSA_DIGITS - 1,
+        }
+    }
+
+    fn bias(self) -> i32 {
+        (1 << (self.exponent_bits() - 1)) - 1
+    }
+}
+
+// n·2^bits, which is all the shifting `Dyadic` needs
+fn shl(n: &BigUint, bits: u32) -> BigUint {
+    n * &BigUint::from(2u32).pow(bits)
+}
+
+// the top 64 bits of `n` and how far they were shifted down. close enough for reporting an
+// error, not for anything exact
+fn approximate(n: &BigUint) -> (f64, i32) {
+    let shift = n.bits().saturating_sub(64) as u32;
+    let top = (n / &BigUint::from(2u32).pow(shift)).to_u128().expect("at most 64 bits are left");
+    (top as f64, shift as i32)
+}
+
+// sign * mantissa * 2^exponent, which every finite float is exactly
+#[derive(Debug, Clone)]
+struct Dyadic {
+    negative: bool,
+    mantissa: BigUint,
+    exponent: i32,
+}
+
+impl Dyadic {
+    fn add(&self, other: &Dyadic) -> Dyadic {
+        let exponent = self.exponent.min(other.exponent);
+        let a = shl(&self.mantissa, (self.exponent - exponent) as u32);
+        let b = shl(&other.mantissa, (other.exponent - exponent) as u32);
+
+        if self.negative == other.negative {
+            return Dyadic { negative: self.negative, mantissa: &a + &b, exponent };
+        }
+        match a.cmp(&b) {
+            Ordering::Less => Dyadic { negative: other.negative, mantissa: &b - &a, exponent },
+            _ => Dyadic { negative: self.negative, mantissa: &a - &b, exponent },
+        }
+    }
+
+    fn negate(&self) -> Dyadic {
+        Dyadic { negative: !self.negative, ..self.clone() }
+    }
+
+    fn mul(&self, other: &Dyadic) -> Dyadic {
+        Dyadic {
+            negative: self.negative != other.negative,
+            mantissa: &self.mantissa * &other.mantissa,
+            exponent: self.exponent + other.exponent,
+        }
+    }
+
+    fn to_f64(&self) -> f64 {
+        let (top, shift) = approximate(&self.mantissa);
+        // two steps so that huge or tiny exponents don't overflow on the way
+        let exponent = self.exponent + shift;
+        let value = top * 2f64.powi(exponent / 2) * 2f64.powi(exponent - exponent / 2);
+        if self.negative { -value } else { value }
+    }
+
+    // the exact decimal expansion. m * 2^-k is m * 5^k / 10^k, and that always terminates
+    fn to_decimal(&self) -> String {
+        let sign = if self.negative && !self.mantissa.is_zero() { "-" } else { "" };
+        if self.exponent >= 0 {
+            return format!("{}{}", sign, shl(&self.mantissa, self.exponent as u32));
+        }
+
+        let places = (-self.exponent) as usize;
+        let scaled = &self.mantissa * &BigUint::from(5u32).pow(places as u32);
+        let digits = format!("{:0>width$}", scaled.to_string(), width = places + 1);
+        let (whole, fraction) = digits.split_at(digits.len() - places);
+        let fraction = fraction.trim_end_matches('0');
+        if fraction.is_empty() {
+            format!("{}{}", sign, whole)
+        } else {
+            format!("{}{}.{}", sign, whole, fraction)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Class {
+    Zero,
+    Subnormal,
+    Normal,
+    Infinite,
+    Nan { quiet: bool, payload: u64 },
+}
+
+impl fmt::Display for Class {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Class::Zero => write!(f, "zero"),
+            Class::Subnormal => write!(f, "subnormal (no implicit leading 1, so precision drops off)"),
+            Class::Normal => write!(f, "normal"),
+            Class::Infinite => write!(f, "infinity"),
+            Class::Nan { quiet, payload } => {
+                write!(f, "NaN, {}, payload {:#x}", if *quiet { "quiet" } else { "signaling" }, payload)
+            }
+        }
+    }
+}
+
+// a float of either width, kept as its raw bits
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Float {
+    pub width: Width,
+    pub bits: u64,
+}
+
+impl Float {
+    pub fn from_f64(value: f64) -> Float {
+        Float { width: Width::F64, bits: value.to_bits() }
+    }
+
+    pub fn from_f32(value: f32) -> Float {
+        Float { width: Width::F32, bits: value.to_bits() as u64 }
+    }
+
+    // widening f32 to f64 is always exact, so this loses nothing for either width
+    pub fn to_f64(self) -> f64 {
+        match self.width {
+            Width::F32 => f32::from_bits(self.bits as u32) as f64,
+            Width::F64 => f64::from_bits(self.bits),
+        }
+    }
+
+    pub fn sign(self) -> u64 {
+        self.bits >> (self.width.total_bits() - 1)
+    }
+
+    pub fn exponent_field(self) -> u64 {
+        (self.bits >> self.width.mantissa_bits()) & ((1 << self.width.exponent_bits()) - 1)
+    }
+
+    pub fn mantissa_field(self) -> u64 {
+        self.bits & ((1 << self.width.mantissa_bits()) - 1)
+    }
+
+    pub fn class(self) -> Class {
+        let max_exponent = (1 << self.width.exponent_bits()) - 1;
+        match (self.exponent_field(), self.mantissa_field()) {
+            (0, 0) => Class::Zero,
+            (0, _) => Class::Subnormal,
+            (e, 0) if e == max_exponent => Class::Infinite,
+            (e, m) if e == max_exponent => {
+                let quiet_bit = 1 << (self.width.mantissa_bits() - 1);
+                Class::Nan { quiet: m & quiet_bit != 0, payload: m & (quiet_bit - 1) }
+            }
+            _ => Class::Normal,
+        }
+    }
+
+    fn is_finite(self) -> bool {
+        !matches!(self.class(), Class::Infinite | Class::Nan { .. })
+    }
+
+    // the exponent with the bias taken off, as it applies to `1.mantissa` (or `0.mantissa`
+    // for subnormals, which are stuck at the smallest exponent)
+    pub fn unbiased_exponent(self) -> i32 {
+        match self.exponent_field() {
+            0 => 1 - self.width.bias(),
+            e => e as i32 - self.width.bias(),
+        }
+    }
+
+    fn dyadic(self) -> Dyadic {
+        let mut mantissa = self.mantissa_field();
+        if self.exponent_field() != 0 {
+            mantissa |= 1 << self.width.mantissa_bits();
+        }
+        Dyadic {
+            negative: self.sign() == 1,
+            mantissa: BigUint::from(mantissa),
+            exponent: self.unbiased_exponent() - self.width.mantissa_bits() as i32,
+        }
+    }
+
+    // every digit of what's really in memory
+    pub fn exact_decimal(self) -> String {
+        match self.class() {
+            Class::Infinite | Class::Nan { .. } => self.to_string(),
+            _ => self.dyadic().to_decimal(),
+        }
+    }
+
+    // the next float up or down, by stepping the bits. the bit patterns of floats with the same
+    // sign are in the same order as their values, which is the whole trick
+    pub fn step(self, up: bool) -> Float {
+        let sign_bit = 1 << (self.width.total_bits() - 1);
+        let bits = match self.class() {
+            Class::Nan { .. } => self.bits,
+            Class::Infinite if (self.sign() == 0) == up => self.bits,
+            // both zeros step to the smallest subnormal of the right sign
+            Class::Zero => if up { 1 } else { sign_bit | 1 },
+            _ if (self.sign() == 0) == up => self.bits + 1,
+            _ => self.bits - 1,
+        };
+        Float { width: self.width, bits }
+    }
+
+    // distance to the neighbour on one side, as a float of the same width
+    pub fn gap(self, up: bool) -> Option<f64> {
+        let neighbour = self.step(up);
+        if !self.is_finite() || !neighbour.is_finite() {
+            return None;
+        }
+        Some((neighbour.to_f64() - self.to_f64()).abs())
+    }
+
+    // the size of one unit in the last place at this value
+    pub fn ulp(self) -> Option<f64> {
+        let magnitude = Float { width: self.width, bits: self.bits & !(1 << (self.width.total_bits() - 1)) };
+        magnitude.gap(true)
+    }
+
+    pub fn bit_string(self) -> String {
+        let all = format!("{:0width$b}", self.bits, width = self.width.total_bits() as usize);
+        let exponent_end = 1 + self.width.exponent_bits() as usize;
+        format!("{} {} {}", &all[..1], &all[1..exponent_end], &all[exponent_end..])
+    }
+}
+
+impl fmt::Display for Float {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.width {
+            Width::F32 => write!(f, "{:?}", f32::from_bits(self.bits as u32)),
+            Width::F64 => write!(f, "{:?}", f64::from_bits(self.bits)),
+        }
+    }
+}
+
+// the full breakdown of one value
+pub fn inspect(value: Float) -> String {
+    let width = value.width;
+    let mut out = String::new();
+    let mut line = |label: &str, text: String| out.push_str(&format!("    {:<10}  {}\n", label, text));
+
+    line("bits", value.bit_string());
+    line("sign", format!("{} ({})", value.sign(), if value.sign() == 1 { "negative" } else { "positive" }));
+    let exponent = match value.class() {
+        Class::Infinite | Class::Nan { .. } => String::from("all ones, which is reserved for infinity and NaN"),
+        Class::Zero | Class::Subnormal => format!("all zeros, which counts as 1 - {} = {}", width.bias(), value.unbiased_exponent()),
+        Class::Normal => format!("{}, minus the bias {} = {}", value.exponent_field(), width.bias(), value.unbiased_exponent()),
+    };
+    line("exponent", format!("{:0w$b} = {}", value.exponent_field(), exponent, w = width.exponent_bits() as usize));
+    let mantissa = format!("{:0w$b}", value.mantissa_field(), w = width.mantissa_bits() as usize);
+    line(
+        "mantissa",
+        match value.class() {
+            Class::Normal => format!("1.{} (the leading 1 isn't stored)", mantissa),
+            Class::Zero | Class::Subnormal => format!("0.{} (no leading 1 for subnormals)", mantissa),
+            Class::Infinite | Class::Nan { .. } => mantissa,
+        },
+    );
+    line("class", value.class().to_string());
+    line("stored", value.exact_decimal());
+
+    for (label, up) in [("next", true), ("previous", false)].iter() {
+        let neighbour = value.step(*up);
+        let text = match value.gap(*up) {
+            Some(gap) => format!("{} ({:e} away)", neighbour, gap),
+            None if neighbour == value => String::from("(none)"),
+            None => neighbour.to_string(),
+        };
+        line(label, text);
+    }
+    if let Some(ulp) = value.ulp() {
+        line("ulp", format!("{:e}", ulp));
+    }
+
+    match width {
+        Width::F64 => {
+            let narrowed = value.to_f64() as f32;
+            let back = narrowed as f64;
+            let change = if back.to_bits() == value.bits {
+                String::from("lossless")
+            } else if let Class::Nan { payload, .. } = Float::from_f64(back).class() {
+                format!("still NaN, but the payload is now {:#x}", payload)
+            } else if back.is_finite() && value.to_f64().is_finite() {
+                format!("changed by {:e}", back - value.to_f64())
+            } else {
+                String::from("out of f32's range")
+            };
+            line("as f32", format!("{:?}, back to f64: {:?} ({})", narrowed, back, change));
+        }
+        Width::F32 => {
+            line("as f64", format!("{:?} (f32 -> f64 is always exact)", value.to_f64()));
+        }
+    }
+
+    out
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+pub struct Operation {
+    pub a: Float,
+    pub op: Op,
+    pub b: Float,
+    pub result: Float,
+}
+
+impl Operation {
+    pub fn new(a: Float, op: Op, b: Float) -> Operation {
+        let result = match a.width {
+            Width::F32 => {
+                let (x, y) = (a.to_f64() as f32, b.to_f64() as f32);
+                Float::from_f32(match op {
+                    Op::Add => x + y,
+                    Op::Sub => x - y,
+                    Op::Mul => x * y,
+                    Op::Div => x / y,
+                })
+            }
+            Width::F64 => {
+                let (x, y) = (a.to_f64(), b.to_f64());
+                Float::from_f64(match op {
+                    Op::Add => x + y,
+                    Op::Sub => x - y,
+                    Op::Mul => x * y,
+                    Op::Div => x / y,
+                })
+            }
+        };
+        Operation { a, op, b, result }
+    }
+
+    // the exact result of the operation on the stored operands, when it's a finite decimal.
+    // sums, differences and products of floats always are; quotients usually aren't
+    pub fn exact(&self) -> Option<String> {
+        self.exact_dyadic().map(|exact| exact.to_decimal())
+    }
+
+    fn exact_dyadic(&self) -> Option<Dyadic> {
+        if !self.a.is_finite() || !self.b.is_finite() {
+            return None;
+        }
+        let (a, b) = (self.a.dyadic(), self.b.dyadic());
+        match self.op {
+            Op::Add => Some(a.add(&b)),
+            Op::Sub => Some(a.add(&b.negate())),
+            Op::Mul => Some(a.mul(&b)),
+            Op::Div => None,
+        }
+    }
+
+    // result minus the exact answer. for division that's (result * b - a) / b, where the top
+    // part is still computed exactly
+    pub fn rounding_error(&self) -> Option<f64> {
+        if !self.result.is_finite() || !self.a.is_finite() || !self.b.is_finite() {
+            return None;
+        }
+        match self.op {
+            Op::Div => {
+                if self.b.class() == Class::Zero {
+                    return None;
+                }
+                let residual = self.result.dyadic().mul(&self.b.dyadic()).add(&self.a.dyadic().negate());
+                Some(residual.to_f64() / self.b.to_f64())
+            }
+            _ => {
+                let exact = self.exact_dyadic()?;
+                Some(self.result.dyadic().add(&exact.negate()).to_f64())
+            }
+        }
+    }
+}
+
+fn parse_value(text: &str, width: Option<Width>) -> Result<Float, String> {
+    let width = match (width, suffix(text)) {
+        (Some(x), Some(y)) if x != y => {
+            return Err(format!("mismatched types: expected `{}`, found `{}`", x.name(), y.name()));
+        }
+        (x, y) => x.or(y).unwrap_or(Width::F64),
+    };
+
+    let value = match text.to_lowercase().as_str() {
+        "nan" => f64::NAN,
+        "inf" | "infinity" => f64::INFINITY,
+        "-inf" | "-infinity" => f64::NEG_INFINITY,
+        _ => {
+            // an unsuffixed literal used as an f32 gets rounded straight to f32, not through f64
+            let source = match (width, suffix(text)) {
+                (Width::F32, None) => format!("{}f32", text),
+                _ => text.to_string(),
+            };
+            let literal = literal::parse(&source).map_err(|e| e.render(&source))?;
+            match literal.value {
+                Value::Float(value) => value,
+                Value::Integer(magnitude) => if literal.negative { -(magnitude as f64) } else { magnitude as f64 },
+            }
+        }
+    };
+
+    Ok(match width {
+        Width::F32 => Float::from_f32(value as f32),
+        Width::F64 => Float::from_f64(value),
+    })
+}
+
+// the width a literal's own suffix asks for, if it has one
+fn suffix(text: &str) -> Option<Width> {
+    match literal::parse(text) {
+        Ok(literal) if literal.suffixed && literal.ty == "f32" => Some(Width::F32),
+        Ok(literal) if literal.suffixed && literal.ty == "f64" => Some(Width::F64),
+        _ => None,
+    }
+}
+
+fn parse_bits(text: &str, width: Width) -> Result<Float, String> {
+    let digits = text.trim_start_matches("0x").replace('_', "");
+    let bits = u64::from_str_radix(&digits, 16).map_err(|_| format!("`{}` isn't a hex bit pattern", text))?;
+    if width == Width::F32 && bits > u32::MAX as u64 {
+        return Err(format!("{} doesn't fit in 32 bits", text));
+    }
+    Ok(Float { width, bits })
+}
+
+fn parse_op(text: &str) -> Option<Op> {
+    match text {
+        "+" => Some(Op::Add),
+        "-" => Some(Op::Sub),
+        "*" => Some(Op::Mul),
+        "/" => Some(Op::Div),
+        _ => None,
+    }
+}
+
+fn show_operation(operation: &Operation) -> String {
+    let width = operation.a.width;
+    let symbol = match operation.op {
+        Op::Add => "+",
+        Op::Sub => "-",
+        Op::Mul => "*",
+        Op::Div => "/",
+    };
+
+    let mut out = format!("{} {} {} in {}\n", operation.a, symbol, operation.b, width.name());
+    out.push_str(&format!("    {:<10}  {}\n", "a stored", operation.a.exact_decimal()));
+    out.push_str(&format!("    {:<10}  {}\n", "b stored", operation.b.exact_decimal()));
+    out.push_str(&format!("    {:<10}  {}\n", "exact", operation.exact().unwrap_or_else(|| String::from("(a quotient, usually not a finite decimal)"))));
+    out.push_str(&format!("    {:<10}  {}\n", "result", operation.result.exact_decimal()));
+    if let Some(error) = operation.rounding_error() {
+        let ulps = match operation.result.ulp() {
+            Some(ulp) if ulp > 0.0 => format!(" ({:.3} ulp)", error.abs() / ulp),
+            _ => String::new(),
+        };
+        out.push_str(&format!("    {:<10}  {:e}{}\n", "rounding", error, ulps));
+    }
+    out.push_str(&format!("\nthe result, {}\n", operation.result));
+    out.push_str(&inspect(operation.result));
+    out
+}
+
+// evaluates a line: a value (`0.1`, `1e3f32`, `nan`), a bit pattern (`bits f64 0x7ff8000000000001`)
+// or an operation (`56.7 / 32.2`). `f32 ...` or `f64 ...` in front picks the width when
+// there's no suffix
+pub fn evaluate(line: &str) -> Result<String, String> {
+    let mut tokens: Vec<&str> = line.split_whitespace().collect();
+    let mut width = None;
+    match tokens.first() {
+        Some(&"f32") => width = Some(Width::F32),
+        Some(&"f64") => width = Some(Width::F64),
+        _ => {}
+    }
+    if width.is_some() {
+        tokens.remove(0);
+    }
+
+    // `bits f64 0x...` works as well as `f64 bits 0x...`
+    if let (None, ["bits", name @ ("f32" | "f64"), _]) = (width, tokens.as_slice()) {
+        width = Some(if *name == "f32" { Width::F32 } else { Width::F64 });
+        tokens.remove(1);
+    }
+
+    match tokens.as_slice() {
+        ["bits", pattern] => {
+            let value = parse_bits(pattern, width.unwrap_or(Width::F64))?;
+            Ok(format!("{} as {}\n{}", value, value.width.name(), inspect(value)))
+        }
+        [value] => {
+            let value = parse_value(value, width)?;
+            Ok(format!("{} as {}\n{}", value, value.width.name(), inspect(value)))
+        }
+        [a, op, b] => {
+            let op = parse_op(op).ok_or(format!("unknown operator `{}`, try + - * /", op))?;
+            // a suffix on either side decides the width for both, the same way rust would infer
+            // `1.0 + 2.0f32` as f32. two different suffixes don't compile
+            let width = match (width, suffix(a), suffix(b)) {
+                (Some(width), _, _) => width,
+                (None, Some(x), Some(y)) if x != y => {
+                    return Err(format!("mismatched types: expected `{}`, found `{}`", x.name(), y.name()));
+                }
+                (None, x, y) => x.or(y).unwrap_or(Width::F64),
+            };
+            let a = parse_value(a, Some(width))?;
+            let b = parse_value(b, Some(width))?;
+            Ok(show_operation(&Operation::new(a, op, b)))
+        }
+        _ => Err(String::from("expected a value like `0.1`, an operation like `56.7 / 32.2`, or `bits f64 0x...`")),
+    }
+}
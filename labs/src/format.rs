@@ -0,0 +1,827 @@
+// nicer output than plain `{}` for the numbers in ch3. `number(x)` takes any integer, float,
+// `Decimal` or big integer and gives back a `Formatted`, which is `Display`, so it drops into a
+// `println!` as is:
+//
+//     println!("{}", number(100_000).locale(Locale::EN));                        // 100,000
+//     println!("{}", number(1536).prefix(Prefix::Binary).unit("B"));             // 1.5 KiB
+//     println!("{}", number(0.000123).significant(3).notation(Notation::Engineering)); // 123e-6
+//
+// underneath, every number becomes a list of decimal digits and a power of ten, so rounding and
+// moving the point are exact whatever the number came from. floats start from the shortest
+// digits that read back as the same float, which are the digits `{}` prints
+
+use std::cmp::Ordering;
+use std::env;
+use std::fmt;
+
+use crate::bigint::{BigInt, BigUint};
+use crate::decimal::{Decimal, Rounding};
+
+// digits × 10^exponent, where the digits have no leading zeros (none at all means zero) but
+// keep their trailing ones, so a Decimal like 9.50 still shows two places
+#[derive(Debug, Clone, PartialEq)]
+pub struct Number {
+    negative: bool,
+    digits: Vec<u8>,
+    exponent: i32,
+    // inf and NaN, which have no digits
+    special: Option<&'static str>,
+}
+
+impl Number {
+    fn new(negative: bool, digits: &str, exponent: i32) -> Number {
+        let mut number = Number {
+            negative,
+            digits: digits.bytes().map(|b| b - b'0').collect(),
+            exponent,
+            special: None,
+        };
+        number.trim_leading();
+        number
+    }
+
+    fn trim_leading(&mut self) {
+        let zeros = self.digits.iter().take_while(|&&d| d == 0).count();
+        self.digits.drain(..zeros);
+    }
+
+    fn is_zero(&self) -> bool {
+        self.digits.is_empty()
+    }
+
+    // the power of ten of the first digit, 2 for 123 and -3 for 0.00123
+    fn magnitude(&self) -> Option<i32> {
+        if self.is_zero() {
+            None
+        } else {
+            Some(self.digits.len() as i32 - 1 + self.exponent)
+        }
+    }
+
+    // drops the digits below 10^place, rounding what's left
+    fn round_at(&mut self, place: i32, rounding: Rounding) {
+        if self.is_zero() || place <= self.exponent {
+            return;
+        }
+        let dropped = (place - self.exponent) as usize;
+        // leading zeros are free to add, and give the rounding something to carry into
+        while self.digits.len() < dropped {
+            self.digits.insert(0, 0);
+        }
+        let keep = self.digits.len() - dropped;
+        round_digits(&mut self.digits, keep, 10, self.negative, rounding);
+        self.exponent = place;
+        self.trim_leading();
+    }
+
+    // trailing zeros say how precise a number is, but scientific notation without a precision
+    // reads better without them, the way `{:e}` prints
+    fn trim_trailing(&mut self) {
+        while self.digits.last() == Some(&0) {
+            self.digits.pop();
+            self.exponent += 1;
+        }
+    }
+
+    // the digits before and after the point
+    fn split(&self) -> (String, String) {
+        let text: String = self.digits.iter().map(|d| char::from(b'0' + d)).collect();
+        if self.is_zero() {
+            return (String::from("0"), "0".repeat((-self.exponent).max(0) as usize));
+        }
+        if self.exponent >= 0 {
+            return (format!("{}{}", text, "0".repeat(self.exponent as usize)), String::new());
+        }
+        let after = (-self.exponent) as usize;
+        if text.len() > after {
+            let (whole, fraction) = text.split_at(text.len() - after);
+            (whole.to_string(), fraction.to_string())
+        } else {
+            (String::from("0"), format!("{}{}", "0".repeat(after - text.len()), text))
+        }
+    }
+
+    // exact division by 1024: 1/1024 = 5^10 / 10^10
+    fn div_1024(&mut self) {
+        for _ in 0..10 {
+            mul_small(&mut self.digits, 5);
+        }
+        self.exponent -= 10;
+        self.trim_leading();
+    }
+
+    // whether the whole part is at least `limit`, without building it
+    fn at_least(&self, limit: u32) -> bool {
+        let (whole, _) = self.split();
+        whole.len() > 10 || whole.parse::<u64>().map_or(true, |w| w >= u64::from(limit))
+    }
+}
+
+// multiplies digits (most significant first) by a small number in place and returns what
+// carried off the front, for moving to another radix and dividing by 1024
+fn mul_small(digits: &mut Vec<u8>, by: u32) -> u32 {
+    let mut carry = 0;
+    for digit in digits.iter_mut().rev() {
+        let product = u32::from(*digit) * by + carry;
+        *digit = (product % 10) as u8;
+        carry = product / 10;
+    }
+    let mut spill = carry;
+    while spill > 0 {
+        digits.insert(0, (spill % 10) as u8);
+        spill /= 10;
+    }
+    carry
+}
+
+// divides decimal digits by a small number in place and returns the remainder
+fn div_small(digits: &mut Vec<u8>, by: u32) -> u32 {
+    let mut remainder = 0;
+    for digit in digits.iter_mut() {
+        let current = remainder * 10 + u32::from(*digit);
+        *digit = (current / by) as u8;
+        remainder = current % by;
+    }
+    let zeros = digits.iter().take_while(|&&d| d == 0).count();
+    digits.drain(..zeros);
+    remainder
+}
+
+// how the dropped digits of a number in `radix` compare with half of the last kept place. an
+// even radix writes a half as the single digit radix/2. an odd one can't write it at all, it's
+// radix/2 repeated forever, so dropped digits that match it all the way are still short of it
+fn against_half(dropped: &[u8], radix: u8) -> Ordering {
+    let half = radix / 2;
+    if radix % 2 == 1 {
+        return dropped.iter().map(|d| d.cmp(&half)).find(|&o| o != Ordering::Equal).unwrap_or(Ordering::Less);
+    }
+    match dropped.split_first() {
+        None => Ordering::Less,
+        Some((first, rest)) if first == &half && rest.iter().any(|&d| d != 0) => Ordering::Greater,
+        Some((first, _)) => first.cmp(&half),
+    }
+}
+
+// whether rounding goes away from zero, given how the dropped part compares with a half,
+// whether it's anything at all, and whether what's kept is odd
+fn rounds_up(tail: Ordering, nonzero: bool, odd: bool, negative: bool, rounding: Rounding) -> bool {
+    match rounding {
+        Rounding::HalfEven => tail == Ordering::Greater || (tail == Ordering::Equal && odd),
+        Rounding::HalfUp => tail != Ordering::Less,
+        Rounding::Floor => negative && nonzero,
+        Rounding::Ceil => !negative && nonzero,
+        Rounding::Truncate => false,
+    }
+}
+
+// adds one to the last digit, carrying. true when it carries out the front, leaving all zeros
+fn increment(digits: &mut [u8], radix: u8) -> bool {
+    for digit in digits.iter_mut().rev() {
+        if *digit + 1 < radix {
+            *digit += 1;
+            return false;
+        }
+        *digit = 0;
+    }
+    true
+}
+
+// keeps the first `keep` digits of a number written in `radix`, rounding by what's dropped.
+// returns true when the rounding carries all the way out the front, like 999 → 1000
+fn round_digits(digits: &mut Vec<u8>, keep: usize, radix: u8, negative: bool, rounding: Rounding) -> bool {
+    if keep >= digits.len() {
+        return false;
+    }
+    let tail = against_half(&digits[keep..], radix);
+    let nonzero = digits[keep..].iter().any(|&d| d != 0);
+    // in an odd radix every place is odd, so the digits all count towards odd or even
+    let odd = if radix % 2 == 1 {
+        digits[..keep].iter().map(|&d| u32::from(d)).sum::<u32>() % 2 == 1
+    } else {
+        keep > 0 && digits[keep - 1] % 2 == 1
+    };
+    let up = rounds_up(tail, nonzero, odd, negative, rounding);
+    digits.truncate(keep);
+    if up && increment(digits, radix) {
+        digits.insert(0, 1);
+        return true;
+    }
+    false
+}
+
+macro_rules! from_unsigned {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Number {
+            fn from(value: $t) -> Number {
+                Number::new(false, &value.to_string(), 0)
+            }
+        }
+    )*};
+}
+
+macro_rules! from_signed {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Number {
+            fn from(value: $t) -> Number {
+                Number::new(value < 0, &value.unsigned_abs().to_string(), 0)
+            }
+        }
+    )*};
+}
+
+from_unsigned!(u8, u16, u32, u64, u128, usize);
+from_signed!(i8, i16, i32, i64, i128, isize);
+
+// `{:e}` gives the shortest digits that read back as the same float, like `1.536e3`
+macro_rules! from_float {
+    ($($t:ty),*) => {$(
+        impl From<$t> for Number {
+            fn from(value: $t) -> Number {
+                if value.is_nan() || value.is_infinite() {
+                    let special = if value.is_nan() { "NaN" } else { "inf" };
+                    return Number { negative: value < 0.0, digits: Vec::new(), exponent: 0, special: Some(special) };
+                }
+                let text = format!("{:e}", value.abs());
+                let (mantissa, exponent) = text.split_once('e').expect("`{:e}` always has an exponent");
+                let fraction = mantissa.split_once('.').map_or(0, |(_, fraction)| fraction.len() as i32);
+                let exponent: i32 = exponent.parse().expect("`{:e}` exponents are integers");
+                Number::new(value.is_sign_negative(), &mantissa.replace('.', ""), exponent - fraction)
+            }
+        }
+    )*};
+}
+
+from_float!(f32, f64);
+
+impl From<Decimal> for Number {
+    fn from(value: Decimal) -> Number {
+        Number::new(value.is_negative(), &value.mantissa().unsigned_abs().to_string(), -(value.scale() as i32))
+    }
+}
+
+impl From<BigUint> for Number {
+    fn from(value: BigUint) -> Number {
+        Number::new(false, &value.to_string(), 0)
+    }
+}
+
+impl From<BigInt> for Number {
+    fn from(value: BigInt) -> Number {
+        Number::new(value.is_negative(), &value.magnitude().to_string(), 0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grouping {
+    None,
+    // 100,000,000
+    Thousands,
+    // 10,00,00,000: the last three, then pairs
+    Indian,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Locale {
+    pub name: &'static str,
+    pub group: &'static str,
+    pub point: &'static str,
+    pub grouping: Grouping,
+}
+
+impl Locale {
+    // what `{}` does
+    pub const PLAIN: Locale = Locale { name: "plain", group: "", point: ".", grouping: Grouping::None };
+    pub const EN: Locale = Locale { name: "en", group: ",", point: ".", grouping: Grouping::Thousands };
+    pub const DE: Locale = Locale { name: "de", group: ".", point: ",", grouping: Grouping::Thousands };
+    pub const FR: Locale = Locale { name: "fr", group: " ", point: ",", grouping: Grouping::Thousands };
+    pub const CH: Locale = Locale { name: "ch", group: "'", point: ".", grouping: Grouping::Thousands };
+    pub const IN: Locale = Locale { name: "in", group: ",", point: ".", grouping: Grouping::Indian };
+    // the way rust lets you write a literal
+    pub const RUST: Locale = Locale { name: "rust", group: "_", point: ".", grouping: Grouping::Thousands };
+
+    pub fn parse(name: &str) -> Option<Locale> {
+        LOCALES.iter().find(|locale| locale.name == name).copied()
+    }
+
+    // from LC_ALL, LC_NUMERIC or LANG, in the order the C library checks them. anything it
+    // doesn't recognise, including the usual `C`, stays plain
+    pub fn from_env() -> Locale {
+        let setting = ["LC_ALL", "LC_NUMERIC", "LANG"]
+            .iter()
+            .filter_map(|name| env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .unwrap_or_default();
+        let tag = setting.split('.').next().unwrap_or("");
+        let language = tag.split('_').next().unwrap_or("");
+        match (tag, language) {
+            (_, "hi") | ("en_IN", _) => Locale::IN,
+            ("de_CH", _) | ("it_CH", _) | ("fr_CH", _) => Locale::CH,
+            (_, "en") | (_, "ja") | (_, "zh") | (_, "ko") | (_, "he") | (_, "th") => Locale::EN,
+            (_, "de") | (_, "nl") | (_, "it") | (_, "es") | (_, "pt") | (_, "da") | (_, "id") | (_, "tr") => Locale::DE,
+            (_, "fr") | (_, "ru") | (_, "pl") | (_, "cs") | (_, "sv") | (_, "fi") | (_, "nb") | (_, "uk") => Locale::FR,
+            _ => Locale::PLAIN,
+        }
+    }
+
+    // puts the group separator into a run of digits
+    fn group(&self, digits: &str, size: usize) -> String {
+        let mut groups = Vec::new();
+        let mut end = digits.len();
+        let mut width = size;
+        while end > width {
+            groups.push(&digits[end - width..end]);
+            end -= width;
+            if self.grouping == Grouping::Indian {
+                width = 2;
+            }
+        }
+        groups.push(&digits[..end]);
+        groups.reverse();
+        groups.join(self.group)
+    }
+}
+
+pub const LOCALES: [Locale; 7] = [Locale::PLAIN, Locale::EN, Locale::DE, Locale::FR, Locale::CH, Locale::IN, Locale::RUST];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Notation {
+    Fixed,
+    // one digit before the point: 1.536e3
+    Scientific,
+    // exponents in steps of three, to line up with k, M, G: 1.536e3, 153.6e3
+    Engineering,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Prefix {
+    None,
+    // k M G ... and m µ n ..., powers of 1000
+    Si,
+    // Ki Mi Gi ..., powers of 1024
+    Binary,
+}
+
+const SI: [(i32, &str); 17] = [
+    (24, "Y"), (21, "Z"), (18, "E"), (15, "P"), (12, "T"), (9, "G"), (6, "M"), (3, "k"), (0, ""),
+    (-3, "m"), (-6, "µ"), (-9, "n"), (-12, "p"), (-15, "f"), (-18, "a"), (-21, "z"), (-24, "y"),
+];
+
+const BINARY: [&str; 9] = ["", "Ki", "Mi", "Gi", "Ti", "Pi", "Ei", "Zi", "Yi"];
+
+// without a precision, a fraction in another radix stops here if it hasn't ended by itself
+const RADIX_PLACES: usize = 20;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Precision {
+    // every digit the number has
+    Exact,
+    Places(usize),
+    Significant(usize),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Formatted {
+    number: Number,
+    locale: Locale,
+    notation: Notation,
+    precision: Precision,
+    rounding: Rounding,
+    radix: u32,
+    prefix: Prefix,
+    unit: String,
+}
+
+pub fn number<T: Into<Number>>(value: T) -> Formatted {
+    Formatted {
+        number: value.into(),
+        locale: Locale::PLAIN,
+        notation: Notation::Fixed,
+        precision: Precision::Exact,
+        rounding: Rounding::HalfEven,
+        radix: 10,
+        prefix: Prefix::None,
+        unit: String::new(),
+    }
+}
+
+impl Formatted {
+    pub fn locale(mut self, locale: Locale) -> Formatted {
+        self.locale = locale;
+        self
+    }
+
+    pub fn notation(mut self, notation: Notation) -> Formatted {
+        self.notation = notation;
+        self
+    }
+
+    // exactly this many digits after the point
+    pub fn places(mut self, places: usize) -> Formatted {
+        self.precision = Precision::Places(places);
+        self
+    }
+
+    // exactly this many digits from the first one that isn't zero
+    pub fn significant(mut self, digits: usize) -> Formatted {
+        assert!(digits > 0, "a number needs at least one significant digit");
+        self.precision = Precision::Significant(digits);
+        self
+    }
+
+    // half-even unless this says otherwise
+    pub fn rounding(mut self, rounding: Rounding) -> Formatted {
+        self.rounding = rounding;
+        self
+    }
+
+    // the digits in another base, with `{:#}` adding 0b, 0o or 0x like it does for integers.
+    // exponents and prefixes are about powers of ten, so they're left out in any other radix
+    pub fn radix(mut self, radix: u32) -> Formatted {
+        assert!((2..=36).contains(&radix), "radix must be between 2 and 36, got {}", radix);
+        self.radix = radix;
+        self
+    }
+
+    pub fn prefix(mut self, prefix: Prefix) -> Formatted {
+        self.prefix = prefix;
+        self
+    }
+
+    // goes after the prefix, like the B in KiB
+    pub fn unit(mut self, unit: &str) -> Formatted {
+        self.unit = unit.to_string();
+        self
+    }
+
+    // rounds to the precision, unless every digit is wanted
+    fn round(&self, number: &mut Number) {
+        match self.precision {
+            Precision::Exact => {}
+            Precision::Places(places) => number.round_at(-(places as i32), self.rounding),
+            Precision::Significant(digits) => {
+                if let Some(magnitude) = number.magnitude() {
+                    number.round_at(magnitude + 1 - digits as i32, self.rounding);
+                }
+            }
+        }
+    }
+
+    // the power of ten to take out of the number, and the prefix that stands for it
+    fn scale(&self, number: &Number) -> (i32, &'static str) {
+        let magnitude = match number.magnitude() {
+            Some(magnitude) => magnitude,
+            None => return (0, ""),
+        };
+        match (self.prefix, self.notation) {
+            (Prefix::Si, _) => {
+                let step = (magnitude.div_euclid(3) * 3).clamp(-24, 24);
+                let (_, name) = SI.iter().find(|(power, _)| *power == step).expect("every step has a prefix");
+                (step, name)
+            }
+            (_, Notation::Scientific) => (magnitude, ""),
+            (_, Notation::Engineering) => (magnitude.div_euclid(3) * 3, ""),
+            _ => (0, ""),
+        }
+    }
+
+    fn render(&self) -> String {
+        if let Some(special) = self.number.special {
+            let sign = if self.number.negative { "-" } else { "" };
+            return format!("{}{}", sign, special);
+        }
+        if self.radix != 10 {
+            return self.render_radix();
+        }
+
+        let mut number = self.number.clone();
+        let (power, prefix);
+        if self.prefix == Prefix::Binary {
+            let mut step = 0;
+            while step + 1 < BINARY.len() && number.at_least(1024) {
+                number.div_1024();
+                step += 1;
+            }
+            self.round(&mut number);
+            // 1023.96 rounded can come out as 1024.0, which belongs to the next prefix
+            if step + 1 < BINARY.len() && number.at_least(1024) {
+                number.div_1024();
+                step += 1;
+                self.round(&mut number);
+            }
+            power = 0;
+            prefix = BINARY[step];
+        } else {
+            // significant digits don't depend on where the point goes, so they round first.
+            // places do, so they're rounded again after the point moves
+            if let Precision::Significant(_) = self.precision {
+                self.round(&mut number);
+            }
+            let (mut scale, mut name) = self.scale(&number);
+            number.exponent -= scale;
+            self.round(&mut number);
+            // 9.996e2 to two places is 10.00e2, which should be 1.00e3
+            let mut unscaled = number.clone();
+            unscaled.exponent += scale;
+            let (rescale, rename) = self.scale(&unscaled);
+            if rescale != scale {
+                number.exponent += scale - rescale;
+                self.round(&mut number);
+                scale = rescale;
+                name = rename;
+            }
+            power = scale;
+            prefix = name;
+        }
+
+        let scaled = self.notation != Notation::Fixed || self.prefix != Prefix::None;
+        if scaled && self.precision == Precision::Exact {
+            number.trim_trailing();
+        }
+
+        let (whole, mut fraction) = number.split();
+        let wanted = match (self.precision, number.magnitude()) {
+            (Precision::Places(places), _) => places,
+            (Precision::Significant(digits), Some(magnitude)) => (digits as i32 - 1 - magnitude).max(0) as usize,
+            (Precision::Significant(digits), None) => digits - 1,
+            (Precision::Exact, _) => 0,
+        };
+        while fraction.len() < wanted {
+            fraction.push('0');
+        }
+
+        let mut out = String::new();
+        if self.number.negative {
+            out.push('-');
+        }
+        out.push_str(&self.group(&whole, 3));
+        if !fraction.is_empty() {
+            out.push_str(self.locale.point);
+            out.push_str(&fraction);
+        }
+        if self.prefix == Prefix::None && self.notation != Notation::Fixed {
+            out.push_str(&format!("e{}", power));
+        }
+        if !prefix.is_empty() || !self.unit.is_empty() {
+            out.push_str(&format!(" {}{}", prefix, self.unit));
+        }
+        out
+    }
+
+    fn group(&self, digits: &str, size: usize) -> String {
+        if self.locale.grouping == Grouping::None {
+            digits.to_string()
+        } else {
+            self.locale.group(digits, size)
+        }
+    }
+
+    // the whole part by dividing over and over, the fraction by multiplying over and over, and
+    // the fraction is rounded in the new radix so the carry can reach the whole part
+    fn render_radix(&self) -> String {
+        let radix = self.radix;
+        let (whole, fraction) = self.number.split();
+
+        let mut fraction: Vec<u8> = fraction.trim_end_matches('0').bytes().map(|b| b - b'0').collect();
+        let places = match self.precision {
+            Precision::Places(places) => places,
+            _ => RADIX_PLACES,
+        };
+        let mut converted = Vec::new();
+        while converted.len() < places && !fraction.is_empty() {
+            let length = fraction.len();
+            let carry = mul_small(&mut fraction, radix);
+            // whatever spilled past the point is the next digit
+            fraction.drain(..fraction.len() - length);
+            converted.push(carry as u8);
+            while fraction.last() == Some(&0) {
+                fraction.pop();
+            }
+        }
+
+        // what's left of the decimal fraction is exactly the part being rounded away, as a
+        // share of the last place, so it gets compared with a half in decimal whatever the radix
+        let mut whole: Vec<u8> = whole.bytes().map(|b| b - b'0').collect();
+        let whole_odd = whole.last().is_some_and(|d| d % 2 == 1);
+        let odd = if radix % 2 == 1 {
+            (converted.iter().map(|&d| u32::from(d)).sum::<u32>() + u32::from(whole_odd)) % 2 == 1
+        } else {
+            converted.last().map_or(whole_odd, |d| d % 2 == 1)
+        };
+        let up = rounds_up(against_half(&fraction, 10), !fraction.is_empty(), odd, self.number.negative, self.rounding);
+        if up && increment(&mut converted, radix as u8) {
+            whole.insert(0, 0);
+            increment(&mut whole, 10);
+        }
+        let mut whole_digits = Vec::new();
+        while whole.iter().any(|&d| d != 0) {
+            whole_digits.push(div_small(&mut whole, radix) as u8);
+        }
+        if whole_digits.is_empty() {
+            whole_digits.push(0);
+        }
+        whole_digits.reverse();
+
+        if let Precision::Places(places) = self.precision {
+            converted.resize(places, 0);
+        } else {
+            while converted.last() == Some(&0) {
+                converted.pop();
+            }
+        }
+
+        let digit = |d: &u8| std::char::from_digit(u32::from(*d), radix).expect("below the radix");
+        let whole: String = whole_digits.iter().map(digit).collect();
+        let fraction: String = converted.iter().map(digit).collect();
+        // nibbles read better in fours
+        let size = if radix == 2 || radix == 16 { 4 } else { 3 };
+
+        let mut out = String::new();
+        if self.number.negative {
+            out.push('-');
+        }
+        out.push_str(&self.group(&whole, size));
+        if !fraction.is_empty() {
+            out.push_str(self.locale.point);
+            out.push_str(&fraction);
+        }
+        if !self.unit.is_empty() {
+            out.push_str(&format!(" {}", self.unit));
+        }
+        out
+    }
+}
+
+// width and alignment work the way they do for strings, so `{:>12}` still lines up columns
+impl fmt::Display for Formatted {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut text = self.render();
+        if f.alternate() {
+            let base = match self.radix {
+                2 => "0b",
+                8 => "0o",
+                16 => "0x",
+                _ => "",
+            };
+            let at = if text.starts_with('-') { 1 } else { 0 };
+            text.insert_str(at, base);
+        }
+        f.pad(&text)
+    }
+}
+
+// every way of writing one number, a row each
+fn overview(value: &Formatted) -> Vec<(String, Formatted)> {
+    let mut rows: Vec<(String, Formatted)> = LOCALES.iter().map(|locale| (locale.name.to_string(), value.clone().locale(*locale))).collect();
+    rows.push((String::from("scientific"), value.clone().notation(Notation::Scientific)));
+    rows.push((String::from("engineering"), value.clone().notation(Notation::Engineering)));
+    rows.push((String::from("3 significant"), value.clone().significant(3)));
+    rows.push((String::from("2 places"), value.clone().places(2)));
+    for radix in [2, 8, 16, 36].iter() {
+        rows.push((format!("radix {}", radix), value.clone().radix(*radix)));
+    }
+    rows.push((String::from("si"), value.clone().prefix(Prefix::Si).significant(3)));
+    rows.push((String::from("binary"), value.clone().prefix(Prefix::Binary).significant(3)));
+    rows
+}
+
+fn show(label: &str, rows: Vec<(String, Formatted)>) -> String {
+    let width = rows.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0);
+    let mut out = String::from(label);
+    for (name, value) in rows {
+        out.push_str(&format!("\n    {:<width$}  {}", name, value, width = width));
+    }
+    out
+}
+
+// the numbers from ch3/varsandmutability.rs, each written the way it's usually wanted
+pub fn demo() -> String {
+    let difference = 95.5 - 4.3;
+    let quotient = 56.7 / 32.2;
+    let sections = [
+        show(
+            "const MAX_VAL: u32 = 100_000",
+            LOCALES.iter().map(|locale| (locale.name.to_string(), number(100_000u32).locale(*locale))).collect(),
+        ),
+        show(
+            "95.5 - 4.3",
+            vec![
+                (String::from("{}"), number(difference)),
+                (String::from("2 places"), number(difference).places(2)),
+                (String::from("4 significant"), number(difference).significant(4)),
+                (String::from("times 1000, de"), number(difference * 1000.0).locale(Locale::DE).places(2)),
+            ],
+        ),
+        show(
+            "56.7 / 32.2",
+            vec![
+                (String::from("{}"), number(quotient)),
+                (String::from("scientific"), number(quotient).notation(Notation::Scientific).significant(5)),
+                (String::from("/ 1e4, engineering"), number(quotient / 1e4).notation(Notation::Engineering).significant(5)),
+                (String::from("/ 1e4, si"), number(quotient / 1e4).prefix(Prefix::Si).unit("m").significant(3)),
+            ],
+        ),
+        show(
+            "u32::MAX bytes",
+            vec![
+                (String::from("en"), number(u32::MAX).locale(Locale::EN).unit("B")),
+                (String::from("si"), number(u32::MAX).prefix(Prefix::Si).unit("B").significant(3)),
+                (String::from("binary"), number(u32::MAX).prefix(Prefix::Binary).unit("B").significant(3)),
+                (String::from("radix 16"), number(u32::MAX).radix(16).locale(Locale::RUST)),
+            ],
+        ),
+    ];
+    sections.join("\n\n")
+}
+
+// the value to format: a rust literal (floats become f64, integers keep every digit), or a type
+// name first for the others
+fn parse_value(ty: Option<&str>, text: &str) -> Result<Number, String> {
+    let invalid = |ty: &str| format!("`{}` isn't a valid {}", text, ty);
+    let cleaned = text.replace('_', "");
+    macro_rules! typed {
+        ($t:ty, $name:expr) => {
+            Ok(Number::from(cleaned.parse::<$t>().map_err(|_| invalid($name))?))
+        };
+    }
+    match ty {
+        Some("i8") => typed!(i8, "i8"),
+        Some("i16") => typed!(i16, "i16"),
+        Some("i32") => typed!(i32, "i32"),
+        Some("i64") => typed!(i64, "i64"),
+        Some("i128") => typed!(i128, "i128"),
+        Some("isize") => typed!(isize, "isize"),
+        Some("u8") => typed!(u8, "u8"),
+        Some("u16") => typed!(u16, "u16"),
+        Some("u32") => typed!(u32, "u32"),
+        Some("u64") => typed!(u64, "u64"),
+        Some("u128") => typed!(u128, "u128"),
+        Some("usize") => typed!(usize, "usize"),
+        Some("f32") => typed!(f32, "f32"),
+        Some("f64") => typed!(f64, "f64"),
+        Some("decimal") => Ok(Number::from(text.parse::<Decimal>()?)),
+        Some("big") => Ok(Number::from(text.parse::<BigInt>()?)),
+        Some(other) => Err(format!("unknown type `{}`", other)),
+        None => match crate::literal::parse(text) {
+            Ok(literal) => Ok(match literal.value {
+                crate::literal::Value::Integer(value) => Number::new(literal.negative, &value.to_string(), 0),
+                // floats already carry their sign
+                crate::literal::Value::Float(value) if literal.ty == "f32" => Number::from(value as f32),
+                crate::literal::Value::Float(value) => Number::from(value),
+            }),
+            // too big for u128 is still fine here
+            Err(e) => text.parse::<BigInt>().map(Number::from).map_err(|_| e.message),
+        },
+    }
+}
+
+const VALUE_TYPES: [&str; 16] = [
+    "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize", "f32", "f64", "decimal", "big",
+];
+
+fn count(option: &str, value: &str) -> Result<usize, String> {
+    value.parse().map_err(|_| format!("{} needs a number, got `{}`", option, value))
+}
+
+// `[TYPE] VALUE [OPTION...]`. with no options it shows every way of writing the value,
+// otherwise the options build up one: locale=NAME places=N sig=N sci eng radix=N si binary
+// unit=U round=MODE
+pub fn evaluate(line: &str) -> Result<String, String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let (ty, rest) = match tokens.split_first() {
+        Some((ty, rest)) if VALUE_TYPES.contains(ty) => (Some(*ty), rest),
+        Some(_) => (None, tokens.as_slice()),
+        None => return Err(String::from("nothing to format")),
+    };
+    let (value, options) = rest.split_first().ok_or("expected a value after the type")?;
+    let mut formatted = number(parse_value(ty, value)?);
+
+    if options.is_empty() {
+        return Ok(show(&tokens.join(" "), overview(&formatted)));
+    }
+    for option in options {
+        let (name, value) = option.split_once('=').unwrap_or((option, ""));
+        formatted = match name {
+            "locale" => formatted.locale(Locale::parse(value).ok_or_else(|| {
+                let names: Vec<&str> = LOCALES.iter().map(|locale| locale.name).collect();
+                format!("unknown locale `{}`, try one of: {}", value, names.join(" "))
+            })?),
+            "places" => formatted.places(count(name, value)?),
+            "sig" => match count(name, value)? {
+                0 => return Err(String::from("sig needs at least one digit")),
+                digits => formatted.significant(digits),
+            },
+            "sci" => formatted.notation(Notation::Scientific),
+            "eng" => formatted.notation(Notation::Engineering),
+            "radix" => match count(name, value)? {
+                radix @ 2..=36 => formatted.radix(radix as u32),
+                radix => return Err(format!("radix must be between 2 and 36, got {}", radix)),
+            },
+            "si" => formatted.prefix(Prefix::Si),
+            "binary" => formatted.prefix(Prefix::Binary),
+            "unit" => formatted.unit(value),
+            "round" => formatted.rounding(Rounding::parse(value).ok_or_else(|| format!("unknown rounding `{}`", value))?),
+            other => return Err(format!("unknown option `{}`", other)),
+        };
+    }
+    Ok(formatted.to_string())
+}
@@ -0,0 +1,535 @@
+// measures how big things are and where their parts go, with `size_of`, `align_of` and
+// `offset_of!`, instead of trusting what ch3/varsandmutability.rs says about it. nothing here is
+// worked out by hand: every number comes from the compiler that built this program, so running
+// it on a 32-bit target gives 32-bit answers
+
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem;
+use std::num::NonZeroU32;
+
+// at most this many bytes per row of a diagram, and this many rows
+const ROW: usize = 16;
+const ROWS: usize = 4;
+
+fn bytes(n: usize) -> String {
+    match n {
+        1 => String::from("1 byte"),
+        n => format!("{} bytes", n),
+    }
+}
+
+pub struct Part {
+    // `.1` for a tuple field, `[2]` for an array element, a word for everything else
+    pub name: String,
+    pub ty: String,
+    pub offset: usize,
+    pub size: usize,
+}
+
+pub struct Layout {
+    pub name: String,
+    pub size: usize,
+    pub align: usize,
+    pub parts: Vec<Part>,
+    pub notes: Vec<String>,
+}
+
+impl Layout {
+    fn padding(&self) -> usize {
+        self.size - self.parts.iter().map(|p| p.size).sum::<usize>()
+    }
+
+    // the label drawn in a part's box, kept short so a one byte box still says which part it is
+    fn label(part: &Part) -> String {
+        let name: String = part.name.trim_start_matches('.').chars().filter(|c| *c != '[' && *c != ']').collect();
+        match name.as_str() {
+            "" => part.ty.clone(),
+            name => format!("{} {}", name, part.ty),
+        }
+    }
+
+    // one box per part, three columns per byte, with the byte offsets on top
+    pub fn diagram(&self) -> Vec<String> {
+        let mut owner = vec![None; self.size];
+        for (i, part) in self.parts.iter().enumerate() {
+            for byte in &mut owner[part.offset..part.offset + part.size] {
+                *byte = Some(i);
+            }
+        }
+
+        let mut lines = Vec::new();
+        for row in (0..self.size).step_by(ROW).take(ROWS) {
+            let end = (row + ROW).min(self.size);
+            let offsets: String = (row..end).map(|i| format!("{:<3}", i)).collect();
+            lines.push(format!("  byte {}", offsets.trim_end()));
+
+            let mut boxes = String::new();
+            let mut start = row;
+            while start < end {
+                let mut stop = start + 1;
+                while stop < end && owner[stop] == owner[start] {
+                    stop += 1;
+                }
+                let width = 3 * (stop - start);
+                match owner[start] {
+                    Some(i) => {
+                        let label: String = Layout::label(&self.parts[i]).chars().take(width - 2).collect();
+                        boxes.push_str(&format!("[{:<1$}]", label, width - 2));
+                    }
+                    None => boxes.push_str(&"░".repeat(width)),
+                }
+                start = stop;
+            }
+            lines.push(format!("       {}", boxes));
+        }
+        if self.size > ROW * ROWS {
+            lines.push(format!("       ... {} more bytes laid out the same way", self.size - ROW * ROWS));
+        }
+        lines
+    }
+}
+
+impl fmt::Display for Layout {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}, aligned to {}", self.name, bytes(self.size), self.align)?;
+        match self.padding() {
+            0 => writeln!(f)?,
+            padding => writeln!(f, " ({} of them padding)", padding)?,
+        }
+        if self.size > 0 {
+            for line in self.diagram() {
+                writeln!(f, "{}", line)?;
+            }
+        }
+        if self.parts.len() > 1 && self.parts.len() <= 8 {
+            for part in &self.parts {
+                writeln!(f, "    {:<10} {:<10} bytes {}..{}", part.name, part.ty, part.offset, part.offset + part.size)?;
+            }
+        }
+        for note in &self.notes {
+            writeln!(f, "    {}", note)?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! primitive {
+    ($t:ty) => {
+        Layout {
+            name: stringify!($t).to_string(),
+            size: mem::size_of::<$t>(),
+            align: mem::align_of::<$t>(),
+            parts: vec![Part { name: String::new(), ty: stringify!($t).to_string(), offset: 0, size: mem::size_of::<$t>() }],
+            notes: Vec::new(),
+        }
+    };
+}
+
+// a tuple the way rustc lays it out, and then a `#[repr(C)]` struct with the same fields in the
+// same order, which is what the tuple would look like if the fields weren't reordered
+macro_rules! tuple {
+    ($(($t:ty, $i:tt)),+) => {{
+        type Tuple = ($($t,)+);
+        #[repr(C)]
+        struct Written($($t),+);
+
+        let parts = |offsets: &[usize]| {
+            let mut parts = Vec::new();
+            $(parts.push(Part {
+                name: format!(".{}", $i),
+                ty: stringify!($t).to_string(),
+                offset: offsets[$i],
+                size: mem::size_of::<$t>(),
+            });)+
+            parts
+        };
+        let name = format!("({})", vec![$(stringify!($t)),+].join(", "));
+        let tuple = Layout {
+            name: name.clone(),
+            size: mem::size_of::<Tuple>(),
+            align: mem::align_of::<Tuple>(),
+            parts: parts(&[$(mem::offset_of!(Tuple, $i)),+]),
+            notes: Vec::new(),
+        };
+        let written = Layout {
+            name: format!("{} in the order written, as a #[repr(C)] struct", name),
+            size: mem::size_of::<Written>(),
+            align: mem::align_of::<Written>(),
+            parts: parts(&[$(mem::offset_of!(Written, $i)),+]),
+            notes: Vec::new(),
+        };
+        (tuple, written)
+    }};
+}
+
+// an array of `$n` `$t`s. the element layout is repeated every `size_of::<$t>()` bytes, padding
+// and all, which rust does promise
+macro_rules! array {
+    ($t:ty, $n:literal, $element:expr) => {{
+        let element: Layout = $element;
+        let stride = mem::size_of::<$t>();
+        let mut parts = Vec::new();
+        for i in 0..$n {
+            for part in &element.parts {
+                parts.push(Part { name: format!("[{}]{}", i, part.name), ty: part.ty.clone(), offset: i * stride + part.offset, size: part.size });
+            }
+        }
+        Layout {
+            name: format!("[{}; {}]", stringify!($t), $n),
+            size: mem::size_of::<[$t; $n]>(),
+            align: mem::align_of::<[$t; $n]>(),
+            parts,
+            notes: vec![format!("{} elements, each starting {} after the last, the alignment is the element's", $n, bytes(stride))],
+        }
+    }};
+}
+
+pub fn primitives() -> Vec<Layout> {
+    let mut layouts = vec![
+        primitive!(i8),
+        primitive!(u8),
+        primitive!(i16),
+        primitive!(u16),
+        primitive!(i32),
+        primitive!(u32),
+        primitive!(i64),
+        primitive!(u64),
+        primitive!(i128),
+        primitive!(u128),
+        primitive!(isize),
+        primitive!(usize),
+        primitive!(f32),
+        primitive!(f64),
+        primitive!(bool),
+        primitive!(char),
+        primitive!(()),
+    ];
+
+    for layout in &mut layouts {
+        let note = match layout.name.as_str() {
+            "i128" | "u128" => format!("aligned to {} on this target, older compilers used 8 on x86_64", layout.align),
+            "isize" | "usize" => format!("pointer sized, and this is a {}-bit target, so {} bytes", usize::BITS, layout.size),
+            "bool" => String::from("only 0 and 1 are valid, the other 254 values are a niche Option<bool> can use"),
+            "char" => String::from("any unicode scalar value, up to U+10FFFF, needs 21 bits, so 4 bytes"),
+            "()" => String::from("zero sized, there's nothing to store"),
+            _ => continue,
+        };
+        layout.notes.push(note);
+    }
+    layouts
+}
+
+pub fn tuples() -> Vec<Layout> {
+    let pairs = vec![
+        tuple!((i32, 0), (f64, 1), (u8, 2)),
+        tuple!((u8, 0), (bool, 1), (u32, 2)),
+        tuple!((u8, 0), (u16, 1), (u8, 2), (u64, 3)),
+        tuple!((char, 0), ([u8; 3], 1), (bool, 2)),
+    ];
+
+    let mut layouts = Vec::new();
+    for (mut tuple, written) in pairs {
+        let mut order: Vec<&Part> = tuple.parts.iter().collect();
+        order.sort_by_key(|p| p.offset);
+        let order: Vec<&str> = order.iter().map(|p| p.name.as_str()).collect();
+        tuple.notes.push(format!("rustc put the fields in the order {}", order.join(" ")));
+        if written.size > tuple.size {
+            tuple.notes.push(format!(
+                "in the order written they'd need {} bytes, {} of them padding. reordering saves {}",
+                written.size,
+                written.padding(),
+                written.size - tuple.size
+            ));
+        } else {
+            tuple.notes.push(String::from("the order written doesn't need any more room, so reordering saves nothing here"));
+        }
+        let reordered = tuple.parts.iter().zip(&written.parts).any(|(a, b)| a.offset != b.offset);
+        layouts.push(tuple);
+        if reordered {
+            layouts.push(written);
+        }
+    }
+    layouts
+}
+
+pub fn arrays() -> Vec<Layout> {
+    let (u8_u32, _) = tuple!((u8, 0), (u32, 1));
+    let mut layouts = vec![
+        array!(i32, 5, primitive!(i32)),
+        array!(u8, 3, primitive!(u8)),
+        array!(char, 3, primitive!(char)),
+        array!((u8, u32), 3, u8_u32),
+    ];
+
+    // where things end up: the local ones are next to each other on the stack, what a Box or a
+    // String points to is on the heap, far away
+    let local = 0i32;
+    let array = [0i32; 5];
+    let boxed = Box::new([0i32; 5]);
+    let address = |p: *const i32| p as usize;
+    layouts[0].notes.push(format!(
+        "a local i32 is at {:#x} and a local [i32; 5] at {:#x}, both on the stack. a Box<[i32; 5]> keeps its array at {:#x}, on the heap",
+        address(&local),
+        address(array.as_ptr()),
+        address(boxed.as_ptr())
+    ));
+    layouts
+}
+
+fn words(names: &[&str]) -> Vec<Part> {
+    let word = mem::size_of::<usize>();
+    names.iter().enumerate().map(|(i, name)| Part { name: name.to_string(), ty: String::from("usize"), offset: i * word, size: word }).collect()
+}
+
+// a `&str`, with its two words told apart by looking at one
+fn str_layout() -> Layout {
+    let text = "hello";
+    let str_words: [usize; 2] = unsafe { mem::transmute_copy(&text) };
+    let str_names: Vec<&str> = str_words.iter().map(|w| if *w == text.as_ptr() as usize { "pointer" } else { "length" }).collect();
+    Layout {
+        name: String::from("&str"),
+        size: mem::size_of::<&str>(),
+        align: mem::align_of::<&str>(),
+        parts: words(&str_names),
+        notes: vec![String::from(
+            "a \"fat\" pointer: where the text starts and how many bytes it has. for a literal like \"hello\" the bytes are baked into the program",
+        )],
+    }
+}
+
+pub fn strings() -> Vec<Layout> {
+    // which word is which isn't promised, so look: make a String whose pointer, capacity and
+    // length are all different and find each of them in its bytes
+    let mut string = String::with_capacity(10);
+    string.push_str("hi");
+    // a String is three initialized words, copying them out is fine as long as nothing frees them
+    let string_words: [usize; 3] = unsafe { mem::transmute_copy(&string) };
+    let string_names: Vec<&str> = string_words
+        .iter()
+        .map(|w| match *w {
+            w if w == string.as_ptr() as usize => "pointer",
+            10 => "capacity",
+            2 => "length",
+            _ => "?",
+        })
+        .collect();
+
+    vec![
+        Layout {
+            name: String::from("String"),
+            size: mem::size_of::<String>(),
+            align: mem::align_of::<String>(),
+            parts: words(&string_names),
+            notes: vec![
+                String::from("the text itself is on the heap, this is just what points to it. the order of the words isn't promised"),
+                format!("String::with_capacity(10) holding \"hi\" was {:?}", string_words),
+            ],
+        },
+        str_layout(),
+        Layout {
+            name: String::from("&String"),
+            size: mem::size_of::<&String>(),
+            align: mem::align_of::<&String>(),
+            parts: words(&["pointer"]),
+            notes: vec![String::from("a plain pointer, the length is in the String it points to")],
+        },
+    ]
+}
+
+// something to do with a type that's only known by name, see `with_primitive`
+trait Visit {
+    fn visit<T>(self) -> Option<Measured>;
+}
+
+// (size, alignment, where each field starts) of a tuple, all constants the compiler fills in
+type Measured = (usize, usize, Vec<usize>);
+
+// calls `visit` with the primitive type called `name`, None for any other name
+fn with_primitive<V: Visit>(name: &str, visitor: V) -> Option<Measured> {
+    match name {
+        "i8" => visitor.visit::<i8>(),
+        "i16" => visitor.visit::<i16>(),
+        "i32" => visitor.visit::<i32>(),
+        "i64" => visitor.visit::<i64>(),
+        "i128" => visitor.visit::<i128>(),
+        "isize" => visitor.visit::<isize>(),
+        "u8" => visitor.visit::<u8>(),
+        "u16" => visitor.visit::<u16>(),
+        "u32" => visitor.visit::<u32>(),
+        "u64" => visitor.visit::<u64>(),
+        "u128" => visitor.visit::<u128>(),
+        "usize" => visitor.visit::<usize>(),
+        "f32" => visitor.visit::<f32>(),
+        "f64" => visitor.visit::<f64>(),
+        "bool" => visitor.visit::<bool>(),
+        "char" => visitor.visit::<char>(),
+        "&str" => visitor.visit::<&str>(),
+        _ => None,
+    }
+}
+
+// the tuple type is built up one field at a time: `First` has the names after the first field,
+// `Second<A>` knows the first field is an `A`, and `Third<A, B>` only needs the last one
+struct First<'a>(&'a [&'a str]);
+struct Second<'a, A>(&'a [&'a str], PhantomData<A>);
+struct Third<A, B>(PhantomData<(A, B)>);
+
+impl Visit for First<'_> {
+    fn visit<A>(self) -> Option<Measured> {
+        match self.0 {
+            [] => Some((mem::size_of::<(A,)>(), mem::align_of::<(A,)>(), vec![0])),
+            [b, rest @ ..] => with_primitive(b, Second::<A>(rest, PhantomData)),
+        }
+    }
+}
+
+impl<A> Visit for Second<'_, A> {
+    fn visit<B>(self) -> Option<Measured> {
+        match self.0 {
+            [] => Some((mem::size_of::<(A, B)>(), mem::align_of::<(A, B)>(), vec![mem::offset_of!((A, B), 0), mem::offset_of!((A, B), 1)])),
+            [c] => with_primitive(c, Third::<A, B>(PhantomData)),
+            _ => None,
+        }
+    }
+}
+
+impl<A, B> Visit for Third<A, B> {
+    fn visit<C>(self) -> Option<Measured> {
+        let offsets = vec![mem::offset_of!((A, B, C), 0), mem::offset_of!((A, B, C), 1), mem::offset_of!((A, B, C), 2)];
+        Some((mem::size_of::<(A, B, C)>(), mem::align_of::<(A, B, C)>(), offsets))
+    }
+}
+
+// one of the primitives by name, or `&str`
+pub fn scalar(name: &str) -> Option<Layout> {
+    if name == "&str" {
+        return Some(str_layout());
+    }
+    primitives().into_iter().find(|layout| layout.name == name)
+}
+
+// a tuple of up to three primitives, laid out by the compiler that built this program. every
+// combination of types is measured by its own copy of the code, which is why it stops at three
+pub fn tuple_of(fields: &[&str]) -> Option<Layout> {
+    let (size, align, offsets) = match fields {
+        [] => (0, 1, Vec::new()),
+        [first, rest @ ..] => with_primitive(first, First(rest))?,
+    };
+
+    let mut parts = Vec::new();
+    for (i, (field, offset)) in fields.iter().zip(offsets).enumerate() {
+        parts.push(Part { name: format!(".{}", i), ty: field.to_string(), offset, size: scalar(field)?.size });
+    }
+    Some(Layout { name: format!("({})", fields.join(", ")), size, align, parts, notes: Vec::new() })
+}
+
+// the primitives are one part each, so a table says it better than a diagram
+pub fn primitive_report() -> String {
+    let mut out = format!("    {:<6} {:>5} {:>6}\n", "type", "size", "align");
+    for layout in primitives() {
+        let row = format!("    {:<6} {:>5} {:>6}   {}", layout.name, layout.size, layout.align, layout.notes.join(" "));
+        out.push_str(row.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+pub struct Niche {
+    pub ty: &'static str,
+    pub size: usize,
+    pub option: usize,
+    pub why: &'static str,
+}
+
+macro_rules! niche {
+    ($t:ty, $why:expr) => {
+        Niche { ty: stringify!($t), size: mem::size_of::<$t>(), option: mem::size_of::<Option<$t>>(), why: $why }
+    };
+}
+
+pub fn niches() -> Vec<Niche> {
+    vec![
+        niche!(&i32, "a reference is never null, so None is stored as a null pointer"),
+        niche!(Box<i32>, "a Box is never null either"),
+        niche!(&str, "the pointer half is never null"),
+        niche!(String, "its pointer is never null"),
+        niche!(NonZeroU32, "0 isn't a valid value, so None is 0"),
+        niche!(bool, "only 0 and 1 are used, None is 2"),
+        niche!(char, "nothing above U+10FFFF is valid, None is 0x110000"),
+        niche!(u8, "every bit pattern is a valid u8, so a separate tag byte is needed"),
+        niche!(i32, "every bit pattern is valid, the tag is padded out to the i32's alignment"),
+        niche!(f64, "every bit pattern is a valid f64 (NaNs included)"),
+    ]
+}
+
+// what None actually looks like in memory for a few of them. Option<&T>'s is promised to be a
+// null pointer, the others aren't promised but the bytes are all initialized, so reading them is
+// fine
+fn nones() -> Vec<String> {
+    let reference: usize = unsafe { mem::transmute(None::<&i32>) };
+    let boolean: u8 = unsafe { mem::transmute(None::<bool>) };
+    let character: u32 = unsafe { mem::transmute(None::<char>) };
+    vec![
+        format!("None::<&i32> is the address {:#x}", reference),
+        format!("None::<bool> is the byte {}", boolean),
+        format!("None::<char> is {:#x}", character),
+    ]
+}
+
+pub fn niche_report() -> String {
+    let mut out = String::from("Option<T> for a T with a niche (bit patterns T never uses) costs nothing extra\n");
+    out.push_str(&format!("    {:<12} {:>8} {:>11}\n", "T", "size_of", "Option<T>"));
+    for niche in niches() {
+        let extra = match niche.option - niche.size {
+            0 => String::from("same size,"),
+            extra => format!("+{},", bytes(extra)),
+        };
+        out.push_str(&format!("    {:<12} {:>8} {:>11}   {} {}\n", niche.ty, niche.size, niche.option, extra, niche.why));
+    }
+    for none in nones() {
+        out.push_str(&format!("    {}\n", none));
+    }
+    out
+}
+
+pub const SECTIONS: [&str; 5] = ["primitives", "tuples", "arrays", "strings", "niches"];
+
+fn section(name: &str) -> Option<String> {
+    let layouts = match name {
+        "primitives" => return Some(primitive_report()),
+        "tuples" => tuples(),
+        "arrays" => arrays(),
+        "strings" => strings(),
+        "niches" => return Some(niche_report()),
+        _ => return None,
+    };
+    Some(layouts.iter().map(Layout::to_string).collect::<Vec<String>>().join("\n"))
+}
+
+// a section, `all`, or one of the types by name, like `(i32, f64, u8)`
+pub fn evaluate(query: &str) -> Result<String, String> {
+    let query = query.trim();
+    if query.is_empty() || query == "all" {
+        let sections: Vec<String> = SECTIONS.iter().filter_map(|name| section(name)).collect();
+        return Ok(sections.join("\n"));
+    }
+    if let Some(report) = section(query) {
+        return Ok(report);
+    }
+
+    let squash = |s: &str| s.split_whitespace().collect::<String>();
+    let everything = primitives().into_iter().chain(tuples()).chain(arrays()).chain(strings());
+    let found: Vec<String> = everything.filter(|layout| squash(&layout.name) == squash(query)).map(|layout| layout.to_string()).collect();
+    if !found.is_empty() {
+        return Ok(found.join("\n"));
+    }
+    if let Some(niche) = niches().into_iter().find(|n| squash(n.ty) == squash(query.trim_start_matches("Option<").trim_end_matches('>'))) {
+        return Ok(format!("{} is {} and Option<{}> is {}: {}\n", niche.ty, bytes(niche.size), niche.ty, bytes(niche.option), niche.why));
+    }
+    Err(format!(
+        "`{}` isn't one of the measured types. try a section ({}) or a type from one of them",
+        query,
+        SECTIONS.join(", ")
+    ))
+}
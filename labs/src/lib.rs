@@ -0,0 +1,23 @@
+// small interactive labs built on top of the notes in ch3. each module has a matching
+// binary in src/bin
+
+pub mod bigint;
+pub mod bindings;
+pub mod bits;
+pub mod calc;
+pub mod cast;
+pub mod chars;
+pub mod compound;
+pub mod consts;
+pub mod countdown;
+pub mod decimal;
+pub mod float;
+pub mod format;
+pub mod layout;
+pub mod literal;
+pub mod numerals;
+pub mod overflow;
+pub mod policy;
+pub mod primes;
+pub mod ratio;
+pub mod rules;
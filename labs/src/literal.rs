@@ -0,0 +1,527 @@
+// parses rust number literals, all the forms from the table in ch3/varsandmutability.rs:
+//
+//     NUMBER LITERALS         EXAMPLE
+//     --------------------------------
+//     Decimal                 98_222
+//     Hex                     0xff
+//     Octal                   0o77
+//     Binary                  0b1111_0000
+//     Byte (use `u8` only)    b'A'
+//
+// plus floats (`1e3`, `2.5f32`) and type suffixes (`255u8`). errors are printed the way rustc
+// prints them, with a caret under the part of the literal that's wrong
+
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Base {
+    Binary,
+    Octal,
+    Decimal,
+    Hex,
+}
+
+impl Base {
+    fn radix(self) -> u32 {
+        match self {
+            Base::Binary => 2,
+            Base::Octal => 8,
+            Base::Decimal => 10,
+            Base::Hex => 16,
+        }
+    }
+
+    fn prefix(self) -> &'static str {
+        match self {
+            Base::Binary => "0b",
+            Base::Octal => "0o",
+            Base::Decimal => "",
+            Base::Hex => "0x",
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Base::Binary => "binary",
+            Base::Octal => "octal",
+            Base::Decimal => "decimal",
+            Base::Hex => "hexadecimal",
+        }
+    }
+
+    // how many digits go between underscores unless told otherwise
+    pub fn default_group(self) -> usize {
+        match self {
+            Base::Binary | Base::Hex => 4,
+            Base::Octal | Base::Decimal => 3,
+        }
+    }
+}
+
+pub const INTEGER_TYPES: [&str; 12] = [
+    "i8", "u8", "i16", "u16", "i32", "u32", "i64", "u64", "i128", "u128", "isize", "usize",
+];
+
+// (bits, signed) for an integer type name
+pub(crate) fn integer_type(name: &str) -> Option<(u32, bool)> {
+    let bits = match &name[1..] {
+        "8" => 8,
+        "16" => 16,
+        "32" => 32,
+        "64" => 64,
+        "128" => 128,
+        "size" => usize::BITS,
+        _ => return None,
+    };
+    match &name[..1] {
+        "i" => Some((bits, true)),
+        "u" => Some((bits, false)),
+        _ => None,
+    }
+}
+
+// does `-magnitude` (or `magnitude`) fit in the type?
+fn integer_fits(magnitude: u128, negative: bool, ty: &str) -> bool {
+    let (bits, signed) = integer_type(ty).expect("not an integer type");
+    let max_positive = if signed { u128::MAX >> (129 - bits) } else { u128::MAX >> (128 - bits) };
+    if negative {
+        // the negative side of a signed type goes one further than the positive side
+        signed && magnitude <= max_positive + 1
+    } else {
+        magnitude <= max_positive
+    }
+}
+
+fn integer_range(ty: &str) -> String {
+    let (bits, signed) = integer_type(ty).expect("not an integer type");
+    if signed {
+        let max = u128::MAX >> (129 - bits);
+        format!("-{}..={}", max + 1, max)
+    } else {
+        format!("0..={}", u128::MAX >> (128 - bits))
+    }
+}
+
+// a rustc-style error pointing at `start..end` of the literal
+#[derive(Debug)]
+pub struct LiteralError {
+    // like `E0308`, for the errors that have one
+    pub code: Option<&'static str>,
+    pub message: String,
+    pub start: usize,
+    pub end: usize,
+    pub label: String,
+    pub notes: Vec<String>,
+    // lints like `non_upper_case_globals` are warnings, everything else stops the build
+    pub warning: bool,
+}
+
+impl LiteralError {
+    pub(crate) fn new(message: String, start: usize, end: usize) -> LiteralError {
+        LiteralError {
+            code: None,
+            message,
+            start,
+            end,
+            label: String::new(),
+            notes: Vec::new(),
+            warning: false,
+        }
+    }
+
+    pub(crate) fn code(mut self, code: &'static str) -> LiteralError {
+        self.code = Some(code);
+        self
+    }
+
+    pub(crate) fn label(mut self, label: &str) -> LiteralError {
+        self.label = label.to_string();
+        self
+    }
+
+    pub(crate) fn note(mut self, note: String) -> LiteralError {
+        self.notes.push(note);
+        self
+    }
+
+    pub(crate) fn warning(mut self) -> LiteralError {
+        self.warning = true;
+        self
+    }
+
+    pub fn render(&self, source: &str) -> String {
+        self.render_line(1, source, 0)
+    }
+
+    // for sources with more than one line: `line` is line `number` of the source and starts at
+    // byte `line_start`. `start` and `end` are byte offsets, but the caret line has to be
+    // counted in chars, and stops at the end of the line
+    pub(crate) fn render_line(&self, number: usize, line: &str, line_start: usize) -> String {
+        let start = (self.start - line_start).min(line.len());
+        let end = (self.end - line_start).clamp(start, line.len());
+        let before = line[..start].chars().count();
+        let width = line[start..end].chars().count().max(1);
+
+        let caret = format!("{}{} {}", " ".repeat(before), "^".repeat(width), self.label);
+        let level = if self.warning { "warning" } else { "error" };
+        let heading = match self.code {
+            Some(code) => format!("{}[{}]", level, code),
+            None => level.to_string(),
+        };
+        let gutter = " ".repeat(number.to_string().len());
+        let mut out = format!(
+            "{}: {}\n{} |\n{} | {}\n{} | {}\n",
+            heading, self.message, gutter, number, line, gutter, caret.trim_end()
+        );
+        for note in &self.notes {
+            out.push_str(&format!("{} = {}\n", gutter, note));
+        }
+        out.trim_end().to_string()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    Integer(u128),
+    Float(f64),
+}
+
+#[derive(Debug)]
+pub struct Literal {
+    pub source: String,
+    pub negative: bool,
+    pub base: Base,
+    pub value: Value,
+    pub ty: String,
+    // false means the type came from the default (i32 or f64), not a suffix
+    pub suffixed: bool,
+    pub byte: bool,
+}
+
+fn is_suffix(s: &str) -> bool {
+    s == "f32" || s == "f64" || INTEGER_TYPES.contains(&s)
+}
+
+fn suffix_error(source: &str, suffix_start: usize, kind: &str) -> LiteralError {
+    let suffix = &source[suffix_start..];
+    let help = if kind == "float" {
+        "help: valid suffixes are `f32` and `f64`"
+    } else {
+        "help: the suffix must be one of the numeric types (`u32`, `isize`, `f32`, etc.)"
+    };
+    LiteralError::new(format!("invalid suffix `{}` for {} literal", suffix, kind), suffix_start, source.len())
+        .label(&format!("invalid suffix `{}`", suffix))
+        .note(help.to_string())
+}
+
+// the byte an escape like `\n` or `\x41` stands for
+fn parse_byte_escape(body: &str, start: usize) -> Result<u8, LiteralError> {
+    let escape = &body[1..];
+    let byte = match escape {
+        "n" => b'\n',
+        "r" => b'\r',
+        "t" => b'\t',
+        "\\" => b'\\',
+        "0" => b'\0',
+        "'" => b'\'',
+        "\"" => b'"',
+        _ if escape.starts_with('x') && escape.len() == 3 => u8::from_str_radix(&escape[1..], 16)
+            .map_err(|_| LiteralError::new(String::from("invalid character in numeric character escape"), start, start + body.len()))?,
+        _ => {
+            return Err(LiteralError::new(format!("unknown byte escape: `{}`", escape), start, start + body.len())
+                .label("unknown byte escape"))
+        }
+    };
+    Ok(byte)
+}
+
+fn parse_byte(source: &str, offset: usize) -> Result<u8, LiteralError> {
+    let text = &source[offset..];
+    if !text.ends_with('\'') || text.len() < 4 {
+        return Err(LiteralError::new(String::from("unterminated byte constant"), offset, source.len()));
+    }
+
+    let body_start = offset + 2;
+    let body = &text[2..text.len() - 1];
+    if body.starts_with('\\') {
+        return parse_byte_escape(body, body_start);
+    }
+
+    let mut chars = body.chars();
+    let c = chars.next().expect("checked the length above");
+    if chars.next().is_some() {
+        return Err(LiteralError::new(String::from("character literal may only contain one codepoint"), body_start, body_start + body.len()));
+    }
+    if !c.is_ascii() {
+        let mut error = LiteralError::new(String::from("non-ASCII character in byte literal"), body_start, body_start + c.len_utf8())
+            .label("must be ASCII");
+        if (c as u32) <= 0xff {
+            error = error.note(format!("help: if you meant to use the unicode code point for {:?}, use a \\xHH escape: b'\\x{:X}'", c, c as u32));
+        }
+        return Err(error);
+    }
+    Ok(c as u8)
+}
+
+pub fn parse(source: &str) -> Result<Literal, LiteralError> {
+    let source = source.trim();
+    // a leading `-` isn't part of the literal in rust, it's the negation operator, but
+    // `-128i8` is common enough that we handle it (and check the range the way rustc does)
+    let negative = source.starts_with('-');
+    let start = if negative { 1 } else { 0 };
+
+    if source[start..].starts_with("b'") {
+        let byte = parse_byte(source, start)?;
+        return Ok(Literal {
+            source: source.to_string(),
+            negative,
+            base: Base::Decimal,
+            value: Value::Integer(byte as u128),
+            ty: String::from("u8"),
+            suffixed: true,
+            byte: true,
+        });
+    }
+
+    let rest = &source[start..];
+    let (base, digits_start) = match rest.get(..2) {
+        Some("0x") => (Base::Hex, start + 2),
+        Some("0o") => (Base::Octal, start + 2),
+        Some("0b") => (Base::Binary, start + 2),
+        _ => (Base::Decimal, start),
+    };
+
+    let bytes = source.as_bytes();
+    let mut i = digits_start;
+    let digit_ok = |b: u8| if base == Base::Hex { b.is_ascii_hexdigit() || b == b'_' } else { b.is_ascii_digit() || b == b'_' };
+    while i < bytes.len() && digit_ok(bytes[i]) {
+        i += 1;
+    }
+    let mut is_float = false;
+
+    if base == Base::Decimal {
+        // fraction: `2.5`, and `2.` on its own is allowed too
+        if i < bytes.len() && bytes[i] == b'.' && (i + 1 == bytes.len() || bytes[i + 1].is_ascii_digit()) {
+            is_float = true;
+            i += 1;
+            while i < bytes.len() && (bytes[i].is_ascii_digit() || bytes[i] == b'_') {
+                i += 1;
+            }
+        }
+        // exponent: `1e3`, `1E-7`
+        if i < bytes.len() && (bytes[i] == b'e' || bytes[i] == b'E') {
+            let mut j = i + 1;
+            if j < bytes.len() && (bytes[j] == b'+' || bytes[j] == b'-') {
+                j += 1;
+            }
+            let exponent_start = j;
+            while j < bytes.len() && (bytes[j].is_ascii_digit() || bytes[j] == b'_') {
+                j += 1;
+            }
+            if !source[exponent_start..j].chars().any(|c| c.is_ascii_digit()) {
+                return Err(LiteralError::new(String::from("expected at least one digit in exponent"), i, j));
+            }
+            is_float = true;
+            i = j;
+        }
+    }
+
+    let digits_end = i;
+    let suffix = &source[digits_end..];
+    let digits: String = source[digits_start..digits_end].chars().filter(|c| *c != '_').collect();
+
+    if digits.is_empty() {
+        return Err(LiteralError::new(String::from("no valid digits found for number"), start, source.len()));
+    }
+    if !suffix.is_empty() && !is_suffix(suffix) {
+        return Err(suffix_error(source, digits_end, if is_float { "float" } else { "number" }));
+    }
+
+    let float_suffix = suffix == "f32" || suffix == "f64";
+    if is_float && !suffix.is_empty() && !float_suffix {
+        return Err(suffix_error(source, digits_end, "float"));
+    }
+    if float_suffix && base != Base::Decimal {
+        return Err(LiteralError::new(format!("{} float literal is not supported", base.name()), start, source.len()));
+    }
+
+    if is_float || float_suffix {
+        let ty = if suffix.is_empty() { "f64" } else { suffix };
+        let value = if ty == "f32" {
+            digits.parse::<f32>().map(|v| v as f64)
+        } else {
+            digits.parse::<f64>()
+        };
+        let value = value.map_err(|_| LiteralError::new(String::from("invalid float literal"), start, digits_end))?;
+
+        return Ok(Literal {
+            source: source.to_string(),
+            negative,
+            base,
+            value: Value::Float(if negative { -value } else { value }),
+            ty: ty.to_string(),
+            suffixed: !suffix.is_empty(),
+            byte: false,
+        });
+    }
+
+    // binary and octal take any decimal digit above so that we can point at the bad one
+    let radix = base.radix();
+    let mut magnitude: u128 = 0;
+    for (offset, c) in source[digits_start..digits_end].char_indices() {
+        if c == '_' {
+            continue;
+        }
+        let digit = c.to_digit(radix).ok_or_else(|| {
+            let at = digits_start + offset;
+            LiteralError::new(format!("invalid digit for a base {} literal", radix), at, at + 1)
+        })?;
+        magnitude = magnitude
+            .checked_mul(radix as u128)
+            .and_then(|m| m.checked_add(digit as u128))
+            .ok_or_else(|| {
+                LiteralError::new(String::from("integer literal is too large"), start, source.len())
+                    .note(format!("note: value exceeds limit of `{}`", u128::MAX))
+            })?;
+    }
+
+    Ok(Literal {
+        source: source.to_string(),
+        negative,
+        base,
+        value: Value::Integer(magnitude),
+        ty: if suffix.is_empty() { String::from("i32") } else { suffix.to_string() },
+        suffixed: !suffix.is_empty(),
+        byte: false,
+    })
+}
+
+// writes `magnitude` in `base` with an underscore every `group` digits (0 for none)
+pub fn format_integer(magnitude: u128, negative: bool, base: Base, group: usize) -> String {
+    let digits = match base {
+        Base::Binary => format!("{:b}", magnitude),
+        Base::Octal => format!("{:o}", magnitude),
+        Base::Decimal => format!("{}", magnitude),
+        Base::Hex => format!("{:x}", magnitude),
+    };
+
+    let mut grouped = String::new();
+    for (i, c) in digits.chars().enumerate() {
+        if group > 0 && i > 0 && (digits.len() - i) % group == 0 {
+            grouped.push('_');
+        }
+        grouped.push(c);
+    }
+
+    format!("{}{}{}", if negative && magnitude != 0 { "-" } else { "" }, base.prefix(), grouped)
+}
+
+fn byte_literal(byte: u8) -> String {
+    match byte {
+        b'\n' => String::from("b'\\n'"),
+        b'\r' => String::from("b'\\r'"),
+        b'\t' => String::from("b'\\t'"),
+        b'\\' => String::from("b'\\\\'"),
+        b'\'' => String::from("b'\\''"),
+        0 => String::from("b'\\0'"),
+        0x20..=0x7e => format!("b'{}'", byte as char),
+        _ => format!("b'\\x{:02x}'", byte),
+    }
+}
+
+impl Literal {
+    pub fn fits(&self) -> bool {
+        match self.value {
+            Value::Integer(magnitude) => integer_fits(magnitude, self.negative, &self.ty),
+            Value::Float(value) => value.is_finite(),
+        }
+    }
+
+    // the smallest type the value would fit in, for the "consider using" help. like rustc it
+    // sticks to the same signedness, except that a positive value may move to unsigned
+    fn smallest_fitting_type(&self) -> Option<&'static str> {
+        let magnitude = match self.value {
+            Value::Integer(magnitude) => magnitude,
+            Value::Float(_) => return None,
+        };
+        let (_, signed) = integer_type(&self.ty)?;
+
+        let widths = [("i8", "u8"), ("i16", "u16"), ("i32", "u32"), ("i64", "u64"), ("i128", "u128")];
+        widths
+            .iter()
+            .flat_map(|&(i, u)| {
+                let signed_choice = if signed { Some(i) } else { None };
+                let unsigned_choice = if self.negative { None } else { Some(u) };
+                signed_choice.into_iter().chain(unsigned_choice)
+            })
+            .find(|ty| integer_fits(magnitude, self.negative, ty))
+    }
+
+    // the deny-by-default `overflowing_literals` lint, if it would fire
+    pub fn range_error(&self) -> Option<LiteralError> {
+        if self.fits() {
+            return None;
+        }
+
+        let source = &self.source;
+        if self.negative && integer_type(&self.ty).is_some_and(|(_, signed)| !signed) {
+            return Some(LiteralError::new(format!("cannot apply unary operator `-` to type `{}`", self.ty), 0, source.len())
+                .label("cannot apply unary operator `-`")
+                .note(String::from("note: unsigned values cannot be negated")));
+        }
+
+        let error = LiteralError::new(format!("literal out of range for `{}`", self.ty), 0, source.len());
+        let error = match self.value {
+            Value::Integer(_) => {
+                let mut error = error.note(format!("note: the literal `{}` does not fit into the type `{}` whose range is `{}`", source, self.ty, integer_range(&self.ty)));
+                if let Some(ty) = self.smallest_fitting_type() {
+                    error = error.note(format!("help: consider using the type `{}` instead", ty));
+                }
+                error
+            }
+            Value::Float(_) => error.note(format!("note: the literal `{}` does not fit into the type `{}` and will be converted to `{}::INFINITY`", source, self.ty, self.ty)),
+        };
+        Some(error.note(String::from("note: `#[deny(overflowing_literals)]` on by default")))
+    }
+
+    // every base, with `group` digits between underscores (None for each base's default)
+    pub fn conversions(&self, group: Option<usize>) -> Vec<(&'static str, String)> {
+        let magnitude = match self.value {
+            Value::Integer(magnitude) => magnitude,
+            Value::Float(value) => return vec![("scientific", format!("{:e}", value)), ("debug", format!("{:?}", value))],
+        };
+
+        let mut rows: Vec<(&'static str, String)> = [Base::Decimal, Base::Hex, Base::Octal, Base::Binary]
+            .iter()
+            .map(|&base| (base.name(), format_integer(magnitude, self.negative, base, group.unwrap_or_else(|| base.default_group()))))
+            .collect();
+
+        if !self.negative && magnitude <= u8::MAX as u128 {
+            rows.push(("byte", byte_literal(magnitude as u8)));
+        }
+        rows
+    }
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let value = match self.value {
+            Value::Integer(magnitude) => format!("{}{}", if self.negative && magnitude != 0 { "-" } else { "" }, magnitude),
+            Value::Float(value) => format!("{}", value),
+        };
+        let how = match (self.suffixed, self.byte) {
+            (_, true) => "byte literals are always u8",
+            (true, false) => "from the suffix",
+            (false, false) => "no suffix, so the default",
+        };
+
+        writeln!(f, "literal     {}", self.source)?;
+        writeln!(f, "value       {}", value)?;
+        writeln!(f, "type        {} ({})", self.ty, how)?;
+        match (&self.value, self.fits()) {
+            (Value::Integer(_), true) => write!(f, "fits        yes, {} is {}", self.ty, integer_range(&self.ty)),
+            (Value::Float(_), true) => write!(f, "fits        yes"),
+            (_, false) => write!(f, "fits        no"),
+        }
+    }
+}
@@ -0,0 +1,500 @@
+// other ways of writing the integers from ch3: roman numerals, english words, ordinals and
+// balanced ternary, each with a parser that turns it back into a number. the parsers are strict:
+// they only take what the writer would have produced (give or take case, spaces, hyphens and
+// an "and"), so a round trip always gives back the same text
+//
+// the ranges are what each system can say: roman numerals 1 to 3999, words and balanced ternary
+// all of i128, ordinals all of u128
+
+use std::convert::TryFrom;
+
+const ROMAN: [(u32, &str); 13] = [
+    (1000, "M"), (900, "CM"), (500, "D"), (400, "CD"), (100, "C"), (90, "XC"), (50, "L"),
+    (40, "XL"), (10, "X"), (9, "IX"), (5, "V"), (4, "IV"), (1, "I"),
+];
+
+pub fn to_roman(n: u32) -> Result<String, String> {
+    if !(1..=3999).contains(&n) {
+        return Err(format!("roman numerals only go from 1 to 3999, not {}", n));
+    }
+    let mut rest = n;
+    let mut out = String::new();
+    for (value, symbol) in ROMAN.iter() {
+        while rest >= *value {
+            out.push_str(symbol);
+            rest -= value;
+        }
+    }
+    Ok(out)
+}
+
+// reads the symbols greedily, then writes the number back out: anything that doesn't come out
+// the same, like IIII or IC, isn't a proper numeral even though its value is obvious
+pub fn parse_roman(text: &str) -> Result<u32, String> {
+    let upper = text.trim().to_uppercase();
+    if upper.is_empty() || !upper.chars().all(|c| "IVXLCDM".contains(c)) {
+        return Err(format!("`{}` isn't a roman numeral, they only use I V X L C D M", text.trim()));
+    }
+    let mut rest = upper.as_str();
+    let mut total = 0;
+    while !rest.is_empty() {
+        let (value, symbol) = ROMAN.iter().find(|(_, symbol)| rest.starts_with(symbol)).expect("every letter is a symbol");
+        total += value;
+        rest = &rest[symbol.len()..];
+    }
+    match to_roman(total) {
+        Ok(written) if written == upper => Ok(total),
+        Ok(written) => Err(format!("`{}` isn't how roman numerals write {}, that's `{}`", text.trim(), total, written)),
+        Err(e) => Err(e),
+    }
+}
+
+const ONES: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+
+const TENS: [&str; 10] = ["", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety"];
+
+// short scale, a thousand times bigger each step. u128 tops out in the undecillions
+const SCALES: [&str; 13] = [
+    "", "thousand", "million", "billion", "trillion", "quadrillion", "quintillion", "sextillion",
+    "septillion", "octillion", "nonillion", "decillion", "undecillion",
+];
+
+// 1 to 999
+fn group_words(n: u128) -> String {
+    let mut parts = Vec::new();
+    if n >= 100 {
+        parts.push(format!("{} hundred", ONES[(n / 100) as usize]));
+    }
+    match n % 100 {
+        0 => {}
+        rest @ 1..=19 => parts.push(ONES[rest as usize].to_string()),
+        rest if rest % 10 == 0 => parts.push(TENS[(rest / 10) as usize].to_string()),
+        rest => parts.push(format!("{}-{}", TENS[(rest / 10) as usize], ONES[(rest % 10) as usize])),
+    }
+    parts.join(" ")
+}
+
+fn magnitude_words(n: u128) -> String {
+    if n == 0 {
+        return String::from("zero");
+    }
+    let mut groups = Vec::new();
+    let mut rest = n;
+    let mut scale = 0;
+    while rest > 0 {
+        let group = rest % 1000;
+        if group > 0 {
+            let words = group_words(group);
+            groups.push(if scale == 0 { words } else { format!("{} {}", words, SCALES[scale]) });
+        }
+        rest /= 1000;
+        scale += 1;
+    }
+    groups.reverse();
+    groups.join(" ")
+}
+
+// 98222 is "ninety-eight thousand two hundred twenty-two", with no "and"
+pub fn to_words(n: i128) -> String {
+    let words = magnitude_words(n.unsigned_abs());
+    if n < 0 {
+        format!("minus {}", words)
+    } else {
+        words
+    }
+}
+
+// where the parser is inside one group of three digits, which decides what can come next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Place {
+    // at the start of a group
+    Start,
+    // after "forty", only "two" and the like can follow
+    Tens,
+    // after "two" or "twelve": "hundred" or a scale
+    Ones,
+    // after "hundred"
+    Hundred,
+}
+
+fn word_value(word: &str) -> Option<(u128, bool)> {
+    if let Some(i) = ONES.iter().position(|w| *w == word) {
+        return Some((i as u128, false));
+    }
+    TENS.iter().position(|w| !w.is_empty() && *w == word).map(|i| (i as u128 * 10, true))
+}
+
+// the words for a number back to (negative, magnitude). "forty two", "Forty-Two" and
+// "one hundred and five" all work, "two forty" and "thousand million" don't
+fn parse_magnitude(text: &str) -> Result<(bool, u128), String> {
+    let lowered = text.trim().to_lowercase().replace(['-', ','], " ");
+    let mut words: Vec<&str> = lowered.split_whitespace().filter(|w| *w != "and").collect();
+    let negative = matches!(words.first(), Some(&"minus") | Some(&"negative"));
+    if negative {
+        words.remove(0);
+    }
+    match words.as_slice() {
+        [] => return Err(String::from("there are no number words here")),
+        ["zero"] => return Ok((negative, 0)),
+        _ => {}
+    }
+
+    let too_big = || format!("`{}` is too big", text.trim());
+    let mut total: u128 = 0;
+    let mut group: u128 = 0;
+    let mut place = Place::Start;
+    let mut last_scale = SCALES.len();
+    let mut previous = "";
+    for word in words {
+        let misplaced = || {
+            if previous.is_empty() {
+                format!("`{}` can't start a number", word)
+            } else {
+                format!("`{}` can't come after `{}`", word, previous)
+            }
+        };
+        if let Some((value, tens)) = word_value(word) {
+            place = match (place, tens, value) {
+                (_, _, 0) => return Err(String::from("`zero` only works on its own")),
+                (Place::Start, true, _) | (Place::Hundred, true, _) => Place::Tens,
+                (Place::Start, false, _) | (Place::Hundred, false, _) => Place::Ones,
+                (Place::Tens, false, 1..=9) => Place::Ones,
+                _ => return Err(misplaced()),
+            };
+            group += value;
+        } else if word == "hundred" {
+            if place != Place::Ones || group >= 10 {
+                return Err(misplaced());
+            }
+            group *= 100;
+            place = Place::Hundred;
+        } else if let Some(scale) = SCALES.iter().position(|s| !s.is_empty() && *s == word) {
+            // each scale has to be smaller than the one before, and say how many of it there are
+            if place == Place::Start || scale >= last_scale {
+                return Err(misplaced());
+            }
+            let size = 1000u128.checked_pow(scale as u32).ok_or_else(too_big)?;
+            total = group.checked_mul(size).and_then(|v| v.checked_add(total)).ok_or_else(too_big)?;
+            group = 0;
+            place = Place::Start;
+            last_scale = scale;
+        } else {
+            return Err(format!("`{}` isn't a number word", word));
+        }
+        previous = word;
+    }
+    Ok((negative, total.checked_add(group).ok_or_else(too_big)?))
+}
+
+pub fn parse_words(text: &str) -> Result<i128, String> {
+    let (negative, magnitude) = parse_magnitude(text)?;
+    let out_of_range = || format!("`{}` doesn't fit in an i128", text.trim());
+    if negative {
+        0i128.checked_sub_unsigned(magnitude).ok_or_else(out_of_range)
+    } else {
+        i128::try_from(magnitude).map_err(|_| out_of_range())
+    }
+}
+
+// 1st 2nd 3rd, except 11th 12th 13th
+fn suffix(n: u128) -> &'static str {
+    match (n % 10, n % 100) {
+        (_, 11..=13) => "th",
+        (1, _) => "st",
+        (2, _) => "nd",
+        (3, _) => "rd",
+        _ => "th",
+    }
+}
+
+pub fn to_ordinal(n: u128) -> String {
+    format!("{}{}", n, suffix(n))
+}
+
+pub fn parse_ordinal(text: &str) -> Result<u128, String> {
+    let text = text.trim();
+    let split = text.find(|c: char| !c.is_ascii_digit()).unwrap_or(text.len());
+    let (digits, ending) = text.split_at(split);
+    let n: u128 = digits.parse().map_err(|_| format!("`{}` isn't an ordinal like 1st or 42nd", text))?;
+    if ending.to_lowercase() != suffix(n) {
+        return Err(format!("`{}` should be `{}`", text, to_ordinal(n)));
+    }
+    Ok(n)
+}
+
+// the last word changes: one → first, twenty → twentieth, hundred → hundredth
+const IRREGULAR: [(&str, &str); 6] = [
+    ("one", "first"), ("two", "second"), ("three", "third"), ("five", "fifth"), ("eight", "eighth"),
+    ("nine", "ninth"),
+];
+
+fn ordinal_word(word: &str) -> String {
+    if let Some((_, ordinal)) = IRREGULAR.iter().find(|(cardinal, _)| *cardinal == word) {
+        ordinal.to_string()
+    } else if word == "twelve" {
+        String::from("twelfth")
+    } else if let Some(stem) = word.strip_suffix('y') {
+        format!("{}ieth", stem)
+    } else {
+        format!("{}th", word)
+    }
+}
+
+fn cardinal_word(word: &str) -> Option<String> {
+    if let Some((cardinal, _)) = IRREGULAR.iter().find(|(_, ordinal)| *ordinal == word) {
+        Some(cardinal.to_string())
+    } else if word == "twelfth" {
+        Some(String::from("twelve"))
+    } else if let Some(stem) = word.strip_suffix("ieth") {
+        Some(format!("{}y", stem))
+    } else {
+        word.strip_suffix("th").map(String::from)
+    }
+}
+
+pub fn to_ordinal_words(n: u128) -> String {
+    let words = magnitude_words(n);
+    // the last word comes after the last space or hyphen, as in "ninety-eighth"
+    let at = words.rfind([' ', '-']).map_or(0, |i| i + 1);
+    format!("{}{}", &words[..at], ordinal_word(&words[at..]))
+}
+
+// lowercase, single spaces and no "and", so differently typed words can be compared
+fn normalize(text: &str) -> String {
+    let lowered = text.to_lowercase().replace('-', " ");
+    let words: Vec<&str> = lowered.split_whitespace().filter(|w| *w != "and").collect();
+    words.join(" ")
+}
+
+pub fn parse_ordinal_words(text: &str) -> Result<u128, String> {
+    let lowered = normalize(text);
+    let at = lowered.rfind(' ').map_or(0, |i| i + 1);
+    let cardinal = cardinal_word(&lowered[at..]).ok_or_else(|| format!("`{}` doesn't end in an ordinal word like first or tenth", text.trim()))?;
+    let (negative, n) = parse_magnitude(&format!("{}{}", &lowered[..at], cardinal))?;
+    // "fourth" is "four" plus th, but "fourty" isn't a number, so the spelling gets checked by
+    // writing the ordinal back out
+    if negative || normalize(&to_ordinal_words(n)) != lowered {
+        return Err(format!("`{}` isn't how the ordinal is written, that's `{}`", text.trim(), to_ordinal_words(n)));
+    }
+    Ok(n)
+}
+
+// base 3 with the digits -1, 0 and 1, written T, 0 and 1. every integer has exactly one way to be
+// written and negative numbers need no sign: flipping every 1 and T negates
+pub fn to_ternary(n: i128) -> String {
+    if n == 0 {
+        return String::from("0");
+    }
+    let mut digits = Vec::new();
+    let mut rest = n;
+    while rest != 0 {
+        // a remainder of 2 is 3 - 1: write T and carry one more into the next digit. dividing
+        // first keeps i128::MAX from overflowing
+        match rest.rem_euclid(3) {
+            0 => {
+                digits.push('0');
+                rest = rest.div_euclid(3);
+            }
+            1 => {
+                digits.push('1');
+                rest = rest.div_euclid(3);
+            }
+            _ => {
+                digits.push('T');
+                rest = rest.div_euclid(3) + 1;
+            }
+        }
+    }
+    digits.iter().rev().collect()
+}
+
+// takes 1 0 T, and + 0 - as the other common way of writing the digits
+pub fn parse_ternary(text: &str) -> Result<i128, String> {
+    let text = text.trim();
+    if text.is_empty() {
+        return Err(String::from("there are no balanced ternary digits here"));
+    }
+    let mut n: i128 = 0;
+    for c in text.chars() {
+        let digit = match c {
+            '1' | '+' => 1,
+            '0' => 0,
+            'T' | 't' | '-' => -1,
+            other => return Err(format!("`{}` isn't a balanced ternary digit, they're 1, 0 and T", other)),
+        };
+        // 3n + d can fit when 3n doesn't, right at the ends of i128, and then 3(n + d) - 2d does
+        n = n
+            .checked_mul(3)
+            .and_then(|m| m.checked_add(digit))
+            .or_else(|| n.checked_add(digit)?.checked_mul(3)?.checked_sub(2 * digit))
+            .ok_or_else(|| format!("`{}` doesn't fit in an i128", text))?;
+    }
+    Ok(n)
+}
+
+// every way of writing one number
+fn describe(n: i128) -> String {
+    let mut rows = vec![("number", n.to_string())];
+    rows.push(("roman", u32::try_from(n).map_err(|_| String::from("roman numerals only go from 1 to 3999")).and_then(to_roman).unwrap_or_else(|e| format!("({})", e))));
+    rows.push(("words", to_words(n)));
+    match u128::try_from(n) {
+        Ok(n) => {
+            rows.push(("ordinal", to_ordinal(n)));
+            rows.push(("ordinal words", to_ordinal_words(n)));
+        }
+        Err(_) => rows.push(("ordinal", String::from("(no ordinals below zero)"))),
+    }
+    rows.push(("balanced ternary", to_ternary(n)));
+
+    let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let lines: Vec<String> = rows.iter().map(|(label, value)| format!("    {:<width$}  {}", label, value, width = width)).collect();
+    lines.join("\n")
+}
+
+const SYSTEMS: [&str; 5] = ["roman", "words", "ordinal", "ternary", "number"];
+
+// reads `text` in one system, or the first one it makes sense in
+fn read(system: Option<&str>, text: &str) -> Result<(i128, &'static str), String> {
+    let ordinal = |n: u128| i128::try_from(n).map_err(|_| format!("`{}` doesn't fit in an i128", text));
+    match system {
+        Some("roman") => Ok((i128::from(parse_roman(text)?), "roman numeral")),
+        Some("words") => Ok((parse_words(text)?, "words")),
+        Some("ordinal") => match parse_ordinal(text) {
+            Ok(n) => Ok((ordinal(n)?, "ordinal")),
+            Err(_) => Ok((ordinal(parse_ordinal_words(text)?)?, "ordinal words")),
+        },
+        Some("ternary") => Ok((parse_ternary(text)?, "balanced ternary")),
+        Some(_) => text.replace('_', "").parse().map(|n| (n, "number")).map_err(|_| format!("`{}` isn't an i128", text)),
+        None => {
+            let trimmed = text.trim();
+            let first = trimmed.chars().next().unwrap_or(' ');
+            let system = if trimmed.replace('_', "").parse::<i128>().is_ok() {
+                "number"
+            } else if trimmed.chars().all(|c| "10Tt+-".contains(c)) {
+                "ternary"
+            } else if first.is_ascii_digit() {
+                "ordinal"
+            } else if trimmed.chars().all(|c| "IVXLCDMivxlcdm".contains(c)) {
+                "roman"
+            } else {
+                // "thousand" ends like "second" does, so only the parsers can tell words and
+                // ordinal words apart
+                return read(Some("words"), text).or_else(|e| read(Some("ordinal"), text).map_err(|_| e));
+            };
+            read(Some(system), text)
+        }
+    }
+}
+
+// `98222`, `MCMXCIV`, `forty two`, `42nd`, `twenty-first` or `1T0`, with `roman`, `words`,
+// `ordinal`, `ternary` or `number` in front when the guess would be wrong
+pub fn evaluate(line: &str) -> Result<String, String> {
+    let line = line.trim();
+    let (system, text) = match line.split_once(' ') {
+        Some((first, rest)) if SYSTEMS.contains(&first) => (Some(first), rest),
+        _ => (None, line),
+    };
+    if text.is_empty() {
+        return Err(String::from("nothing to convert"));
+    }
+    let (n, read_as) = read(system, text)?;
+    Ok(format!("{} (read as {})\n{}", text, read_as, describe(n)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // a little xorshift, so the tests don't need rand and give the same numbers every run
+    fn xorshift(state: &mut u64) -> u64 {
+        *state ^= *state << 13;
+        *state ^= *state >> 7;
+        *state ^= *state << 17;
+        *state
+    }
+
+    // the small numbers, around every power of two and ten up to u64::MAX, and a seeded sample
+    // of the rest, cut to a random length so every magnitude comes up
+    fn unsigned() -> Vec<u64> {
+        let mut values: Vec<u64> = (0..=2000).chain([u64::MAX - 1, u64::MAX]).collect();
+        for power in 1..64 {
+            let two = 1u64 << power;
+            values.extend([two - 1, two, two + 1]);
+        }
+        let mut ten: u64 = 10;
+        while let Some(next) = ten.checked_mul(10) {
+            values.extend([ten - 1, ten, ten + 1]);
+            ten = next;
+        }
+        let mut state = 0x2545_f491_4f6c_dd1d;
+        for _ in 0..2000 {
+            let bits = xorshift(&mut state);
+            values.push(bits >> (xorshift(&mut state) % 64));
+        }
+        values
+    }
+
+    // the same spread over i64, on both sides of zero
+    fn signed() -> Vec<i64> {
+        let mut values: Vec<i64> = unsigned().into_iter().map(|n| n as i64).collect();
+        values.extend(unsigned().into_iter().map(|n| (n as i64).wrapping_neg()));
+        values.extend([i64::MIN, i64::MIN + 1, i64::MAX]);
+        values
+    }
+
+    #[test]
+    fn roman_round_trips() {
+        for n in 1..=3999 {
+            let roman = to_roman(n).unwrap();
+            assert_eq!(parse_roman(&roman), Ok(n), "{}", roman);
+            assert_eq!(parse_roman(&roman.to_lowercase()), Ok(n), "{}", roman);
+        }
+    }
+
+    #[test]
+    fn roman_edges() {
+        assert_eq!(to_roman(1994), Ok(String::from("MCMXCIV")));
+        assert_eq!(to_roman(3999), Ok(String::from("MMMCMXCIX")));
+        assert!(to_roman(0).is_err());
+        assert!(to_roman(4000).is_err());
+        assert!(parse_roman("IIII").is_err());
+        assert!(parse_roman("IC").is_err());
+    }
+
+    #[test]
+    fn words_round_trip() {
+        for n in signed() {
+            let words = to_words(n.into());
+            assert_eq!(parse_words(&words), Ok(n.into()), "{}", words);
+        }
+        assert_eq!(to_words(-42), "minus forty-two");
+        assert_eq!(parse_words("one hundred and one"), Ok(101));
+    }
+
+    #[test]
+    fn ordinals_round_trip() {
+        for n in unsigned() {
+            let ordinal = to_ordinal(n.into());
+            assert_eq!(parse_ordinal(&ordinal), Ok(n.into()), "{}", ordinal);
+            let words = to_ordinal_words(n.into());
+            assert_eq!(parse_ordinal_words(&words), Ok(n.into()), "{}", words);
+        }
+        assert_eq!(to_ordinal(11), "11th");
+        assert_eq!(to_ordinal(22), "22nd");
+        assert_eq!(to_ordinal_words(21), "twenty-first");
+    }
+
+    #[test]
+    fn balanced_ternary_round_trips() {
+        for n in signed() {
+            let ternary = to_ternary(n.into());
+            assert_eq!(parse_ternary(&ternary), Ok(n.into()), "{}", ternary);
+        }
+        assert_eq!(to_ternary(0), "0");
+        assert_eq!(to_ternary(2), "1T");
+        assert_eq!(to_ternary(-2), "T1");
+    }
+}
@@ -0,0 +1,316 @@
+// runs an integer operation every way rust lets you run it: what a debug build does (panic on
+// overflow), what a release build does (two's complement wrapping), and the `wrapping_*`,
+// `checked_*`, `overflowing_*` and `saturating_*` families from the note in ch3/varsandmutability.rs.
+// every value here comes from actually calling those methods on the real type
+
+use std::fmt;
+use std::num::IntErrorKind;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+    Shl,
+    Shr,
+    Neg,
+}
+
+impl Op {
+    fn parse_binary(s: &str) -> Option<Op> {
+        match s {
+            "+" => Some(Op::Add),
+            "-" => Some(Op::Sub),
+            "*" => Some(Op::Mul),
+            "/" => Some(Op::Div),
+            "%" => Some(Op::Rem),
+            "<<" => Some(Op::Shl),
+            ">>" => Some(Op::Shr),
+            _ => None,
+        }
+    }
+
+    // the part after `wrapping_`, `checked_` and so on
+    fn method(self) -> &'static str {
+        match self {
+            Op::Add => "add",
+            Op::Sub => "sub",
+            Op::Mul => "mul",
+            Op::Div => "div",
+            Op::Rem => "rem",
+            Op::Shl => "shl",
+            Op::Shr => "shr",
+            Op::Neg => "neg",
+        }
+    }
+
+    // the exact messages rust panics with
+    pub(crate) fn overflow_message(self) -> &'static str {
+        match self {
+            Op::Add => "attempt to add with overflow",
+            Op::Sub => "attempt to subtract with overflow",
+            Op::Mul => "attempt to multiply with overflow",
+            Op::Div => "attempt to divide with overflow",
+            Op::Rem => "attempt to calculate the remainder with overflow",
+            Op::Shl => "attempt to shift left with overflow",
+            Op::Shr => "attempt to shift right with overflow",
+            Op::Neg => "attempt to negate with overflow",
+        }
+    }
+
+    pub(crate) fn zero_message(self) -> &'static str {
+        match self {
+            Op::Rem => "attempt to calculate the remainder with a divisor of zero",
+            _ => "attempt to divide by zero",
+        }
+    }
+}
+
+pub enum Outcome {
+    Value(String),
+    Panic(&'static str),
+    CompileError(String),
+    // there's no method by that name, e.g. `saturating_rem` or `saturating_shl`
+    Missing,
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Value(v) => write!(f, "{}", v),
+            Outcome::Panic(message) => write!(f, "panic: {}", message),
+            Outcome::CompileError(message) => write!(f, "won't compile: {}", message),
+            Outcome::Missing => write!(f, "(no such method)"),
+        }
+    }
+}
+
+pub struct Row {
+    pub label: String,
+    pub outcome: Outcome,
+}
+
+pub struct Report {
+    pub expression: String,
+    pub rows: Vec<Row>,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.expression)?;
+        let width = self.rows.iter().map(|row| row.label.len()).max().unwrap_or(0);
+        for row in &self.rows {
+            writeln!(f, "    {:<width$}  {}", row.label, row.outcome, width = width)?;
+        }
+        Ok(())
+    }
+}
+
+fn row(label: &str, outcome: Outcome) -> Row {
+    Row {
+        label: label.to_string(),
+        outcome,
+    }
+}
+
+// turns the results of the method families into the rows of a report. `None` for `wrapping`,
+// `overflowing` or `saturating` means calling that method would have panicked (dividing by zero)
+fn report<T: fmt::Display>(
+    op: Op,
+    checked: Option<T>,
+    wrapping: Option<T>,
+    overflowing: Option<(T, bool)>,
+    saturating: Option<Option<T>>,
+) -> Vec<Row> {
+    let method = op.method();
+    let by_zero = wrapping.is_none();
+    let value = |v: &T| Outcome::Value(v.to_string());
+
+    let debug = match (&checked, by_zero) {
+        (_, true) => Outcome::Panic(op.zero_message()),
+        (Some(v), false) => value(v),
+        (None, false) => Outcome::Panic(op.overflow_message()),
+    };
+
+    // release builds stop checking overflow for + - * << >> and negation, but division by
+    // zero and MIN / -1 still panic no matter how you compile
+    let release = match (&wrapping, &checked) {
+        (None, _) => Outcome::Panic(op.zero_message()),
+        (Some(_), None) if op == Op::Div || op == Op::Rem => Outcome::Panic(op.overflow_message()),
+        (Some(v), _) => value(v),
+    };
+
+    let wrapping = match &wrapping {
+        Some(v) => value(v),
+        None => Outcome::Panic(op.zero_message()),
+    };
+
+    let checked = match &checked {
+        Some(v) => Outcome::Value(format!("Some({})", v)),
+        None => Outcome::Value(String::from("None")),
+    };
+
+    let overflowing = match &overflowing {
+        Some((v, overflowed)) => Outcome::Value(format!("({}, {})", v, overflowed)),
+        None => Outcome::Panic(op.zero_message()),
+    };
+
+    let saturating = match &saturating {
+        None => Outcome::Missing,
+        Some(Some(v)) => value(v),
+        Some(None) => Outcome::Panic(op.zero_message()),
+    };
+
+    vec![
+        row("debug build", debug),
+        row("release build", release),
+        row(&format!("wrapping_{}", method), wrapping),
+        row(&format!("checked_{}", method), checked),
+        row(&format!("overflowing_{}", method), overflowing),
+        row(&format!("saturating_{}", method), saturating),
+    ]
+}
+
+trait Lab: Sized + Copy + FromStr<Err = std::num::ParseIntError> + fmt::Display {
+    const NAME: &'static str;
+    const SIGNED: bool;
+
+    fn binary(a: Self, op: Op, b: Self) -> Vec<Row>;
+    fn shift(a: Self, op: Op, b: u32) -> Vec<Row>;
+    fn negate(a: Self) -> Vec<Row>;
+}
+
+macro_rules! lab_common {
+    ($t:ty) => {
+        const NAME: &'static str = stringify!($t);
+
+        fn binary(a: $t, op: Op, b: $t) -> Vec<Row> {
+            let by_zero = b == 0;
+            match op {
+                Op::Add => report(op, a.checked_add(b), Some(a.wrapping_add(b)), Some(a.overflowing_add(b)), Some(Some(a.saturating_add(b)))),
+                Op::Sub => report(op, a.checked_sub(b), Some(a.wrapping_sub(b)), Some(a.overflowing_sub(b)), Some(Some(a.saturating_sub(b)))),
+                Op::Mul => report(op, a.checked_mul(b), Some(a.wrapping_mul(b)), Some(a.overflowing_mul(b)), Some(Some(a.saturating_mul(b)))),
+                // only `checked_div` is safe to call with a zero divisor
+                Op::Div if by_zero => report(op, a.checked_div(b), None, None, Some(None)),
+                Op::Div => report(op, a.checked_div(b), Some(a.wrapping_div(b)), Some(a.overflowing_div(b)), Some(Some(a.saturating_div(b)))),
+                Op::Rem if by_zero => report(op, a.checked_rem(b), None, None, None),
+                Op::Rem => report(op, a.checked_rem(b), Some(a.wrapping_rem(b)), Some(a.overflowing_rem(b)), None),
+                _ => unreachable!("not a binary arithmetic operator"),
+            }
+        }
+
+        // there's no `saturating_shl` or `saturating_shr`
+        fn shift(a: $t, op: Op, b: u32) -> Vec<Row> {
+            match op {
+                Op::Shl => report(op, a.checked_shl(b), Some(a.wrapping_shl(b)), Some(a.overflowing_shl(b)), None),
+                Op::Shr => report(op, a.checked_shr(b), Some(a.wrapping_shr(b)), Some(a.overflowing_shr(b)), None),
+                _ => unreachable!("not a shift"),
+            }
+        }
+    };
+}
+
+macro_rules! signed_lab {
+    ($($t:ty),*) => {
+        $(
+            impl Lab for $t {
+                const SIGNED: bool = true;
+                lab_common!($t);
+
+                fn negate(a: $t) -> Vec<Row> {
+                    report(Op::Neg, a.checked_neg(), Some(a.wrapping_neg()), Some(a.overflowing_neg()), Some(Some(a.saturating_neg())))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! unsigned_lab {
+    ($($t:ty),*) => {
+        $(
+            impl Lab for $t {
+                const SIGNED: bool = false;
+                lab_common!($t);
+
+                // `-x` doesn't even compile for unsigned types, but the methods exist
+                fn negate(a: $t) -> Vec<Row> {
+                    let mut rows = report(Op::Neg, a.checked_neg(), Some(a.wrapping_neg()), Some(a.overflowing_neg()), None);
+                    let error = format!("cannot apply unary operator `-` to type `{}`", stringify!($t));
+                    rows[0].outcome = Outcome::CompileError(error.clone());
+                    rows[1].outcome = Outcome::CompileError(error);
+                    rows
+                }
+            }
+        )*
+    };
+}
+
+signed_lab!(i8, i16, i32, i64, i128, isize);
+unsigned_lab!(u8, u16, u32, u64, u128, usize);
+
+// every type from the integer table, in table order
+pub const TYPES: [&str; 12] = [
+    "i8", "u8", "i16", "u16", "i32", "u32", "i64", "u64", "i128", "u128", "isize", "usize",
+];
+
+fn operand<T: Lab>(text: &str) -> Result<T, String> {
+    let digits = text.replace('_', "");
+    digits.parse().map_err(|e: std::num::ParseIntError| match e.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+            format!("literal out of range for `{}`: {}", T::NAME, text)
+        }
+        _ if digits.starts_with('-') && !T::SIGNED => {
+            format!("cannot apply unary operator `-` to type `{}`", T::NAME)
+        }
+        _ => format!("`{}` isn't a valid {}", text, T::NAME),
+    })
+}
+
+fn run<T: Lab>(tokens: &[&str]) -> Result<Vec<Row>, String> {
+    match tokens {
+        [op, a] if *op == "-" || *op == "neg" => Ok(T::negate(operand(a)?)),
+        [a, op, b] => {
+            let op = Op::parse_binary(op).ok_or(format!("unknown operator `{}`", op))?;
+            let a = operand::<T>(a)?;
+            match op {
+                Op::Shl | Op::Shr => {
+                    let b: u32 = b.replace('_', "").parse()
+                        .map_err(|_| format!("the shift amount has to be a u32, got `{}`", b))?;
+                    Ok(T::shift(a, op, b))
+                }
+                _ => Ok(T::binary(a, op, operand(b)?)),
+            }
+        }
+        _ => Err(String::from("expected `TYPE A OP B` (like `u8 250 + 10`) or `TYPE - A`")),
+    }
+}
+
+// evaluates a line like `u8 250 + 10`, `i32 1 << 40` or `i8 - -128`
+pub fn evaluate(line: &str) -> Result<Report, String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let (ty, rest) = tokens.split_first().ok_or("nothing to evaluate")?;
+
+    let rows = match *ty {
+        "i8" => run::<i8>(rest)?,
+        "i16" => run::<i16>(rest)?,
+        "i32" => run::<i32>(rest)?,
+        "i64" => run::<i64>(rest)?,
+        "i128" => run::<i128>(rest)?,
+        "isize" => run::<isize>(rest)?,
+        "u8" => run::<u8>(rest)?,
+        "u16" => run::<u16>(rest)?,
+        "u32" => run::<u32>(rest)?,
+        "u64" => run::<u64>(rest)?,
+        "u128" => run::<u128>(rest)?,
+        "usize" => run::<usize>(rest)?,
+        other => return Err(format!("unknown type `{}`, try one of: {}", other, TYPES.join(" "))),
+    };
+
+    Ok(Report {
+        expression: tokens.join(" "),
+        rows,
+    })
+}
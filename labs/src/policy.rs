@@ -0,0 +1,607 @@
+// the overflow policies from the note in ch3/varsandmutability.rs as types, so the policy is
+// picked once instead of at every `checked_add`. each wrapper holds any integer from the table
+// and its operators follow one rule:
+//   Wrapping    two's complement wraparound, like a release build
+//   Saturating  sticks at MIN or MAX
+//   Checked     keeps the first overflow as an error and carries it through everything after
+//   Panicking   panics with the debug build message, whatever the build
+// dividing by zero panics under every policy except Checked, the same as `wrapping_div` and
+// `saturating_div` do. switching policy is always spelled out with `from`/`into`, and getting
+// out of a Checked is a `try_from` since it might be holding an error
+
+use std::cmp::Ordering;
+use std::convert::TryFrom;
+use std::fmt;
+use std::hint::black_box;
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, RemAssign, Shl, Shr, Sub, SubAssign};
+use std::panic;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::overflow::{Op, TYPES};
+
+pub trait Integer: Copy + Ord + fmt::Display + fmt::Debug + FromStr {
+    const NAME: &'static str;
+    const ZERO: Self;
+
+    fn from_u8(value: u8) -> Self;
+    fn to_f64(self) -> f64;
+    // only for whole numbers in range, so nothing gets rounded on the way in
+    fn from_f64(value: f64) -> Option<Self>;
+    // `b` is ignored for `Op::Neg`, and shifts go through the `_shift` versions
+    fn wrapping(a: Self, op: Op, b: Self) -> Self;
+    fn checked(a: Self, op: Op, b: Self) -> Option<Self>;
+    fn saturating(a: Self, op: Op, b: Self) -> Self;
+    fn wrapping_shift(a: Self, op: Op, by: u32) -> Self;
+    fn checked_shift(a: Self, op: Op, by: u32) -> Option<Self>;
+}
+
+macro_rules! integer {
+    ($($t:ty),*) => {
+        $(
+            impl Integer for $t {
+                const NAME: &'static str = stringify!($t);
+                const ZERO: $t = 0;
+
+                fn from_u8(value: u8) -> $t {
+                    value as $t
+                }
+
+                fn to_f64(self) -> f64 {
+                    self as f64
+                }
+
+                // every whole f64 below 2^128 is exact as an i128 or u128, so the range check is
+                // left to `try_from`
+                fn from_f64(value: f64) -> Option<$t> {
+                    if !value.is_finite() || value.fract() != 0.0 || value.abs() >= 2f64.powi(128) {
+                        return None;
+                    }
+                    if value < 0.0 {
+                        if value < -(2f64.powi(127)) {
+                            return None;
+                        }
+                        <$t>::try_from(value as i128).ok()
+                    } else {
+                        <$t>::try_from(value as u128).ok()
+                    }
+                }
+
+                fn wrapping(a: $t, op: Op, b: $t) -> $t {
+                    match op {
+                        Op::Add => a.wrapping_add(b),
+                        Op::Sub => a.wrapping_sub(b),
+                        Op::Mul => a.wrapping_mul(b),
+                        Op::Div => a.wrapping_div(b),
+                        Op::Rem => a.wrapping_rem(b),
+                        Op::Neg => a.wrapping_neg(),
+                        Op::Shl | Op::Shr => unreachable!("shifts take a u32"),
+                    }
+                }
+
+                fn checked(a: $t, op: Op, b: $t) -> Option<$t> {
+                    match op {
+                        Op::Add => a.checked_add(b),
+                        Op::Sub => a.checked_sub(b),
+                        Op::Mul => a.checked_mul(b),
+                        Op::Div => a.checked_div(b),
+                        Op::Rem => a.checked_rem(b),
+                        Op::Neg => a.checked_neg(),
+                        Op::Shl | Op::Shr => unreachable!("shifts take a u32"),
+                    }
+                }
+
+                fn saturating(a: $t, op: Op, b: $t) -> $t {
+                    match op {
+                        Op::Add => a.saturating_add(b),
+                        Op::Sub => a.saturating_sub(b),
+                        Op::Mul => a.saturating_mul(b),
+                        Op::Div => a.saturating_div(b),
+                        // there's no `saturating_rem`. the only overflow is MIN % -1, whose real
+                        // answer is 0 anyway
+                        Op::Rem if b == 0 => panic!("{}", Op::Rem.zero_message()),
+                        Op::Rem => a.checked_rem(b).unwrap_or(0),
+                        // `0 - a` is `-a` for signed types, and clamps to 0 for unsigned ones
+                        Op::Neg => (0 as $t).saturating_sub(a),
+                        Op::Shl | Op::Shr => unreachable!("shifts take a u32"),
+                    }
+                }
+
+                fn wrapping_shift(a: $t, op: Op, by: u32) -> $t {
+                    match op {
+                        Op::Shl => a.wrapping_shl(by),
+                        _ => a.wrapping_shr(by),
+                    }
+                }
+
+                fn checked_shift(a: $t, op: Op, by: u32) -> Option<$t> {
+                    match op {
+                        Op::Shl => a.checked_shl(by),
+                        _ => a.checked_shr(by),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Wrapping<T>(pub T);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Saturating<T>(pub T);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Panicking<T>(pub T);
+
+// the operation that went wrong first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error {
+    pub op: Op,
+    pub by_zero: bool,
+}
+
+impl Error {
+    fn new<T: Integer>(op: Op, b: T) -> Error {
+        Error {
+            op,
+            by_zero: (op == Op::Div || op == Op::Rem) && b == T::ZERO,
+        }
+    }
+
+    // the message the debug build would have panicked with
+    pub fn message(&self) -> &'static str {
+        if self.by_zero {
+            self.op.zero_message()
+        } else {
+            self.op.overflow_message()
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.message())
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Checked<T>(pub Result<T, Error>);
+
+impl<T: Integer> Checked<T> {
+    pub fn new(value: T) -> Checked<T> {
+        Checked(Ok(value))
+    }
+
+    pub fn value(&self) -> Option<T> {
+        self.0.ok()
+    }
+
+    pub fn error(&self) -> Option<Error> {
+        self.0.err()
+    }
+}
+
+impl<T: Integer> Default for Checked<T> {
+    fn default() -> Checked<T> {
+        Checked::new(T::ZERO)
+    }
+}
+
+// only values compare, an error isn't bigger or smaller than anything. it's only equal to the
+// same error, the way `==` has it
+impl<T: Integer> PartialOrd for Checked<T> {
+    fn partial_cmp(&self, other: &Checked<T>) -> Option<Ordering> {
+        match (&self.0, &other.0) {
+            (Ok(a), Ok(b)) => Some(a.cmp(b)),
+            (Err(a), Err(b)) if a == b => Some(Ordering::Equal),
+            _ => None,
+        }
+    }
+}
+
+// each policy's answer for `a op b`, which is all the operator traits below need
+trait Policy: Sized {
+    fn apply(self, op: Op, other: Self) -> Self;
+}
+
+impl<T: Integer> Policy for Wrapping<T> {
+    fn apply(self, op: Op, other: Wrapping<T>) -> Wrapping<T> {
+        Wrapping(T::wrapping(self.0, op, other.0))
+    }
+}
+
+impl<T: Integer> Policy for Saturating<T> {
+    fn apply(self, op: Op, other: Saturating<T>) -> Saturating<T> {
+        Saturating(T::saturating(self.0, op, other.0))
+    }
+}
+
+impl<T: Integer> Policy for Checked<T> {
+    fn apply(self, op: Op, other: Checked<T>) -> Checked<T> {
+        match (self.0, other.0) {
+            (Ok(a), Ok(b)) => Checked(T::checked(a, op, b).ok_or_else(|| Error::new(op, b))),
+            (Err(error), _) | (_, Err(error)) => Checked(Err(error)),
+        }
+    }
+}
+
+impl<T: Integer> Policy for Panicking<T> {
+    fn apply(self, op: Op, other: Panicking<T>) -> Panicking<T> {
+        match T::checked(self.0, op, other.0) {
+            Some(value) => Panicking(value),
+            None => panic!("{}", Error::new(op, other.0)),
+        }
+    }
+}
+
+macro_rules! operators {
+    ($($wrapper:ident),*) => {
+        $(
+            impl<T: Integer> Add for $wrapper<T> {
+                type Output = $wrapper<T>;
+                fn add(self, other: $wrapper<T>) -> $wrapper<T> {
+                    self.apply(Op::Add, other)
+                }
+            }
+
+            impl<T: Integer> Sub for $wrapper<T> {
+                type Output = $wrapper<T>;
+                fn sub(self, other: $wrapper<T>) -> $wrapper<T> {
+                    self.apply(Op::Sub, other)
+                }
+            }
+
+            impl<T: Integer> Mul for $wrapper<T> {
+                type Output = $wrapper<T>;
+                fn mul(self, other: $wrapper<T>) -> $wrapper<T> {
+                    self.apply(Op::Mul, other)
+                }
+            }
+
+            impl<T: Integer> Div for $wrapper<T> {
+                type Output = $wrapper<T>;
+                fn div(self, other: $wrapper<T>) -> $wrapper<T> {
+                    self.apply(Op::Div, other)
+                }
+            }
+
+            impl<T: Integer> Rem for $wrapper<T> {
+                type Output = $wrapper<T>;
+                fn rem(self, other: $wrapper<T>) -> $wrapper<T> {
+                    self.apply(Op::Rem, other)
+                }
+            }
+
+            impl<T: Integer> Neg for $wrapper<T> {
+                type Output = $wrapper<T>;
+                fn neg(self) -> $wrapper<T> {
+                    self.apply(Op::Neg, self)
+                }
+            }
+
+            impl<T: Integer> AddAssign for $wrapper<T> {
+                fn add_assign(&mut self, other: $wrapper<T>) {
+                    *self = self.apply(Op::Add, other);
+                }
+            }
+
+            impl<T: Integer> SubAssign for $wrapper<T> {
+                fn sub_assign(&mut self, other: $wrapper<T>) {
+                    *self = self.apply(Op::Sub, other);
+                }
+            }
+
+            impl<T: Integer> MulAssign for $wrapper<T> {
+                fn mul_assign(&mut self, other: $wrapper<T>) {
+                    *self = self.apply(Op::Mul, other);
+                }
+            }
+
+            impl<T: Integer> DivAssign for $wrapper<T> {
+                fn div_assign(&mut self, other: $wrapper<T>) {
+                    *self = self.apply(Op::Div, other);
+                }
+            }
+
+            impl<T: Integer> RemAssign for $wrapper<T> {
+                fn rem_assign(&mut self, other: $wrapper<T>) {
+                    *self = self.apply(Op::Rem, other);
+                }
+            }
+        )*
+    };
+}
+
+operators!(Wrapping, Saturating, Checked, Panicking);
+
+// shifting by the bit width or more is the overflow for shifts. there's no saturating shift in
+// std, and no obvious answer for one, so Saturating doesn't get `<<` or `>>`
+macro_rules! shifts {
+    ($($trait:ident $method:ident $op:expr),*) => {
+        $(
+            impl<T: Integer> $trait<u32> for Wrapping<T> {
+                type Output = Wrapping<T>;
+                fn $method(self, by: u32) -> Wrapping<T> {
+                    Wrapping(T::wrapping_shift(self.0, $op, by))
+                }
+            }
+
+            impl<T: Integer> $trait<u32> for Checked<T> {
+                type Output = Checked<T>;
+                fn $method(self, by: u32) -> Checked<T> {
+                    Checked(self.0.and_then(|value| {
+                        T::checked_shift(value, $op, by).ok_or(Error { op: $op, by_zero: false })
+                    }))
+                }
+            }
+
+            impl<T: Integer> $trait<u32> for Panicking<T> {
+                type Output = Panicking<T>;
+                fn $method(self, by: u32) -> Panicking<T> {
+                    match T::checked_shift(self.0, $op, by) {
+                        Some(value) => Panicking(value),
+                        None => panic!("{}", $op.overflow_message()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+shifts!(Shl shl Op::Shl, Shr shr Op::Shr);
+
+macro_rules! display {
+    ($($wrapper:ident),*) => {
+        $(
+            impl<T: Integer> fmt::Display for $wrapper<T> {
+                fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                    fmt::Display::fmt(&self.0, f)
+                }
+            }
+        )*
+    };
+}
+
+display!(Wrapping, Saturating, Panicking);
+
+impl<T: Integer> fmt::Display for Checked<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            Ok(value) => fmt::Display::fmt(value, f),
+            Err(error) => write!(f, "error: {}", error),
+        }
+    }
+}
+
+macro_rules! conversions {
+    ($($from:ident => $($to:ident),*);*) => {
+        $(
+            $(
+                impl<T: Integer> From<$from<T>> for $to<T> {
+                    fn from(value: $from<T>) -> $to<T> {
+                        $to(value.0)
+                    }
+                }
+            )*
+
+            impl<T: Integer> From<$from<T>> for Checked<T> {
+                fn from(value: $from<T>) -> Checked<T> {
+                    Checked::new(value.0)
+                }
+            }
+
+            impl<T: Integer> TryFrom<Checked<T>> for $from<T> {
+                type Error = Error;
+                fn try_from(value: Checked<T>) -> Result<$from<T>, Error> {
+                    value.0.map($from)
+                }
+            }
+        )*
+    };
+}
+
+conversions!(
+    Wrapping => Saturating, Panicking;
+    Saturating => Wrapping, Panicking;
+    Panicking => Wrapping, Saturating
+);
+
+// runs `f` and turns a panic into its message, without the usual report on stderr
+fn catch<R>(f: impl FnOnce() -> R + panic::UnwindSafe) -> Result<R, String> {
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+    let result = panic::catch_unwind(f);
+    panic::set_hook(hook);
+    result.map_err(|payload| {
+        payload.downcast_ref::<String>().cloned()
+            .or_else(|| payload.downcast_ref::<&str>().map(|s| s.to_string()))
+            .unwrap_or_else(|| String::from("(no message)"))
+    })
+}
+
+fn operand<T: Integer>(text: &str) -> Result<T, String> {
+    text.replace('_', "").parse().map_err(|_| format!("`{}` isn't a valid {}", text, T::NAME))
+}
+
+// folds `A OP B OP C ...` left to right under one policy
+fn fold<P: Policy + Copy + fmt::Display + Shl<u32, Output = P> + Shr<u32, Output = P>>(
+    first: P,
+    steps: &[(Op, P, u32)],
+) -> String {
+    let mut value = first;
+    for &(op, operand, shift) in steps {
+        value = match op {
+            Op::Shl => value << shift,
+            Op::Shr => value >> shift,
+            _ => value.apply(op, operand),
+        };
+    }
+    value.to_string()
+}
+
+fn run<T: Integer + panic::RefUnwindSafe>(tokens: &[&str]) -> Result<Vec<(String, String)>, String> {
+    let (first, rest) = tokens.split_first().ok_or("nothing to evaluate")?;
+    let first: T = operand(first)?;
+    if rest.is_empty() || rest.len() % 2 != 0 {
+        return Err(String::from("expected `TYPE A OP B`, with as many more `OP C` as you like"));
+    }
+
+    let mut steps = Vec::new();
+    for pair in rest.chunks(2) {
+        let op = match pair[0] {
+            "+" => Op::Add,
+            "-" => Op::Sub,
+            "*" => Op::Mul,
+            "/" => Op::Div,
+            "%" => Op::Rem,
+            "<<" => Op::Shl,
+            ">>" => Op::Shr,
+            other => return Err(format!("unknown operator `{}`", other)),
+        };
+        let (value, shift) = match op {
+            Op::Shl | Op::Shr => {
+                let shift = pair[1].parse().map_err(|_| format!("the shift amount has to be a u32, got `{}`", pair[1]))?;
+                (T::ZERO, shift)
+            }
+            _ => (operand(pair[1])?, 0),
+        };
+        steps.push((op, value, shift));
+    }
+
+    let outcome = |result: Result<String, String>| match result {
+        Ok(value) => value,
+        Err(message) => format!("panic: {}", message),
+    };
+    let wrapping = outcome(catch(|| {
+        let steps: Vec<_> = steps.iter().map(|&(op, value, shift)| (op, Wrapping(value), shift)).collect();
+        fold(Wrapping(first), &steps)
+    }));
+    let checked = outcome(catch(|| {
+        let steps: Vec<_> = steps.iter().map(|&(op, value, shift)| (op, Checked::new(value), shift)).collect();
+        fold(Checked::new(first), &steps)
+    }));
+    let panicking = outcome(catch(|| {
+        let steps: Vec<_> = steps.iter().map(|&(op, value, shift)| (op, Panicking(value), shift)).collect();
+        fold(Panicking(first), &steps)
+    }));
+    // no shifts for Saturating, so it gets its own loop
+    let saturating = if steps.iter().any(|&(op, _, _)| op == Op::Shl || op == Op::Shr) {
+        String::from("(no saturating shifts)")
+    } else {
+        outcome(catch(|| {
+            let mut value = Saturating(first);
+            for &(op, operand, _) in &steps {
+                value = value.apply(op, Saturating(operand));
+            }
+            value.to_string()
+        }))
+    };
+
+    Ok(vec![
+        (format!("Wrapping<{}>", T::NAME), wrapping),
+        (format!("Saturating<{}>", T::NAME), saturating),
+        (format!("Checked<{}>", T::NAME), checked),
+        (format!("Panicking<{}>", T::NAME), panicking),
+    ])
+}
+
+// evaluates a line like `u8 200 + 100 - 60` under every policy, left to right with no precedence
+pub fn evaluate(line: &str) -> Result<String, String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let (ty, rest) = tokens.split_first().ok_or("nothing to evaluate")?;
+
+    let rows = match *ty {
+        "i8" => run::<i8>(rest)?,
+        "i16" => run::<i16>(rest)?,
+        "i32" => run::<i32>(rest)?,
+        "i64" => run::<i64>(rest)?,
+        "i128" => run::<i128>(rest)?,
+        "isize" => run::<isize>(rest)?,
+        "u8" => run::<u8>(rest)?,
+        "u16" => run::<u16>(rest)?,
+        "u32" => run::<u32>(rest)?,
+        "u64" => run::<u64>(rest)?,
+        "u128" => run::<u128>(rest)?,
+        "usize" => run::<usize>(rest)?,
+        other => return Err(format!("unknown type `{}`, try one of: {}", other, TYPES.join(" "))),
+    };
+
+    let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    let mut out = tokens.join(" ");
+    for (label, value) in rows {
+        out.push_str(&format!("\n    {:<width$}  {}", label, value, width = width));
+    }
+    Ok(out)
+}
+
+// `acc + x - y` over and over. the values telescope, so nothing ever overflows and every policy
+// does exactly the same arithmetic, which leaves only the cost of checking
+fn time<P: Copy + Add<Output = P> + Sub<Output = P>>(values: &[P], start: P, rounds: usize) -> Duration {
+    let began = Instant::now();
+    let mut acc = start;
+    for i in 0..rounds {
+        let x = black_box(values[i % values.len()]);
+        let y = black_box(values[(i + 1) % values.len()]);
+        acc = black_box(acc + x - y);
+    }
+    black_box(acc);
+    began.elapsed()
+}
+
+fn bench_type<T>(rounds: usize) -> Vec<Duration>
+where
+    T: Integer + Add<Output = T> + Sub<Output = T>,
+{
+    // 0 to 39, starting at 40, stays between 0 and 120: fine for every type, even i8
+    let values: Vec<T> = (0..40).map(T::from_u8).collect();
+    let start = T::from_u8(40);
+
+    vec![
+        time(&values, start, rounds),
+        time(&values.iter().map(|&v| Wrapping(v)).collect::<Vec<_>>(), Wrapping(start), rounds),
+        time(&values.iter().map(|&v| Saturating(v)).collect::<Vec<_>>(), Saturating(start), rounds),
+        time(&values.iter().map(|&v| Checked::new(v)).collect::<Vec<_>>(), Checked::new(start), rounds),
+        time(&values.iter().map(|&v| Panicking(v)).collect::<Vec<_>>(), Panicking(start), rounds),
+    ]
+}
+
+// how long each policy takes for the same work, in nanoseconds per operation and relative to
+// the plain type
+pub fn bench(rounds: usize) -> String {
+    let mut out = String::new();
+    if cfg!(debug_assertions) {
+        out.push_str("this is a debug build, where even the plain type checks for overflow. run it with\n");
+        out.push_str("`cargo run --release --bin policy-lab -- bench` for numbers that mean something\n\n");
+    }
+    out.push_str(&format!("{} rounds of `acc + x - y` (ns per operation, and relative to the plain type)\n", rounds));
+    out.push_str(&format!("{:<7} {:>14} {:>14} {:>14} {:>14} {:>14}\n", "type", "plain", "Wrapping", "Saturating", "Checked", "Panicking"));
+
+    let operations = (rounds * 2) as f64;
+    for ty in TYPES.iter() {
+        let times = match *ty {
+            "i8" => bench_type::<i8>(rounds),
+            "i16" => bench_type::<i16>(rounds),
+            "i32" => bench_type::<i32>(rounds),
+            "i64" => bench_type::<i64>(rounds),
+            "i128" => bench_type::<i128>(rounds),
+            "isize" => bench_type::<isize>(rounds),
+            "u8" => bench_type::<u8>(rounds),
+            "u16" => bench_type::<u16>(rounds),
+            "u32" => bench_type::<u32>(rounds),
+            "u64" => bench_type::<u64>(rounds),
+            "u128" => bench_type::<u128>(rounds),
+            _ => bench_type::<usize>(rounds),
+        };
+        let plain = times[0].as_secs_f64().max(f64::MIN_POSITIVE);
+        let cells: Vec<String> = times.iter()
+            .map(|time| format!("{:.2} ({:.1}x)", time.as_secs_f64() * 1e9 / operations, time.as_secs_f64() / plain))
+            .collect();
+        out.push_str(&format!("{:<7} {:>14} {:>14} {:>14} {:>14} {:>14}\n", ty, cells[0], cells[1], cells[2], cells[3], cells[4]));
+    }
+    out.trim_end().to_string()
+}
@@ -0,0 +1,445 @@
+// number theory for the `num % 4`, `% 3`, `% 2` ladder in ch3/controlflow.rs. the ladder stops
+// at the first divisor it finds; here every divisor of a u64 gets listed along with why it
+// divides. getting there takes a factorization: trial division for the small primes, then
+// miller–rabin to tell whether what's left is prime and pollard's rho to split it if it isn't
+//
+// everything works on u64. products go through u128 so they can't overflow
+
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+// `None` when it doesn't fit in a u64
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+// every prime up to `limit`, crossing out the multiples of each prime from its square up
+pub fn sieve(limit: usize) -> Vec<u64> {
+    let mut composite = vec![false; limit + 1];
+    let mut primes = Vec::new();
+    for n in 2..=limit {
+        if composite[n] {
+            continue;
+        }
+        primes.push(n as u64);
+        let mut multiple = n.saturating_mul(n);
+        while multiple <= limit {
+            composite[multiple] = true;
+            multiple += n;
+        }
+    }
+    primes
+}
+
+// divides out every prime up to `limit` and gives back the prime powers found and whatever is
+// left over, which has no prime factors up to `limit`
+pub fn trial_division(mut n: u64, limit: u64) -> (Vec<(u64, u32)>, u64) {
+    let mut factors = Vec::new();
+    let mut divisor = 2;
+    while divisor <= limit && divisor.saturating_mul(divisor) <= n {
+        let mut power = 0;
+        while n.is_multiple_of(divisor) {
+            n /= divisor;
+            power += 1;
+        }
+        if power > 0 {
+            factors.push((divisor, power));
+        }
+        // 2, then the odd numbers. the odd composites never divide, their primes are gone
+        divisor += if divisor == 2 { 1 } else { 2 };
+    }
+    // what's left has no factor up to its square root, so it's prime, or too big to say yet
+    if n > 1 && divisor.saturating_mul(divisor) > n {
+        factors.push((n, 1));
+        n = 1;
+    }
+    (factors, n)
+}
+
+fn mul_mod(a: u64, b: u64, m: u64) -> u64 {
+    (u128::from(a) * u128::from(b) % u128::from(m)) as u64
+}
+
+fn pow_mod(mut base: u64, mut exponent: u64, m: u64) -> u64 {
+    let mut result = 1 % m;
+    base %= m;
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = mul_mod(result, base, m);
+        }
+        base = mul_mod(base, base, m);
+        exponent >>= 1;
+    }
+    result
+}
+
+// testing against the first twelve primes as bases is enough for every number below 2^64, so
+// for u64 this never guesses
+const WITNESSES: [u64; 12] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37];
+
+pub fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    for &p in WITNESSES.iter() {
+        if n.is_multiple_of(p) {
+            return n == p;
+        }
+    }
+    // n - 1 = d · 2^s with d odd
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+    WITNESSES.iter().all(|&a| {
+        let mut x = pow_mod(a, d, n);
+        if x == 1 || x == n - 1 {
+            return true;
+        }
+        for _ in 1..s {
+            x = mul_mod(x, x, n);
+            if x == n - 1 {
+                return true;
+            }
+        }
+        false
+    })
+}
+
+// a factor of the composite `n` other than 1 and n. x → x² + c wanders around mod n until it
+// repeats, and it repeats sooner mod any factor p, which gcd(|x - y|, n) picks up. this is
+// brent's version, which batches the gcds, and tries the next c if one cycles without luck
+pub fn pollard_rho(n: u64) -> u64 {
+    if n.is_multiple_of(2) {
+        return 2;
+    }
+    const BATCH: u64 = 128;
+    for c in 1..n {
+        let step = |x: u64| ((u128::from(x) * u128::from(x) + u128::from(c)) % u128::from(n)) as u64;
+        let (mut y, mut r, mut q, mut g) = (2, 1, 1, 1);
+        let (mut x, mut saved) = (y, y);
+        while g == 1 {
+            x = y;
+            for _ in 0..r {
+                y = step(y);
+            }
+            let mut k = 0;
+            while k < r && g == 1 {
+                saved = y;
+                for _ in 0..BATCH.min(r - k) {
+                    y = step(y);
+                    q = mul_mod(q, x.abs_diff(y), n);
+                }
+                g = gcd(q, n);
+                k += BATCH;
+            }
+            r *= 2;
+        }
+        // the batch overshot, so go back over it one step at a time
+        if g == n {
+            loop {
+                saved = step(saved);
+                g = gcd(x.abs_diff(saved), n);
+                if g > 1 {
+                    break;
+                }
+            }
+        }
+        if g != n {
+            return g;
+        }
+    }
+    unreachable!("{} is prime", n)
+}
+
+// the primes below this get found by trial division, the rest by pollard's rho
+const TRIAL_LIMIT: u64 = 1000;
+
+// the prime factorization, smallest prime first. 0 and 1 have no prime factors
+pub fn factorize(n: u64) -> Vec<(u64, u32)> {
+    if n < 2 {
+        return Vec::new();
+    }
+    let (mut factors, rest) = trial_division(n, TRIAL_LIMIT);
+    let mut pending = vec![rest];
+    let mut found = Vec::new();
+    while let Some(m) = pending.pop() {
+        if m == 1 {
+            continue;
+        }
+        if is_prime(m) {
+            found.push(m);
+        } else {
+            let d = pollard_rho(m);
+            pending.push(d);
+            pending.push(m / d);
+        }
+    }
+    found.sort_unstable();
+    for p in found {
+        match factors.last_mut() {
+            Some((last, power)) if *last == p => *power += 1,
+            _ => factors.push((p, 1)),
+        }
+    }
+    factors
+}
+
+// how many numbers from 1 to n share no factor with n: n times (1 - 1/p) for each prime p
+pub fn totient(n: u64) -> u64 {
+    if n == 0 {
+        return 0;
+    }
+    factorize(n).iter().map(|&(p, k)| p.pow(k - 1) * (p - 1)).product()
+}
+
+// every divisor of n in order, by picking a power of each prime factor. 0 is left out, since
+// everything divides it
+pub fn divisors(n: u64) -> Vec<u64> {
+    if n == 0 {
+        return Vec::new();
+    }
+    let mut all = vec![1];
+    for (p, k) in factorize(n) {
+        let mut more = Vec::new();
+        for &d in &all {
+            let mut power = d;
+            for _ in 0..k {
+                power *= p;
+                more.push(power);
+            }
+        }
+        all.extend(more);
+    }
+    all.sort_unstable();
+    all
+}
+
+// 2^3 × 3^2 × 5
+pub fn format_factors(factors: &[(u64, u32)]) -> String {
+    if factors.is_empty() {
+        return String::from("1");
+    }
+    let parts: Vec<String> = factors
+        .iter()
+        .map(|&(p, k)| if k == 1 { p.to_string() } else { format!("{}^{}", p, k) })
+        .collect();
+    parts.join(" × ")
+}
+
+fn digit_sum(n: u64) -> u64 {
+    n.to_string().bytes().map(|b| u64::from(b - b'0')).sum()
+}
+
+// the schoolbook reasons for the small divisors, from the digits alone
+fn digit_rule(n: u64, d: u64) -> Option<String> {
+    let last = |places: u32| n % 10u64.pow(places);
+    let reason = match d {
+        2 => format!("it ends in {}, which is even", last(1)),
+        3 => format!("its digits add up to {}, a multiple of 3", digit_sum(n)),
+        4 => format!("its last two digits, {:02}, are a multiple of 4", last(2)),
+        5 => format!("it ends in {}", last(1)),
+        6 => String::from("it's divisible by both 2 and 3"),
+        8 => format!("its last three digits, {:03}, are a multiple of 8", last(3)),
+        9 => format!("its digits add up to {}, a multiple of 9", digit_sum(n)),
+        10 => String::from("it ends in 0"),
+        11 => {
+            // the digits from the right, alternately added and subtracted
+            let alternating: i64 = n
+                .to_string()
+                .bytes()
+                .rev()
+                .enumerate()
+                .map(|(i, b)| if i % 2 == 0 { 1 } else { -1 } * i64::from(b - b'0'))
+                .sum();
+            format!("its alternating digit sum, {}, is a multiple of 11", alternating)
+        }
+        25 => format!("it ends in {:02}", last(2)),
+        100 => String::from("it ends in 00"),
+        _ => return None,
+    };
+    Some(reason)
+}
+
+// why d divides n: a digit rule if there is one, otherwise because d's prime factors are all
+// among n's
+fn reason(n: u64, factors: &[(u64, u32)], d: u64) -> String {
+    if d == 1 {
+        return String::from("1 divides everything");
+    }
+    if d == n {
+        return String::from("every number divides itself");
+    }
+    if let Some(rule) = digit_rule(n, d) {
+        return rule;
+    }
+    let own = factorize(d);
+    if let [(p, 1)] = own.as_slice() {
+        return format!("{} is one of its prime factors", p);
+    }
+    let used: Vec<String> = own
+        .iter()
+        .map(|&(p, k)| {
+            let has = factors.iter().find(|(q, _)| *q == p).map_or(0, |&(_, k)| k);
+            if has == 1 {
+                format!("its {}", p)
+            } else if has == k {
+                format!("all {} of its {}s", k, p)
+            } else if k == 1 {
+                format!("one of its {}s", p)
+            } else {
+                format!("{} of its {}s", k, p)
+            }
+        })
+        .collect();
+    format!("{} = {}, which takes {}", d, format_factors(&own), used.join(" and "))
+}
+
+// what the if/else-if ladder in ch3/controlflow.rs prints for this number
+fn ladder(n: u64) -> &'static str {
+    if n.is_multiple_of(4) {
+        "num is divisible by 4"
+    } else if n.is_multiple_of(3) {
+        "num is divisible by 3"
+    } else if n.is_multiple_of(2) {
+        "num is divisible by 2"
+    } else {
+        "num is not divisible by 4, 3, or 2"
+    }
+}
+
+// more than this many divisors (a u64 can have over 100000) and the list gets cut short
+const SHOWN: usize = 100;
+
+pub fn explain(n: u64) -> String {
+    if n == 0 {
+        return String::from("0 is divisible by every number: 0 = d × 0 for any d");
+    }
+    let factors = factorize(n);
+    let all = divisors(n);
+    let mut out = format!("{} = {}", n, format_factors(&factors));
+    let mut rows = vec![
+        ("prime", if is_prime(n) { String::from("yes") } else { String::from("no") }),
+        ("totient", totient(n).to_string()),
+        ("divisors", all.len().to_string()),
+        ("controlflow.rs", ladder(n).to_string()),
+    ];
+    let width = rows.iter().map(|(label, _)| label.len()).max().unwrap_or(0);
+    for (label, value) in rows.drain(..) {
+        out.push_str(&format!("\n    {:<width$}  {}", label, value, width = width));
+    }
+
+    out.push_str("\n\ndivisible by");
+    let width = all.iter().take(SHOWN).map(|d| d.to_string().len()).max().unwrap_or(0);
+    let reasons: Vec<String> = all.iter().take(SHOWN).map(|&d| reason(n, &factors, d)).collect();
+    let reason_width = reasons.iter().map(|r| r.chars().count()).max().unwrap_or(0);
+    for (&d, reason) in all.iter().zip(&reasons) {
+        let product = format!("{} = {} × {}", n, d, n / d);
+        out.push_str(&format!("\n    {:>width$}  {:<reason_width$}  {}", d, reason, product, width = width, reason_width = reason_width));
+    }
+    if all.len() > SHOWN {
+        out.push_str(&format!("\n    ... and {} more", all.len() - SHOWN));
+    }
+    out
+}
+
+// how the factors were found: which primes trial division turned up, and how miller–rabin and
+// pollard's rho dealt with the rest
+pub fn factor_steps(n: u64) -> String {
+    if n == 0 {
+        return String::from("0 has no prime factorization: every prime divides it, any number of times");
+    }
+    let mut lines = vec![format!("{} = {}", n, format_factors(&factorize(n)))];
+    if n < 2 {
+        return lines.join("\n");
+    }
+    let (small, rest) = trial_division(n, TRIAL_LIMIT);
+    if !small.is_empty() {
+        lines.push(format!("    trial division up to {} finds {}", TRIAL_LIMIT, format_factors(&small)));
+    }
+    if rest == 1 {
+        return lines.join("\n");
+    }
+    let mut pending = vec![rest];
+    while let Some(m) = pending.pop() {
+        if is_prime(m) {
+            lines.push(format!("    miller–rabin: {} is prime", m));
+        } else {
+            let d = pollard_rho(m);
+            lines.push(format!("    miller–rabin: {} is composite, pollard's rho splits it into {} × {}", m, d, m / d));
+            pending.push(m / d);
+            pending.push(d);
+        }
+    }
+    lines.join("\n")
+}
+
+// the sieve's primes, and for small limits the grid with the composites crossed out
+fn show_sieve(limit: usize) -> String {
+    let primes = sieve(limit);
+    let mut out = format!("{} primes up to {}", primes.len(), limit);
+    if limit <= 200 {
+        let width = limit.to_string().len();
+        let mut row = String::new();
+        for n in 1..=limit {
+            let cell = if primes.binary_search(&(n as u64)).is_ok() { n.to_string() } else { String::from("·") };
+            row.push_str(&format!("{:>width$} ", cell, width = width));
+            if n % 10 == 0 || n == limit {
+                out.push_str(&format!("\n    {}", row.trim_end()));
+                row.clear();
+            }
+        }
+    } else {
+        let shown: Vec<String> = primes.iter().rev().take(10).rev().map(|p| p.to_string()).collect();
+        out.push_str(&format!("\n    the last ten: {}", shown.join(" ")));
+    }
+    out
+}
+
+// the sieve needs a byte per number, so it stops here
+const SIEVE_LIMIT: usize = 100_000_000;
+
+fn number(text: &str) -> Result<u64, String> {
+    text.replace('_', "").parse().map_err(|_| format!("`{}` isn't a u64", text))
+}
+
+// `N` explains every divisor, and `factor N`, `prime N`, `gcd A B`, `lcm A B`, `totient N` and
+// `sieve N` do one thing each
+pub fn evaluate(line: &str) -> Result<String, String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    match tokens.as_slice() {
+        [n] => Ok(explain(number(n)?)),
+        ["factor", n] => Ok(factor_steps(number(n)?)),
+        ["prime", n] => {
+            let n = number(n)?;
+            Ok(format!("{} {} prime", n, if is_prime(n) { "is" } else { "isn't" }))
+        }
+        ["totient", n] => {
+            let n = number(n)?;
+            Ok(format!("φ({}) = {}", n, totient(n)))
+        }
+        ["gcd", a, b] => {
+            let (a, b) = (number(a)?, number(b)?);
+            Ok(format!("gcd({}, {}) = {}", a, b, gcd(a, b)))
+        }
+        ["lcm", a, b] => {
+            let (a, b) = (number(a)?, number(b)?);
+            match lcm(a, b) {
+                Some(l) => Ok(format!("lcm({}, {}) = {}", a, b, l)),
+                None => Err(format!("lcm({}, {}) doesn't fit in a u64", a, b)),
+            }
+        }
+        ["sieve", n] => match number(n)? {
+            n if n > SIEVE_LIMIT as u64 => Err(format!("the sieve only goes up to {}", SIEVE_LIMIT)),
+            n => Ok(show_sieve(n as usize)),
+        },
+        _ => Err(String::from("expected `N`, `factor N`, `prime N`, `totient N`, `gcd A B`, `lcm A B` or `sieve N`")),
+    }
+}
@@ -0,0 +1,565 @@
+// fractions over any integer type from the table, for the float examples in
+// ch3/varsandmutability.rs: `56.7 / 32.2` has to be rounded as an f64, but as a ratio it's
+// exactly 81/46. a `Ratio` is always in lowest terms with a positive denominator, so two equal
+// ratios have the same fields. nothing ever wraps: the `checked_*` methods return an error when
+// a step overflows, and the operators panic with that error, like the debug build would
+//
+// going from an f64 either takes its exact value, which is always some integer over a power of
+// two, or the simplest fraction (smallest denominator) within a tolerance, which is how 22/7
+// and 355/113 fall out of pi
+
+use std::cmp::Ordering;
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+use std::str::FromStr;
+
+use crate::bigint::BigUint;
+use crate::overflow::{Op, TYPES};
+use crate::policy::{Error, Integer};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Ratio<T> {
+    numer: T,
+    // always positive
+    denom: T,
+}
+
+fn one<T: Integer>() -> T {
+    T::from_u8(1)
+}
+
+fn overflow(op: Op) -> Error {
+    Error { op, by_zero: false }
+}
+
+fn by_zero() -> Error {
+    Error { op: Op::Div, by_zero: true }
+}
+
+// `a op b`, where overflowing is blamed on the ratio operation `blame`
+fn checked<T: Integer>(a: T, op: Op, b: T, blame: Op) -> Result<T, Error> {
+    T::checked(a, op, b).ok_or_else(|| overflow(blame))
+}
+
+// euclid's algorithm, which works for negative numbers too. the answer is made positive unless
+// it's MIN, which has no positive version
+fn gcd<T: Integer>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        // MIN % -1 is the only remainder that overflows, and it's 0
+        let r = T::checked(a, Op::Rem, b).unwrap_or(T::ZERO);
+        a = b;
+        b = r;
+    }
+    if a < T::ZERO {
+        T::checked(a, Op::Neg, a).unwrap_or(a)
+    } else {
+        a
+    }
+}
+
+// (quotient, remainder) with the quotient rounded down, so the remainder is never negative when
+// `b` is positive
+fn div_floor<T: Integer>(a: T, b: T) -> (T, T) {
+    let quotient = T::checked(a, Op::Div, b).expect("b is positive");
+    let remainder = T::checked(a, Op::Rem, b).expect("b is positive");
+    if remainder < T::ZERO {
+        (T::wrapping(quotient, Op::Sub, one()), T::wrapping(remainder, Op::Add, b))
+    } else {
+        (quotient, remainder)
+    }
+}
+
+impl<T: Integer> Ratio<T> {
+    // numer / denom in lowest terms, blaming any overflow on `op`
+    fn normalize(numer: T, denom: T, op: Op) -> Result<Ratio<T>, Error> {
+        if denom == T::ZERO {
+            return Err(by_zero());
+        }
+        if numer == T::ZERO {
+            return Ok(Ratio { numer, denom: one() });
+        }
+        let divisor = gcd(numer, denom);
+        let mut numer = checked(numer, Op::Div, divisor, op)?;
+        let mut denom = checked(denom, Op::Div, divisor, op)?;
+        if denom < T::ZERO {
+            numer = checked(numer, Op::Neg, numer, op)?;
+            denom = checked(denom, Op::Neg, denom, op)?;
+        }
+        Ok(Ratio { numer, denom })
+    }
+
+    pub fn new(numer: T, denom: T) -> Result<Ratio<T>, Error> {
+        Ratio::normalize(numer, denom, Op::Div)
+    }
+
+    pub fn from_integer(value: T) -> Ratio<T> {
+        Ratio { numer: value, denom: one() }
+    }
+
+    pub fn numer(&self) -> T {
+        self.numer
+    }
+
+    pub fn denom(&self) -> T {
+        self.denom
+    }
+
+    pub fn is_integer(&self) -> bool {
+        self.denom == one()
+    }
+
+    // a/b ± c/d over the smallest common denominator, which keeps the numbers small
+    fn add_or_sub(self, other: Ratio<T>, op: Op) -> Result<Ratio<T>, Error> {
+        let divisor = gcd(self.denom, other.denom);
+        let left = T::checked(self.denom, Op::Div, divisor).expect("the gcd divides it");
+        let right = T::checked(other.denom, Op::Div, divisor).expect("the gcd divides it");
+        let a = checked(self.numer, Op::Mul, right, op)?;
+        let b = checked(other.numer, Op::Mul, left, op)?;
+        let numer = checked(a, op, b, op)?;
+        let denom = checked(self.denom, Op::Mul, right, op)?;
+        Ratio::normalize(numer, denom, op)
+    }
+
+    pub fn checked_add(self, other: Ratio<T>) -> Result<Ratio<T>, Error> {
+        self.add_or_sub(other, Op::Add)
+    }
+
+    pub fn checked_sub(self, other: Ratio<T>) -> Result<Ratio<T>, Error> {
+        self.add_or_sub(other, Op::Sub)
+    }
+
+    // cancels across first, a/b · c/d = (a/g1 · c/g2) / (b/g2 · d/g1), so the products are as
+    // small as they can be
+    pub fn checked_mul(self, other: Ratio<T>) -> Result<Ratio<T>, Error> {
+        let g1 = gcd(self.numer, other.denom);
+        let g2 = gcd(other.numer, self.denom);
+        let numer = checked(checked(self.numer, Op::Div, g1, Op::Mul)?, Op::Mul, checked(other.numer, Op::Div, g2, Op::Mul)?, Op::Mul)?;
+        let denom = checked(checked(self.denom, Op::Div, g2, Op::Mul)?, Op::Mul, checked(other.denom, Op::Div, g1, Op::Mul)?, Op::Mul)?;
+        Ratio::normalize(numer, denom, Op::Mul)
+    }
+
+    pub fn recip(self) -> Result<Ratio<T>, Error> {
+        Ratio::normalize(self.denom, self.numer, Op::Div)
+    }
+
+    pub fn checked_div(self, other: Ratio<T>) -> Result<Ratio<T>, Error> {
+        let flipped = other.recip()?;
+        self.checked_mul(flipped).map_err(|_| overflow(Op::Div))
+    }
+
+    // what's left after taking out the whole number of `other`s, rounded toward zero like `%`
+    pub fn checked_rem(self, other: Ratio<T>) -> Result<Ratio<T>, Error> {
+        if other.numer == T::ZERO {
+            return Err(Error { op: Op::Rem, by_zero: true });
+        }
+        let blame = |_| overflow(Op::Rem);
+        let quotient = self.checked_div(other).map_err(blame)?;
+        let whole = T::checked(quotient.numer, Op::Div, quotient.denom).expect("the denominator is positive");
+        let taken = other.checked_mul(Ratio::from_integer(whole)).map_err(blame)?;
+        self.checked_sub(taken).map_err(blame)
+    }
+
+    pub fn checked_neg(self) -> Result<Ratio<T>, Error> {
+        Ok(Ratio { numer: checked(self.numer, Op::Neg, self.numer, Op::Neg)?, denom: self.denom })
+    }
+
+    pub fn to_f64(self) -> f64 {
+        self.numer.to_f64() / self.denom.to_f64()
+    }
+
+    // every finite f64 is some integer over a power of two. doubling is exact, so keep doubling
+    // until the fraction is gone
+    pub fn from_f64_exact(value: f64) -> Result<Ratio<T>, String> {
+        if !value.is_finite() {
+            return Err(format!("{} isn't a number any ratio can hold", value));
+        }
+        let (mut scaled, mut doublings) = (value, 0);
+        while scaled.fract() != 0.0 {
+            scaled *= 2.0;
+            doublings += 1;
+        }
+        let too_big = || format!("{:?} is exactly {}, which doesn't fit a Ratio<{}>", value, describe_exact(scaled, doublings), T::NAME);
+        let numer = T::from_f64(scaled).ok_or_else(too_big)?;
+        let denom = T::from_f64(2f64.powi(doublings)).ok_or_else(too_big)?;
+        Ratio::new(numer, denom).map_err(|e| e.to_string())
+    }
+
+    // the simplest ratio between value - tolerance and value + tolerance: the one with the
+    // smallest denominator, and the smallest numerator among those
+    pub fn approximate(value: f64, tolerance: f64) -> Result<Ratio<T>, String> {
+        if tolerance == 0.0 {
+            return Ratio::from_f64_exact(value);
+        }
+        if !value.is_finite() || !tolerance.is_finite() || tolerance < 0.0 {
+            return Err(String::from("the value has to be a number and the tolerance a positive one"));
+        }
+
+        let (low, high) = (value - tolerance, value + tolerance);
+        if low <= 0.0 && high >= 0.0 {
+            return Ok(Ratio::from_integer(T::ZERO));
+        }
+        let negative = high < 0.0;
+        let (low, high) = if negative { (-high, -low) } else { (low, high) };
+
+        // builds the fraction back up from the last term: [a0; a1, ..., an] = a0 + 1 / [a1; ...]
+        let terms = simplest_terms(low, high);
+        let no_fit = || format!("no Ratio<{}> within {} of {} fits", T::NAME, tolerance, value);
+        let (last, rest) = terms.split_last().expect("there's always a term");
+        let mut numer = T::from_f64(*last).ok_or_else(no_fit)?;
+        let mut denom = one::<T>();
+        for term in rest.iter().rev() {
+            let term = T::from_f64(*term).ok_or_else(no_fit)?;
+            let product = T::checked(term, Op::Mul, numer).ok_or_else(no_fit)?;
+            let next = T::checked(product, Op::Add, denom).ok_or_else(no_fit)?;
+            denom = numer;
+            numer = next;
+        }
+        if negative {
+            numer = T::checked(numer, Op::Neg, numer).ok_or_else(|| format!("{} is negative, and a Ratio<{}> can't be", value, T::NAME))?;
+        }
+        Ratio::new(numer, denom).map_err(|e| e.to_string())
+    }
+
+    // -7/3 is -2 1/3: the whole part rounds toward zero and takes the sign
+    pub fn mixed(&self) -> String {
+        let whole = T::checked(self.numer, Op::Div, self.denom).expect("the denominator is positive");
+        let rest = T::checked(self.numer, Op::Rem, self.denom).expect("the denominator is positive");
+        if rest == T::ZERO {
+            return whole.to_string();
+        }
+        if whole == T::ZERO {
+            return self.to_string();
+        }
+        let rest = T::checked(rest, Op::Neg, rest).filter(|_| rest < T::ZERO).unwrap_or(rest);
+        format!("{} {}/{}", whole, rest, self.denom)
+    }
+
+    // [a0; a1, a2, ...], where the fraction is a0 + 1/(a1 + 1/(a2 + ...)). only a0 can be
+    // negative or zero
+    pub fn continued_fraction(&self) -> Vec<T> {
+        let mut terms = Vec::new();
+        let (mut numer, mut denom) = (self.numer, self.denom);
+        loop {
+            let (whole, rest) = div_floor(numer, denom);
+            terms.push(whole);
+            if rest == T::ZERO {
+                return terms;
+            }
+            numer = denom;
+            denom = rest;
+        }
+    }
+}
+
+// `m·2^-k`, for the error when it doesn't fit. `{}` on an f64 only prints enough digits to tell
+// it apart from its neighbours, so the digits come from a BigUint: m·5^k / 10^k is the decimal
+// expansion, and when that gets long it stays a fraction, with m written out in full
+fn describe_exact(scaled: f64, doublings: i32) -> String {
+    let sign = if scaled < 0.0 { "-" } else { "" };
+    let bits = scaled.abs().to_bits();
+    let (exponent, fraction) = ((bits >> 52) as u32, bits & ((1 << 52) - 1));
+    // `scaled` is a whole number, so it's zero or normal and anything below the point is zero
+    let whole = match exponent {
+        0 => BigUint::from(0u32),
+        _ if exponent >= 1075 => BigUint::from(fraction | 1 << 52) * BigUint::from(2u32).pow(exponent - 1075),
+        _ => BigUint::from((fraction | 1 << 52) >> (1075 - exponent)),
+    };
+    let places = doublings as usize;
+    let digits = (&whole * &BigUint::from(5u32).pow(doublings as u32)).to_string();
+    if places == 0 {
+        return format!("{}{}", sign, digits);
+    }
+    if digits.len() <= 40 {
+        let digits = format!("{:0>width$}", digits, width = places + 1);
+        let (int, frac) = digits.split_at(digits.len() - places);
+        return format!("{}{}.{}", sign, int, frac);
+    }
+    format!("{}{} / 2^{}", sign, whole, doublings)
+}
+
+// the continued fraction terms of the simplest number between `low` and `high`, both positive.
+// if there's a whole number in the range the smallest one is simplest, otherwise both ends share
+// the whole part and the rest is the simplest number between the reciprocals of what's left
+fn simplest_terms(mut low: f64, mut high: f64) -> Vec<f64> {
+    let mut terms = Vec::new();
+    loop {
+        let whole = low.floor();
+        if whole == low || whole + 1.0 <= high || terms.len() == 64 {
+            terms.push(low.ceil());
+            return terms;
+        }
+        terms.push(whole);
+        let (next_low, next_high) = (1.0 / (high - whole), 1.0 / (low - whole));
+        low = next_low;
+        high = next_high;
+    }
+}
+
+impl<T: Integer> Ord for Ratio<T> {
+    // compares the whole parts, and if they match compares what's left by flipping both
+    // leftovers over, which walks the continued fraction of each side one term at a time.
+    // nothing gets multiplied, so it can't overflow the way a·d < c·b could
+    fn cmp(&self, other: &Ratio<T>) -> Ordering {
+        let (mut a, mut b, mut c, mut d) = (self.numer, self.denom, other.numer, other.denom);
+        let mut flipped = false;
+        loop {
+            let (q1, r1) = div_floor(a, b);
+            let (q2, r2) = div_floor(c, d);
+            let ordering = match (q1.cmp(&q2), r1 == T::ZERO, r2 == T::ZERO) {
+                (Ordering::Equal, true, true) => Ordering::Equal,
+                (Ordering::Equal, true, false) => Ordering::Less,
+                (Ordering::Equal, false, true) => Ordering::Greater,
+                (Ordering::Equal, false, false) => {
+                    // r1/b < r2/d exactly when b/r1 > d/r2
+                    a = b;
+                    b = r1;
+                    c = d;
+                    d = r2;
+                    flipped = !flipped;
+                    continue;
+                }
+                (ordering, _, _) => ordering,
+            };
+            return if flipped { ordering.reverse() } else { ordering };
+        }
+    }
+}
+
+impl<T: Integer> PartialOrd for Ratio<T> {
+    fn partial_cmp(&self, other: &Ratio<T>) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Integer> From<T> for Ratio<T> {
+    fn from(value: T) -> Ratio<T> {
+        Ratio::from_integer(value)
+    }
+}
+
+// one side of a ratio: an integer, or a decimal like 56.7, which is 567/10
+fn parse_part<T: Integer>(text: &str) -> Result<Ratio<T>, String> {
+    let invalid = || format!("`{}` isn't a number a Ratio<{}> can hold", text, T::NAME);
+    let cleaned = text.replace('_', "");
+    let (whole, fraction) = match cleaned.split_once('.') {
+        Some((whole, fraction)) => (whole, fraction),
+        None => (cleaned.as_str(), ""),
+    };
+    if !fraction.chars().all(|c| c.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let numer = format!("{}{}", whole, fraction).parse::<T>().map_err(|_| invalid())?;
+    let denom = format!("1{}", "0".repeat(fraction.len())).parse::<T>().map_err(|_| invalid())?;
+    Ratio::new(numer, denom).map_err(|e| e.to_string())
+}
+
+// `3/4`, `-7/3`, `5` or `56.7`, and either side of the slash can be a decimal
+impl<T: Integer> FromStr for Ratio<T> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Ratio<T>, String> {
+        match s.split_once('/') {
+            Some((numer, denom)) => {
+                let (numer, denom) = (parse_part::<T>(numer.trim())?, parse_part::<T>(denom.trim())?);
+                numer.checked_div(denom).map_err(|e| e.to_string())
+            }
+            None => parse_part(s.trim()),
+        }
+    }
+}
+
+impl<T: Integer> fmt::Display for Ratio<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.is_integer() {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+// the continued fraction, like [4; 2, 6, 7]
+pub fn format_continued<T: fmt::Display>(terms: &[T]) -> String {
+    let rest: Vec<String> = terms.iter().skip(1).map(|t| t.to_string()).collect();
+    if rest.is_empty() {
+        format!("[{}]", terms[0])
+    } else {
+        format!("[{}; {}]", terms[0], rest.join(", "))
+    }
+}
+
+macro_rules! operator {
+    ($trait:ident, $method:ident, $checked:ident) => {
+        impl<T: Integer> $trait for Ratio<T> {
+            type Output = Ratio<T>;
+
+            fn $method(self, other: Ratio<T>) -> Ratio<T> {
+                self.$checked(other).unwrap_or_else(|e| panic!("{}", e))
+            }
+        }
+    };
+}
+
+operator!(Add, add, checked_add);
+operator!(Sub, sub, checked_sub);
+operator!(Mul, mul, checked_mul);
+operator!(Div, div, checked_div);
+operator!(Rem, rem, checked_rem);
+
+impl<T: Integer> Neg for Ratio<T> {
+    type Output = Ratio<T>;
+
+    fn neg(self) -> Ratio<T> {
+        self.checked_neg().unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+// everything there is to say about one ratio, a row per line
+fn rows<T: Integer>(ratio: Ratio<T>) -> Vec<(&'static str, String)> {
+    vec![
+        ("ratio", ratio.to_string()),
+        ("mixed", ratio.mixed()),
+        ("continued", format_continued(&ratio.continued_fraction())),
+        ("f64", format!("{:?}", ratio.to_f64())),
+    ]
+}
+
+fn show(label: &str, rows: Vec<(&str, String)>) -> String {
+    let width = rows.iter().map(|(name, _)| name.len()).max().unwrap_or(0);
+    let mut out = String::from(label);
+    for (name, value) in rows {
+        out.push_str(&format!("\n    {:<width$}  {}", name, value, width = width));
+    }
+    out
+}
+
+fn ratio<T: Integer>(text: &str) -> Ratio<T> {
+    text.parse().expect("the demo only uses valid ratios")
+}
+
+// the division from ch3 done exactly, then fractions pulled out of f64s
+pub fn demo() -> String {
+    let quotient = ratio::<i64>("56.7") / ratio("32.2");
+    let mut division = rows(quotient);
+    division.push(("56.7 / 32.2", format!("{:?} as f64", 56.7 / 32.2)));
+    let mut sections = vec![show("56.7 / 32.2 (the division in ch3)", division)];
+
+    let pi = std::f64::consts::PI;
+    let mut approximations = Vec::new();
+    for tolerance in [1e-2, 1e-3, 1e-6, 1e-9].iter() {
+        let close = Ratio::<i64>::approximate(pi, *tolerance).expect("fits in i64");
+        approximations.push((*tolerance, close));
+    }
+    let mut section = String::from("the simplest fraction within a tolerance of pi");
+    for (tolerance, close) in approximations {
+        section.push_str(&format!("\n    {:<4}  {:<12}  off by {:e}", format!("{:e}", tolerance), close.to_string(), (close.to_f64() - pi).abs()));
+    }
+    sections.push(section);
+
+    // 0.1 can't be stored, so its exact value is a huge power of two underneath
+    let exact = Ratio::<i64>::from_f64_exact(0.1).expect("fits in i64");
+    let close = Ratio::<i64>::approximate(0.1, 1e-12).expect("fits in i64");
+    sections.push(format!("0.1 as an f64\n    exact        {}\n    within 1e-12  {}", exact, close));
+
+    // the same operations on types too small for them
+    let mut errors = String::from("overflow is an error, not a wrap");
+    let failures = vec![
+        ("u8 200 + 100", ratio::<u8>("200").checked_add(ratio("100")).map(|r| r.to_string())),
+        ("i8 1/127 + 1/126", ratio::<i8>("1/127").checked_add(ratio("1/126")).map(|r| r.to_string())),
+        ("i8 -128 / -1", ratio::<i8>("-128").checked_div(ratio("-1")).map(|r| r.to_string())),
+        ("i32 1 / 0", ratio::<i32>("1").checked_div(ratio("0")).map(|r| r.to_string())),
+    ];
+    for (label, result) in failures {
+        let shown = match result {
+            Ok(value) => value,
+            Err(e) => format!("error: {}", e),
+        };
+        errors.push_str(&format!("\n    {:<16}  {}", label, shown));
+    }
+    let pi_in_i8 = Ratio::<i8>::from_f64_exact(pi).unwrap_err();
+    errors.push_str(&format!("\n    {:<16}  error: {}", "i8 exact pi", pi_in_i8));
+    sections.push(errors);
+
+    sections.join("\n\n")
+}
+
+fn run<T: Integer>(tokens: &[&str]) -> Result<String, String> {
+    match tokens {
+        ["approx", value, rest @ ..] => {
+            let value: f64 = value.parse().map_err(|_| format!("`{}` isn't an f64", value))?;
+            let tolerance = match rest {
+                [] => 0.0,
+                [tolerance] => tolerance.parse().map_err(|_| format!("`{}` isn't a tolerance", tolerance))?,
+                _ => return Err(String::from("expected `approx X [TOLERANCE]`")),
+            };
+            let close = Ratio::<T>::approximate(value, tolerance)?;
+            let mut found = rows(close);
+            found.push(("off by", format!("{:e}", (close.to_f64() - value).abs())));
+            Ok(show(&tokens.join(" "), found))
+        }
+        [a] => Ok(show(a, rows(a.parse::<Ratio<T>>()?))),
+        [a, op, b] => {
+            let (x, y) = (a.parse::<Ratio<T>>()?, b.parse::<Ratio<T>>()?);
+            let float = match *op {
+                "+" => x.to_f64() + y.to_f64(),
+                "-" => x.to_f64() - y.to_f64(),
+                "*" => x.to_f64() * y.to_f64(),
+                "/" => x.to_f64() / y.to_f64(),
+                "%" => x.to_f64() % y.to_f64(),
+                "<" | ">" | "<=" | ">=" | "==" | "!=" => {
+                    let ordering = x.cmp(&y);
+                    let holds = match *op {
+                        "<" => ordering == Ordering::Less,
+                        ">" => ordering == Ordering::Greater,
+                        "<=" => ordering != Ordering::Greater,
+                        ">=" => ordering != Ordering::Less,
+                        "==" => ordering == Ordering::Equal,
+                        _ => ordering != Ordering::Equal,
+                    };
+                    let float = format!("{:?} {} {:?}", x.to_f64(), op, y.to_f64());
+                    return Ok(show(&tokens.join(" "), vec![("exact", holds.to_string()), ("f64", float)]));
+                }
+                other => return Err(format!("unknown operator `{}`, try + - * / % or a comparison", other)),
+            };
+            let result = match *op {
+                "+" => x.checked_add(y),
+                "-" => x.checked_sub(y),
+                "*" => x.checked_mul(y),
+                "/" => x.checked_div(y),
+                _ => x.checked_rem(y),
+            }
+            .map_err(|e| e.to_string())?;
+            let mut found = rows(result);
+            found.push(("as f64s", format!("{:?}", float)));
+            Ok(show(&tokens.join(" "), found))
+        }
+        _ => Err(String::from("expected `A OP B`, `A` or `approx X [TOLERANCE]`")),
+    }
+}
+
+// `[TYPE] A OP B`, `[TYPE] A` or `[TYPE] approx X [TOLERANCE]`, where the type is i64 unless one
+// from the table comes first
+pub fn evaluate(line: &str) -> Result<String, String> {
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let (ty, rest) = match tokens.split_first() {
+        Some((ty, rest)) if TYPES.contains(ty) => (*ty, rest),
+        Some(_) => ("i64", tokens.as_slice()),
+        None => return Err(String::from("nothing to evaluate")),
+    };
+
+    let report = match ty {
+        "i8" => run::<i8>(rest)?,
+        "i16" => run::<i16>(rest)?,
+        "i32" => run::<i32>(rest)?,
+        "i128" => run::<i128>(rest)?,
+        "isize" => run::<isize>(rest)?,
+        "u8" => run::<u8>(rest)?,
+        "u16" => run::<u16>(rest)?,
+        "u32" => run::<u32>(rest)?,
+        "u64" => run::<u64>(rest)?,
+        "u128" => run::<u128>(rest)?,
+        "usize" => run::<usize>(rest)?,
+        _ => run::<i64>(rest)?,
+    };
+    Ok(format!("Ratio<{}> {}", ty, report))
+}
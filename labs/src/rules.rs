@@ -0,0 +1,406 @@
+// the `num % 4`, `% 3`, `% 2` ladder in ch3/controlflow.rs as data instead of code. a rule file
+// is a list of rules, each a divisor (or some other test) and the label to print when it matches.
+// in "first" mode a number gets the label of the first rule that matches, like the ladder. in
+// "all" mode it gets every matching label joined together, which is how fizzbuzz works. a number
+// nothing matches gets the default
+//
+// the files are toml:
+//
+//     mode = "all"        # or "first", which is what you get if it's left out
+//     default = "{n}"     # `{n}` in any label becomes the number
+//     separator = ""      # goes between the labels in "all" mode
+//     from = 1            # the range to run over when none is given, 1 to 100 if left out
+//     to = 100
+//
+//     [[rule]]
+//     divisor = 3
+//     label = "Fizz"
+//
+//     [[rule]]
+//     when = { kind = "contains", digit = 7 }
+//     label = "Bazz"
+//
+// the tests for `when` are `divisible` (by), `remainder` (by, is), `prime`, `square` and
+// `contains` (digit). `divisor = 3` is short for `when = { kind = "divisible", by = 3 }`
+use std::fmt;
+use std::fs;
+
+use serde::Deserialize;
+
+use crate::primes;
+
+// shipped with the lab so `rule-lab fizzbuzz` works without writing a file first
+pub const BUNDLED: [(&str, &str); 5] = [
+    ("controlflow", include_str!("../rules/controlflow.toml")),
+    ("fizzbuzz", include_str!("../rules/fizzbuzz.toml")),
+    ("fizzbuzzbazz", include_str!("../rules/fizzbuzzbazz.toml")),
+    ("team", include_str!("../rules/team.toml")),
+    ("reordered", include_str!("../rules/reordered.toml")),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Mode {
+    #[default]
+    First,
+    All,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case", deny_unknown_fields)]
+pub enum Test {
+    Divisible { by: i64 },
+    Remainder { by: i64, is: i64 },
+    Prime,
+    Square,
+    Contains { digit: u8 },
+}
+
+#[derive(Debug, Clone)]
+pub struct Rule {
+    pub test: Test,
+    pub label: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct Rules {
+    pub mode: Mode,
+    pub rules: Vec<Rule>,
+    pub default: String,
+    pub separator: String,
+    pub from: i64,
+    pub to: i64,
+}
+
+// what's actually in the file, before the rules get checked
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleFile {
+    #[serde(default)]
+    mode: Mode,
+    default: Option<String>,
+    #[serde(default)]
+    separator: String,
+    from: Option<i64>,
+    to: Option<i64>,
+    #[serde(default)]
+    rule: Vec<RuleEntry>,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleEntry {
+    divisor: Option<i64>,
+    when: Option<Test>,
+    label: String,
+}
+
+impl Test {
+    pub fn matches(&self, n: i64) -> bool {
+        match *self {
+            Test::Divisible { by } => n % by == 0,
+            Test::Remainder { by, is } => n.rem_euclid(by) == is,
+            Test::Prime => n >= 2 && primes::is_prime(n as u64),
+            Test::Square => n >= 0 && n.isqrt() * n.isqrt() == n,
+            Test::Contains { digit } => n.unsigned_abs().to_string().contains(char::from(b'0' + digit)),
+        }
+    }
+
+    // the tests that only depend on n % m match the numbers with one remainder mod m. `None`
+    // for the others, and for a remainder that can't happen
+    fn residue(&self) -> Option<(u64, u64)> {
+        match *self {
+            Test::Divisible { by } => Some((by as u64, 0)),
+            Test::Remainder { by, is } if (0..by).contains(&is) => Some((by as u64, is as u64)),
+            _ => None,
+        }
+    }
+
+    // why a test matches nothing at all, for the ones that can
+    fn impossible(&self) -> Option<String> {
+        match *self {
+            Test::Remainder { by, is } if !(0..by).contains(&is) => {
+                Some(format!("the remainder after dividing by {} is always 0 to {}", by, by - 1))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Test {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Test::Divisible { by } => write!(f, "divisible by {}", by),
+            Test::Remainder { by, is } => write!(f, "n % {} == {}", by, is),
+            Test::Prime => write!(f, "prime"),
+            Test::Square => write!(f, "a perfect square"),
+            Test::Contains { digit } => write!(f, "has a {} in it", digit),
+        }
+    }
+}
+
+// a bundled set by name, or a file
+pub fn load(source: &str) -> Result<Rules, String> {
+    if let Some((_, text)) = BUNDLED.iter().find(|(name, _)| *name == source) {
+        return parse(text);
+    }
+    let text = fs::read_to_string(source).map_err(|e| {
+        let names: Vec<&str> = BUNDLED.iter().map(|(name, _)| *name).collect();
+        format!("can't read {} ({}), and it isn't one of {}", source, e, names.join(", "))
+    })?;
+    parse(&text).map_err(|e| format!("{}: {}", source, e))
+}
+
+pub fn parse(text: &str) -> Result<Rules, String> {
+    let file: RuleFile = toml::from_str(text).map_err(|e| e.to_string())?;
+    let mut rules = Vec::new();
+    for (i, entry) in file.rule.into_iter().enumerate() {
+        let test = match (entry.divisor, entry.when) {
+            (Some(by), None) => Test::Divisible { by },
+            (None, Some(test)) => test,
+            (Some(_), Some(_)) => return Err(format!("rule {} has both a `divisor` and a `when`, pick one", i + 1)),
+            (None, None) => return Err(format!("rule {} needs a `divisor` or a `when`", i + 1)),
+        };
+        match test {
+            Test::Divisible { by } | Test::Remainder { by, .. } if by <= 0 => {
+                return Err(format!("rule {} divides by {}, divisors have to be positive", i + 1, by));
+            }
+            Test::Contains { digit } if digit > 9 => {
+                return Err(format!("rule {} looks for the digit {}, which isn't one", i + 1, digit));
+            }
+            _ => {}
+        }
+        rules.push(Rule { test, label: entry.label });
+    }
+
+    let (from, to) = (file.from.unwrap_or(1), file.to.unwrap_or(100));
+    if from > to {
+        return Err(format!("the range {}..={} is empty", from, to));
+    }
+    Ok(Rules {
+        mode: file.mode,
+        rules,
+        default: file.default.unwrap_or_else(|| String::from("{n}")),
+        separator: file.separator,
+        from,
+        to,
+    })
+}
+
+// past this the shadowing check stops looking at every remainder and only compares rules in pairs
+const PERIOD_LIMIT: u64 = 1_000_000;
+
+impl Rules {
+    // which rules fire for n: the first match, or all of them
+    pub fn fired(&self, n: i64) -> Vec<usize> {
+        let matching = (0..self.rules.len()).filter(|&i| self.rules[i].test.matches(n));
+        match self.mode {
+            Mode::First => matching.take(1).collect(),
+            Mode::All => matching.collect(),
+        }
+    }
+
+    pub fn label(&self, n: i64) -> String {
+        let fired = self.fired(n);
+        let label = if fired.is_empty() {
+            self.default.clone()
+        } else {
+            let labels: Vec<&str> = fired.iter().map(|&i| self.rules[i].label.as_str()).collect();
+            labels.join(&self.separator)
+        };
+        label.replace("{n}", &n.to_string())
+    }
+
+    // everything that can never show up in the output, each with the reason. in "all" mode
+    // every rule gets its say, so only the impossible ones and the default can be dead
+    pub fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        for (j, rule) in self.rules.iter().enumerate() {
+            let name = format!("rule {} ({})", j + 1, rule.test);
+            if let Some(reason) = rule.test.impossible() {
+                problems.push(format!("{} can never fire: {}", name, reason));
+            } else if self.mode == Mode::First {
+                if let Some(earlier) = self.covered(j, Some(&rule.test)) {
+                    problems.push(format!("{} can never fire: {}", name, self.blame(&earlier, true, "everything it would")));
+                }
+            }
+        }
+        if let Some(all) = self.covered(self.rules.len(), None) {
+            problems.push(format!("the default can never show: {}", self.blame(&all, false, "every number")));
+        }
+        problems
+    }
+
+    // the rules before `j` that between them match every number `test` does, if they do. no
+    // test stands for every number, which is what the default is left with. it's exact for the
+    // remainder tests, and otherwise only catches a rule repeating an earlier one, so it can
+    // miss a dead rule but never flags a live one
+    fn covered(&self, j: usize, test: Option<&Test>) -> Option<Vec<usize>> {
+        if let Some(test) = test {
+            if let Some(i) = self.rules[..j].iter().position(|rule| rule.test == *test) {
+                return Some(vec![i]);
+            }
+        }
+        let (m, r) = test.map_or(Some((1, 0)), Test::residue).unwrap_or((1, 0));
+        let earlier: Vec<(usize, u64, u64)> = self.rules[..j]
+            .iter()
+            .enumerate()
+            .filter_map(|(i, rule)| rule.test.residue().map(|(a, s)| (i, a, s)))
+            .collect();
+
+        // everything repeats every lcm of the divisors, so one cycle of that settles it
+        let period = earlier
+            .iter()
+            .try_fold(m, |period, &(_, a, _)| primes::lcm(period, a).filter(|&l| l <= PERIOD_LIMIT));
+        let period = match period {
+            Some(period) => period,
+            None => {
+                // a single earlier rule covers this one when its divisor goes into ours and
+                // the remainders agree
+                let (i, _, _) = earlier.iter().find(|&&(_, a, s)| m.is_multiple_of(a) && r % a == s)?;
+                return Some(vec![*i]);
+            }
+        };
+        let mut used = Vec::new();
+        for x in (r..period).step_by(m as usize) {
+            let (i, _, _) = earlier.iter().find(|&&(_, a, s)| x % a == s)?;
+            if !used.contains(i) {
+                used.push(*i);
+            }
+        }
+        used.sort_unstable();
+        Some(used)
+    }
+
+    // `first` is for a rule, which the earlier ones get to before it. the default comes after
+    // all of them anyway
+    fn blame(&self, earlier: &[usize], first: bool, what: &str) -> String {
+        let names: Vec<String> = earlier.iter().map(|&i| format!("{} ({})", i + 1, self.rules[i].test)).collect();
+        match (names.as_slice(), first) {
+            ([one], true) => format!("rule {} comes first and matches {}", one, what),
+            ([one], false) => format!("rule {} matches {}", one, what),
+            ([rest @ .., last], true) => format!("rules {} and {} come first and between them match {}", rest.join(", "), last, what),
+            ([rest @ .., last], false) => format!("rules {} and {} between them match {}", rest.join(", "), last, what),
+            ([], _) => unreachable!("a rule is only covered by at least one other"),
+        }
+    }
+}
+
+fn describe_mode(rules: &Rules) -> String {
+    match rules.mode {
+        Mode::First => format!("the first matching label, otherwise {:?}", rules.default),
+        Mode::All => format!("every matching label, joined with {:?}, otherwise {:?}", rules.separator, rules.default),
+    }
+}
+
+// counting past this would take a while, so a longer range only counts its start
+const COUNT_LIMIT: i64 = 1_000_000;
+
+// the rules, how often each one fires over the range, and anything the validator found
+pub fn check(name: &str, rules: &Rules, from: i64, to: i64) -> String {
+    let to = to.min(from.saturating_add(COUNT_LIMIT - 1));
+    let mut counts = vec![0u64; rules.rules.len() + 1];
+    for n in from..=to {
+        let fired = rules.fired(n);
+        if fired.is_empty() {
+            counts[rules.rules.len()] += 1;
+        }
+        for i in fired {
+            counts[i] += 1;
+        }
+    }
+
+    let mut rows = vec![(String::from("rule"), String::from("test"), String::from("label"), format!("fires in {}..={}", from, to))];
+    for (i, rule) in rules.rules.iter().enumerate() {
+        rows.push(((i + 1).to_string(), rule.test.to_string(), format!("{:?}", rule.label), counts[i].to_string()));
+    }
+    rows.push((String::from("default"), String::new(), format!("{:?}", rules.default), counts[rules.rules.len()].to_string()));
+    let width = |column: fn(&(String, String, String, String)) -> &String| rows.iter().map(|row| column(row).chars().count()).max().unwrap_or(0);
+    let (first, second, third) = (width(|row| &row.0), width(|row| &row.1), width(|row| &row.2));
+
+    let mut out = format!("{}: {}", name, describe_mode(rules));
+    for (number, test, label, count) in &rows {
+        out.push_str(&format!("\n    {:<first$}  {:<second$}  {:<third$}  {}", number, test, label, count, first = first, second = second, third = third));
+    }
+    let problems = rules.validate();
+    if problems.is_empty() {
+        out.push_str("\nno problems: every rule and the default can show up");
+    } else {
+        out.push_str("\nproblems");
+        for problem in problems {
+            out.push_str(&format!("\n    {}", problem));
+        }
+    }
+    out
+}
+
+// more lines than anyone wants to scroll through
+const RUN_LIMIT: i128 = 100_000;
+
+// every label over the range, one per line
+pub fn run(rules: &Rules, from: i64, to: i64) -> Result<String, String> {
+    let count = i128::from(to) - i128::from(from) + 1;
+    if count > RUN_LIMIT {
+        return Err(format!("that's {} lines, keep it to {}", count, RUN_LIMIT));
+    }
+    let labels: Vec<String> = (from..=to).map(|n| rules.label(n)).collect();
+    Ok(labels.join("\n"))
+}
+
+pub fn demo() -> String {
+    let mut sections = Vec::new();
+    for name in ["controlflow", "fizzbuzz"].iter() {
+        let rules = load(name).expect("bundled rules are valid");
+        let to = if *name == "fizzbuzz" { 15 } else { rules.to };
+        let labels = run(&rules, rules.from, to).expect("a short range");
+        sections.push(format!("{} over {}..={}\n{}", name, rules.from, to, labels));
+    }
+    let reordered = load("reordered").expect("bundled rules are valid");
+    sections.push(check("reordered", &reordered, reordered.from, reordered.to));
+    sections.join("\n\n")
+}
+
+fn number(text: &str) -> Result<i64, String> {
+    text.parse().map_err(|_| format!("`{}` isn't a number that fits in an i64", text))
+}
+
+// `N`, `A B`, `A..B` or `A..=B`
+fn range(words: &[&str]) -> Result<(i64, i64), String> {
+    match words {
+        [single] => {
+            if let Some((from, to)) = single.split_once("..=") {
+                Ok((number(from)?, number(to)?))
+            } else if let Some((from, to)) = single.split_once("..") {
+                Ok((number(from)?, number(to)?.checked_sub(1).ok_or("the range is empty")?))
+            } else {
+                let n = number(single)?;
+                Ok((n, n))
+            }
+        }
+        [from, to] => Ok((number(from)?, number(to)?)),
+        _ => Err(String::from("give the range as `N`, `FROM TO`, `FROM..TO` or `FROM..=TO`")),
+    }
+}
+
+// `[check] SOURCE [RANGE]`, where the source is a bundled set or a file
+pub fn evaluate(line: &str) -> Result<String, String> {
+    let mut words: Vec<&str> = line.split_whitespace().collect();
+    let checking = words.first() == Some(&"check");
+    if checking {
+        words.remove(0);
+    }
+    let (source, bounds) = match words.split_first() {
+        Some(split) => split,
+        None => return Err(String::from("give a rule file or the name of a bundled set")),
+    };
+    let rules = load(source)?;
+    let (from, to) = if bounds.is_empty() { (rules.from, rules.to) } else { range(bounds)? };
+    if from > to {
+        return Err(format!("the range {}..={} is empty", from, to));
+    }
+    if checking {
+        Ok(check(source, &rules, from, to))
+    } else {
+        run(&rules, from, to)
+    }
+}